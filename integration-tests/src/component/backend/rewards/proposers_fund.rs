@@ -49,11 +49,11 @@ pub struct ProposalRewardsTestConfig {
 
 impl ProposalRewardsTestConfig {
     pub fn voter_funds(mut self, voters_funds: &[u64]) -> Self {
+        let mut rng = rand::thread_rng();
         self.config_builder = self.config_builder.block0_initials(Block0Initials(
             voters_funds
                 .iter()
-                .cloned()
-                .map(Block0Initial::new_random_wallet)
+                .map(|funds| Block0Initial::new_random_wallet(*funds, &mut rng))
                 .collect(),
         ));
         self
@@ -16,6 +16,8 @@ pub enum Error {
     CertFileDoesNotExist,
     #[error("key file does not exists")]
     KeyFileDoesNotExist,
+    #[error("ca file does not exists")]
+    CaFileDoesNotExist,
     #[error(transparent)]
     Io(#[from] std::io::Error),
 }
@@ -39,6 +41,10 @@ pub struct ValigrindStartupCommand {
 
     #[structopt(long = "key")]
     pub key_path: Option<PathBuf>,
+
+    /// CA certificate which signed server certificate (informational only)
+    #[structopt(long = "ca")]
+    pub ca_path: Option<PathBuf>,
 }
 
 impl ValigrindStartupCommand {
@@ -62,9 +68,16 @@ impl ValigrindStartupCommand {
                 return Err(Error::CertFileDoesNotExist);
             }
 
+            if let Some(ca_path) = &self.ca_path {
+                if !ca_path.exists() {
+                    return Err(Error::CaFileDoesNotExist);
+                }
+            }
+
             let certs = Certs {
                 key_path,
                 cert_path,
+                ca_path: self.ca_path,
             };
 
             return Ok(ProxyServerStub::new_https(
@@ -106,6 +119,8 @@ impl Protocol {
 pub struct Certs {
     pub key_path: PathBuf,
     pub cert_path: PathBuf,
+    #[serde(default)]
+    pub ca_path: Option<PathBuf>,
 }
 
 impl From<Certs> for Protocol {
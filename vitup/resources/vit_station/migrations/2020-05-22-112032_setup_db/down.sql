@@ -1,5 +1,6 @@
 -- This file should undo anything in `up.sql`
 DROP TABLE IF EXISTS proposals;
+DROP TABLE IF EXISTS proposals_voteplans;
 DROP TABLE IF EXISTS funds;
 DROP TABLE IF EXISTS voteplans;
 DROP TABLE IF EXISTS api_tokens;
@@ -8,5 +9,4 @@ DROP TABLE IF EXISTS proposal_simple_challenge;
 DROP TABLE IF EXISTS proposal_community_choice_challenge;
 DROP TABLE IF EXISTS community_advisors_reviews;
 DROP VIEW IF EXISTS full_proposals_info;
-DROP TABLE IF EXISTS goals;
 
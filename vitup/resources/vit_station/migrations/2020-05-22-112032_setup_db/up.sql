@@ -78,8 +78,7 @@ create table voteplans
     chain_committee_end_time BIGINT NOT NULL,
     chain_voteplan_payload VARCHAR NOT NULL,
     chain_vote_encryption_key VARCHAR NOT NULL,
-    fund_id INTEGER NOT NULL,
-    token_identifier VARCHAR NOT NULL
+    fund_id INTEGER NOT NULL
 );
 
 create table api_tokens
@@ -117,22 +116,6 @@ create table community_advisors_reviews (
   ranking INTEGER NOT NULL
 );
 
-create table goals
-(
-    id INTEGER NOT NULL
-        primary key autoincrement,
-    goal_name VARCHAR NOT NULL,
-    fund_id INTEGER NOT NULL,
-    FOREIGN KEY(fund_id) REFERENCES funds(id)
-);
-
-create table groups (
-    fund_id INTEGER NOT NULL,
-    token_identifier VARCHAR NOT NULL,
-    group_id VARCHAR NOT NULL,
-    PRIMARY KEY(token_identifier, fund_id)
-);
-
 CREATE VIEW full_proposals_info
 AS
 SELECT
@@ -151,14 +134,12 @@ SELECT
     proposal_community_choice_challenge.proposal_goal,
     proposal_community_choice_challenge.proposal_metrics,
     proposals_voteplans.chain_proposal_index,
-    proposals_voteplans.chain_voteplan_id,
-    groups.group_id
+    proposals_voteplans.chain_voteplan_id
 FROM
     proposals
         INNER JOIN proposals_voteplans ON proposals.proposal_id = proposals_voteplans.proposal_id
         INNER JOIN voteplans ON proposals_voteplans.chain_voteplan_id = voteplans.chain_voteplan_id
         INNER JOIN challenges on challenges.id = proposals.challenge_id
-        INNER JOIN groups on voteplans.token_identifier = groups.token_identifier
         LEFT JOIN proposal_simple_challenge
             on proposals.proposal_id = proposal_simple_challenge.proposal_id
             and (challenges.challenge_type = 'simple' or challenges.challenge_type = 'native')
@@ -0,0 +1,8 @@
+create table goals
+(
+    id INTEGER NOT NULL
+        primary key autoincrement,
+    goal_name VARCHAR NOT NULL,
+    fund_id INTEGER NOT NULL,
+    FOREIGN KEY(fund_id) REFERENCES funds(id)
+);
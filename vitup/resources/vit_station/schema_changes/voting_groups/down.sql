@@ -0,0 +1,59 @@
+-- This file should undo anything in `up.sql`
+DROP VIEW IF EXISTS full_proposals_info;
+DROP TABLE IF EXISTS groups;
+
+create table voteplans_without_groups
+(
+    id INTEGER NOT NULL
+        primary key autoincrement,
+    chain_voteplan_id VARCHAR NOT NULL
+        unique,
+    chain_vote_start_time BIGINT NOT NULL,
+    chain_vote_end_time BIGINT NOT NULL,
+    chain_committee_end_time BIGINT NOT NULL,
+    chain_voteplan_payload VARCHAR NOT NULL,
+    chain_vote_encryption_key VARCHAR NOT NULL,
+    fund_id INTEGER NOT NULL
+);
+
+INSERT INTO voteplans_without_groups
+SELECT id, chain_voteplan_id, chain_vote_start_time, chain_vote_end_time, chain_committee_end_time,
+    chain_voteplan_payload, chain_vote_encryption_key, fund_id
+FROM voteplans;
+
+DROP TABLE voteplans;
+ALTER TABLE voteplans_without_groups RENAME TO voteplans;
+
+CREATE VIEW full_proposals_info
+AS
+SELECT
+    proposals.*,
+    ifnull(reviews_count, 0) as reviews_count,
+    voteplans.chain_vote_start_time,
+    voteplans.chain_vote_end_time,
+    voteplans.chain_committee_end_time,
+    voteplans.chain_voteplan_payload,
+    voteplans.chain_vote_encryption_key,
+    voteplans.fund_id,
+    challenges.challenge_type,
+    proposal_simple_challenge.proposal_solution,
+    proposal_community_choice_challenge.proposal_brief,
+    proposal_community_choice_challenge.proposal_importance,
+    proposal_community_choice_challenge.proposal_goal,
+    proposal_community_choice_challenge.proposal_metrics,
+    proposals_voteplans.chain_proposal_index,
+    proposals_voteplans.chain_voteplan_id
+FROM
+    proposals
+        INNER JOIN proposals_voteplans ON proposals.proposal_id = proposals_voteplans.proposal_id
+        INNER JOIN voteplans ON proposals_voteplans.chain_voteplan_id = voteplans.chain_voteplan_id
+        INNER JOIN challenges on challenges.id = proposals.challenge_id
+        LEFT JOIN proposal_simple_challenge
+            on proposals.proposal_id = proposal_simple_challenge.proposal_id
+            and (challenges.challenge_type = 'simple' or challenges.challenge_type = 'native')
+        LEFT JOIN proposal_community_choice_challenge
+            on proposals.proposal_id = proposal_community_choice_challenge.proposal_id
+            and challenges.challenge_type = 'community-choice'
+        LEFT JOIN (SELECT proposal_id as review_proposal_id, COUNT (DISTINCT assessor) as reviews_count FROM community_advisors_reviews GROUP BY proposal_id)
+            on proposals.proposal_id = review_proposal_id;
+
@@ -0,0 +1,51 @@
+-- existing vote plans are put into single 'direct' group with empty token,
+-- as databases before voting groups did not distinguish voters
+ALTER TABLE voteplans ADD COLUMN token_identifier VARCHAR NOT NULL DEFAULT '';
+
+create table groups (
+    fund_id INTEGER NOT NULL,
+    token_identifier VARCHAR NOT NULL,
+    group_id VARCHAR NOT NULL,
+    PRIMARY KEY(token_identifier, fund_id)
+);
+
+INSERT INTO groups (fund_id, token_identifier, group_id)
+SELECT DISTINCT fund_id, token_identifier, 'direct' FROM voteplans;
+
+DROP VIEW IF EXISTS full_proposals_info;
+
+CREATE VIEW full_proposals_info
+AS
+SELECT
+    proposals.*,
+    ifnull(reviews_count, 0) as reviews_count,
+    voteplans.chain_vote_start_time,
+    voteplans.chain_vote_end_time,
+    voteplans.chain_committee_end_time,
+    voteplans.chain_voteplan_payload,
+    voteplans.chain_vote_encryption_key,
+    voteplans.fund_id,
+    challenges.challenge_type,
+    proposal_simple_challenge.proposal_solution,
+    proposal_community_choice_challenge.proposal_brief,
+    proposal_community_choice_challenge.proposal_importance,
+    proposal_community_choice_challenge.proposal_goal,
+    proposal_community_choice_challenge.proposal_metrics,
+    proposals_voteplans.chain_proposal_index,
+    proposals_voteplans.chain_voteplan_id,
+    groups.group_id
+FROM
+    proposals
+        INNER JOIN proposals_voteplans ON proposals.proposal_id = proposals_voteplans.proposal_id
+        INNER JOIN voteplans ON proposals_voteplans.chain_voteplan_id = voteplans.chain_voteplan_id
+        INNER JOIN challenges on challenges.id = proposals.challenge_id
+        INNER JOIN groups on voteplans.token_identifier = groups.token_identifier
+        LEFT JOIN proposal_simple_challenge
+            on proposals.proposal_id = proposal_simple_challenge.proposal_id
+            and (challenges.challenge_type = 'simple' or challenges.challenge_type = 'native')
+        LEFT JOIN proposal_community_choice_challenge
+            on proposals.proposal_id = proposal_community_choice_challenge.proposal_id
+            and challenges.challenge_type = 'community-choice'
+        LEFT JOIN (SELECT proposal_id as review_proposal_id, COUNT (DISTINCT assessor) as reviews_count FROM community_advisors_reviews GROUP BY proposal_id)
+            on proposals.proposal_id = review_proposal_id;
+
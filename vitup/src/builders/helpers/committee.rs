@@ -0,0 +1,410 @@
+use bech32::{FromBase32, ToBase32, Variant};
+use chain_crypto::bech32::Bech32;
+use chain_crypto::{Ed25519, SecretKey};
+use chain_impl_mockchain::certificate::{
+    DecryptedPrivateTally, DecryptedPrivateTallyError, DecryptedPrivateTallyProposal,
+};
+use chain_impl_mockchain::vote::{CommitteeId, PrivateTallyState, Tally, VotePlanStatus};
+use chain_vote::committee::{
+    MemberCommunicationKey, MemberCommunicationPublicKey, MemberPublicKey, MemberSecretKey,
+    MemberState,
+};
+use chain_vote::{Crs, ElectionPublicKey, EncryptedTally, TallyOptimizationTable};
+use jormungandr_lib::interfaces::CommitteeIdDef;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// suffix of member secrets files written next to committee file
+pub const MEMBER_SECRETS_SUFFIX: &str = "_secrets.json";
+
+/// bech32 prefixes used by jcli for vote committee keys
+pub const MEMBER_SK_HRP: &str = "p256k1_membersk";
+pub const MEMBER_PK_HRP: &str = "p256k1_memberpk";
+pub const COMMUNICATION_SK_HRP: &str = "p256k1_vcommsk";
+pub const COMMUNICATION_PK_HRP: &str = "p256k1_vcommpk";
+
+/// Public part of election committee, which can be referenced from
+/// `vote_plan.election_committee` in configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElectionCommitteeDef {
+    pub threshold: usize,
+    /// bech32 encoded election public key, used to encrypt private votes
+    pub election_key: String,
+    /// committee ids ready to be put into `blockchain.committees`
+    pub committee_ids: Vec<CommitteeIdDef>,
+    /// bech32 encoded member public keys, in member order
+    pub member_keys: Vec<String>,
+}
+
+impl ElectionCommitteeDef {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(Into::into)
+    }
+
+    pub fn member_public_keys(&self) -> Result<Vec<MemberPublicKey>, Error> {
+        self.member_keys
+            .iter()
+            .map(|key| {
+                let bytes = decode(MEMBER_PK_HRP, key)?;
+                MemberPublicKey::from_bytes(&bytes).ok_or_else(|| Error::InvalidKey(key.clone()))
+            })
+            .collect()
+    }
+
+    /// Secrets of all members, read from files exported next to committee file.
+    /// They are ordered as member keys, which is the order of decrypt shares in tally
+    pub fn member_secrets<P: AsRef<Path>>(
+        &self,
+        committee_file: P,
+    ) -> Result<Vec<CommitteeMemberSecrets>, Error> {
+        let dir = committee_file
+            .as_ref()
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let mut files: Vec<PathBuf> = std::fs::read_dir(&dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .map_or(false, |name| name.ends_with(MEMBER_SECRETS_SUFFIX))
+            })
+            .collect();
+        files.sort();
+        let secrets = files
+            .iter()
+            .map(CommitteeMemberSecrets::from_file)
+            .collect::<Result<Vec<_>, _>>()?;
+        self.order_secrets(secrets)
+    }
+
+    /// Matches secrets with member keys. Tally can be decrypted only with share of every member
+    pub fn order_secrets(
+        &self,
+        secrets: Vec<CommitteeMemberSecrets>,
+    ) -> Result<Vec<CommitteeMemberSecrets>, Error> {
+        self.member_keys
+            .iter()
+            .enumerate()
+            .map(|(index, member_pk)| {
+                secrets
+                    .iter()
+                    .find(|secrets| secrets.member_pk == *member_pk)
+                    .cloned()
+                    .ok_or(Error::MissingMemberSecrets { index })
+            })
+            .collect()
+    }
+
+    /// Decrypts private tally of vote plan using members secret keys, so it can be
+    /// sent in private vote tally certificate
+    pub fn decrypt_tally<R: RngCore + CryptoRng>(
+        &self,
+        status: &VotePlanStatus,
+        secrets: &[CommitteeMemberSecrets],
+        rng: &mut R,
+    ) -> Result<DecryptedPrivateTally, Error> {
+        let member_pks = self.member_public_keys()?;
+        let member_sks = secrets
+            .iter()
+            .map(CommitteeMemberSecrets::member_secret_key)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let proposals = status
+            .proposals
+            .iter()
+            .map(|proposal| match &proposal.tally {
+                Tally::Private {
+                    state:
+                        PrivateTallyState::Encrypted {
+                            encrypted_tally,
+                            total_stake,
+                        },
+                } => decrypt_proposal(
+                    encrypted_tally,
+                    u64::from(*total_stake),
+                    &member_pks,
+                    &member_sks,
+                    rng,
+                ),
+                _ => Err(Error::NotEncrypted(proposal.index)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        DecryptedPrivateTally::new(proposals).map_err(Into::into)
+    }
+}
+
+fn decrypt_proposal<R: RngCore + CryptoRng>(
+    encrypted_tally: &EncryptedTally,
+    max_votes: u64,
+    member_pks: &[MemberPublicKey],
+    member_sks: &[MemberSecretKey],
+    rng: &mut R,
+) -> Result<DecryptedPrivateTallyProposal, Error> {
+    let decrypt_shares: Vec<_> = member_sks
+        .iter()
+        .map(|secret_key| encrypted_tally.partial_decrypt(&mut *rng, secret_key))
+        .collect();
+    let table = TallyOptimizationTable::generate_with_balance(max_votes, 1);
+    let tally = encrypted_tally
+        .validate_partial_decryptions(member_pks, &decrypt_shares)
+        .and_then(|validated| validated.decrypt_tally(max_votes, &table))
+        .map_err(|e| Error::Tally(format!("{:?}", e)))?;
+    Ok(DecryptedPrivateTallyProposal {
+        decrypt_shares: decrypt_shares.into_boxed_slice(),
+        tally_result: tally.votes.into_boxed_slice(),
+    })
+}
+
+/// All keys of single committee member. Should be distributed only to that member
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitteeMemberSecrets {
+    pub alias: String,
+    pub index: usize,
+    pub committee_id: CommitteeIdDef,
+    /// bech32 ed25519 secret key of wallet which committee id is derived from
+    pub committee_sk: String,
+    pub communication_sk: String,
+    pub communication_pk: String,
+    pub member_sk: String,
+    pub member_pk: String,
+}
+
+impl CommitteeMemberSecrets {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(Into::into)
+    }
+
+    pub fn member_secret_key(&self) -> Result<MemberSecretKey, Error> {
+        let bytes = decode(MEMBER_SK_HRP, &self.member_sk)?;
+        MemberSecretKey::from_bytes(&bytes).ok_or_else(|| Error::InvalidKey(self.alias.clone()))
+    }
+}
+
+pub struct CommitteeMember {
+    pub alias: String,
+    pub committee_sk: SecretKey<Ed25519>,
+    pub communication_key: MemberCommunicationKey,
+    pub state: MemberState,
+}
+
+impl CommitteeMember {
+    pub fn committee_id(&self) -> CommitteeIdDef {
+        CommitteeIdDef::from(CommitteeId::from(self.committee_sk.to_public()))
+    }
+}
+
+/// N-member committee with keys generated for given `threshold`. Tally decryption
+/// needs a share from every member
+pub struct ElectionCommittee {
+    pub threshold: usize,
+    pub members: Vec<CommitteeMember>,
+}
+
+impl ElectionCommittee {
+    pub fn generate<R: RngCore + CryptoRng>(
+        alias_prefix: &str,
+        members: usize,
+        threshold: usize,
+        crs: &[u8],
+        rng: &mut R,
+    ) -> Result<Self, Error> {
+        if threshold == 0 || threshold > members {
+            return Err(Error::InvalidThreshold { threshold, members });
+        }
+
+        let crs = Crs::from_hash(crs);
+        let communication_keys: Vec<MemberCommunicationKey> = (0..members)
+            .map(|_| MemberCommunicationKey::new(&mut *rng))
+            .collect();
+        let communication_pks: Vec<MemberCommunicationPublicKey> = communication_keys
+            .iter()
+            .map(|key| key.to_public())
+            .collect();
+
+        let members = communication_keys
+            .into_iter()
+            .enumerate()
+            .map(|(index, communication_key)| CommitteeMember {
+                alias: format!("{}_{}", alias_prefix, index + 1),
+                committee_sk: SecretKey::generate(&mut *rng),
+                state: MemberState::new(&mut *rng, threshold, &crs, &communication_pks, index),
+                communication_key,
+            })
+            .collect();
+
+        Ok(Self { threshold, members })
+    }
+
+    pub fn member_public_keys(&self) -> Vec<MemberPublicKey> {
+        self.members
+            .iter()
+            .map(|member| member.state.public_key())
+            .collect()
+    }
+
+    pub fn election_key(&self) -> ElectionPublicKey {
+        ElectionPublicKey::from_participants(&self.member_public_keys())
+    }
+
+    pub fn def(&self) -> ElectionCommitteeDef {
+        ElectionCommitteeDef {
+            threshold: self.threshold,
+            election_key: self.election_key().to_bech32_str(),
+            committee_ids: self
+                .members
+                .iter()
+                .map(|member| member.committee_id())
+                .collect(),
+            member_keys: self
+                .member_public_keys()
+                .iter()
+                .map(|key| encode(MEMBER_PK_HRP, &key.to_bytes()))
+                .collect(),
+        }
+    }
+
+    pub fn secrets(&self) -> Vec<CommitteeMemberSecrets> {
+        self.members
+            .iter()
+            .enumerate()
+            .map(|(index, member)| CommitteeMemberSecrets {
+                alias: member.alias.clone(),
+                index,
+                committee_id: member.committee_id(),
+                committee_sk: member.committee_sk.to_bech32_str(),
+                communication_sk: encode(
+                    COMMUNICATION_SK_HRP,
+                    &member.communication_key.to_bytes(),
+                ),
+                communication_pk: encode(
+                    COMMUNICATION_PK_HRP,
+                    &member.communication_key.to_public().to_bytes(),
+                ),
+                member_sk: encode(MEMBER_SK_HRP, &member.state.secret_key().to_bytes()),
+                member_pk: encode(MEMBER_PK_HRP, &member.state.public_key().to_bytes()),
+            })
+            .collect()
+    }
+}
+
+fn encode(hrp: &str, bytes: &[u8]) -> String {
+    bech32::encode(hrp, bytes.to_base32(), Variant::Bech32).unwrap()
+}
+
+fn decode(hrp: &str, value: &str) -> Result<Vec<u8>, Error> {
+    let (actual, data, _) = bech32::decode(value)?;
+    if actual != hrp {
+        return Err(Error::InvalidHrp {
+            expected: hrp.to_string(),
+            actual,
+        });
+    }
+    Ok(Vec::<u8>::from_base32(&data)?)
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("threshold should be between 1 and members count ({members}), got: {threshold}")]
+    InvalidThreshold { threshold: usize, members: usize },
+    #[error("invalid bech32 prefix, expected: '{expected}', got: '{actual}'")]
+    InvalidHrp { expected: String, actual: String },
+    #[error("cannot decode member key: {0}")]
+    InvalidKey(String),
+    #[error("no secrets of committee member #{index}, all members are needed to decrypt tally")]
+    MissingMemberSecrets { index: usize },
+    #[error("tally of proposal #{0} is not encrypted")]
+    NotEncrypted(u8),
+    #[error("cannot decrypt tally: {0}")]
+    Tally(String),
+    #[error(transparent)]
+    DecryptedTally(#[from] DecryptedPrivateTallyError),
+    #[error(transparent)]
+    Bech32(#[from] bech32::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::ChaChaRng;
+    use rand_core::SeedableRng;
+
+    #[test]
+    fn committee_def_roundtrip() {
+        let mut rng = ChaChaRng::seed_from_u64(1);
+        let committee = ElectionCommittee::generate("member", 3, 2, b"crs", &mut rng).unwrap();
+        let def = committee.def();
+
+        assert_eq!(def.committee_ids.len(), 3);
+        assert_eq!(
+            def.member_public_keys().unwrap().len(),
+            committee.members.len()
+        );
+        assert_eq!(
+            ElectionPublicKey::from_participants(&def.member_public_keys().unwrap())
+                .to_bech32_str(),
+            def.election_key
+        );
+        assert!(matches!(
+            ElectionCommittee::generate("member", 3, 4, b"crs", &mut rng),
+            Err(Error::InvalidThreshold { .. })
+        ));
+    }
+
+    #[test]
+    fn tally_is_decrypted_with_secrets_of_all_members() {
+        let mut rng = ChaChaRng::seed_from_u64(2);
+        let committee = ElectionCommittee::generate("member", 3, 2, b"crs", &mut rng).unwrap();
+        let def = committee.def();
+        let dir = tempdir::TempDir::new("committee").unwrap();
+        let committee_file = dir.path().join("committee.json");
+        std::fs::write(&committee_file, serde_json::to_string(&def).unwrap()).unwrap();
+        for secrets in committee.secrets().iter().rev() {
+            std::fs::write(
+                dir.path()
+                    .join(format!("{}{}", secrets.alias, MEMBER_SECRETS_SUFFIX)),
+                serde_json::to_string(secrets).unwrap(),
+            )
+            .unwrap();
+        }
+
+        let secrets = def.member_secrets(&committee_file).unwrap();
+        assert_eq!(
+            secrets
+                .iter()
+                .map(|secrets| secrets.member_pk.clone())
+                .collect::<Vec<_>>(),
+            def.member_keys
+        );
+
+        let encrypted_tally =
+            EncryptedTally::new(3, committee.election_key(), Crs::from_hash(b"crs"));
+        let member_sks: Vec<MemberSecretKey> = secrets
+            .iter()
+            .map(|secrets| secrets.member_secret_key().unwrap())
+            .collect();
+        let proposal = decrypt_proposal(
+            &encrypted_tally,
+            10,
+            &def.member_public_keys().unwrap(),
+            &member_sks,
+            &mut rng,
+        )
+        .unwrap();
+        assert_eq!(proposal.decrypt_shares.len(), 3);
+        assert_eq!(proposal.tally_result.to_vec(), vec![0, 0, 0]);
+
+        assert!(matches!(
+            def.order_secrets(secrets[1..].to_vec()),
+            Err(Error::MissingMemberSecrets { index: 0 })
+        ));
+    }
+}
@@ -1,9 +1,9 @@
 use chain_crypto::digest::DigestOf;
 use chain_impl_mockchain::certificate::ExternalProposalId;
 use serde::Serialize;
-use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use typed_bytes::ByteBuilder;
+use vit_servicing_station_lib::db::models::community_advisors_reviews::AdvisorReview;
 use vit_servicing_station_tests::common::data::{
     parse_proposals, parse_reviews, ProposalTemplate, ReviewTemplate, TemplateLoad,
 };
@@ -31,18 +31,110 @@ impl ProposalWithReviews {
 #[allow(clippy::from_over_into)]
 impl Into<ExternalProposalId> for ProposalWithReviews {
     fn into(self) -> ExternalProposalId {
-            let bb = ByteBuilder::new()
-                .bytes(self.proposal.proposal_id.as_bytes())
-                .bytes( self.proposal.proposal_url.as_bytes())
-                .bytes( self.proposal.proposal_summary.as_bytes())
-                .bytes( self.proposal.category_name.as_bytes())
-                .bytes( self.proposal.challenge_type.to_string().as_bytes())
-                .bytes( self.proposal.chain_vote_options.as_csv_string().as_bytes())
-                .bytes( self.proposal.chain_vote_type.as_bytes())
-                .bytes( self.proposal.files_url.as_bytes())
-                .bytes( self.proposal.proposer_url.as_bytes())
-                .finalize();
-        DigestOf::digest_byteslice(&bb.as_byteslice())
+        ProposalHashFields::from(self).external_id()
+    }
+}
+
+/// Proposal data which external proposal id is calculated from. Can be filled either
+/// from import templates or from data exposed by running vit-servicing-station.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProposalHashFields {
+    pub proposal_id: String,
+    pub proposal_url: String,
+    pub proposal_summary: String,
+    pub category_name: String,
+    pub challenge_type: String,
+    pub chain_vote_options: String,
+    pub chain_vote_type: String,
+    pub files_url: String,
+    pub proposer_url: String,
+    pub reviews: Vec<ReviewHashFields>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReviewHashFields {
+    pub id: String,
+    pub assessor: String,
+    pub impact_alignment_rating_given: i32,
+    pub impact_alignment_note: String,
+    pub feasibility_rating_given: i32,
+    pub feasibility_note: String,
+    pub auditability_rating_given: i32,
+    pub auditability_note: String,
+}
+
+impl ProposalHashFields {
+    pub fn external_id(&self) -> ExternalProposalId {
+        let mut bb = ByteBuilder::new()
+            .bytes(self.proposal_id.as_bytes())
+            .bytes(self.proposal_url.as_bytes())
+            .bytes(self.proposal_summary.as_bytes())
+            .bytes(self.category_name.as_bytes())
+            .bytes(self.challenge_type.as_bytes())
+            .bytes(self.chain_vote_options.as_bytes())
+            .bytes(self.chain_vote_type.as_bytes())
+            .bytes(self.files_url.as_bytes())
+            .bytes(self.proposer_url.as_bytes());
+        for review in &self.reviews {
+            bb = bb
+                .bytes(review.id.as_bytes())
+                .bytes(review.assessor.as_bytes())
+                .bytes(&review.impact_alignment_rating_given.to_be_bytes())
+                .bytes(review.impact_alignment_note.as_bytes())
+                .bytes(&review.feasibility_rating_given.to_be_bytes())
+                .bytes(review.feasibility_note.as_bytes())
+                .bytes(&review.auditability_rating_given.to_be_bytes())
+                .bytes(review.auditability_note.as_bytes());
+        }
+        DigestOf::digest_byteslice(&bb.finalize().as_byteslice())
+    }
+}
+
+impl From<ProposalWithReviews> for ProposalHashFields {
+    fn from(data: ProposalWithReviews) -> Self {
+        let proposal = data.proposal;
+        Self {
+            proposal_id: proposal.proposal_id,
+            proposal_url: proposal.proposal_url,
+            proposal_summary: proposal.proposal_summary,
+            category_name: proposal.category_name,
+            challenge_type: proposal.challenge_type.to_string(),
+            chain_vote_options: proposal.chain_vote_options.as_csv_string(),
+            chain_vote_type: proposal.chain_vote_type,
+            files_url: proposal.files_url,
+            proposer_url: proposal.proposer_url,
+            reviews: data.reviews.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ReviewTemplate> for ReviewHashFields {
+    fn from(review: ReviewTemplate) -> Self {
+        Self {
+            id: review.id.unwrap_or_default(),
+            assessor: review.assessor,
+            impact_alignment_rating_given: review.impact_alignment_rating_given,
+            impact_alignment_note: review.impact_alignment_note,
+            feasibility_rating_given: review.feasibility_rating_given,
+            feasibility_note: review.feasibility_note,
+            auditability_rating_given: review.auditability_rating_given,
+            auditability_note: review.auditability_note,
+        }
+    }
+}
+
+impl From<AdvisorReview> for ReviewHashFields {
+    fn from(review: AdvisorReview) -> Self {
+        Self {
+            id: review.id.to_string(),
+            assessor: review.assessor,
+            impact_alignment_rating_given: review.impact_alignment_rating_given,
+            impact_alignment_note: review.impact_alignment_note,
+            feasibility_rating_given: review.feasibility_rating_given,
+            feasibility_note: review.feasibility_note,
+            auditability_rating_given: review.auditability_rating_given,
+            auditability_note: review.auditability_note,
+        }
     }
 }
 
@@ -50,11 +142,15 @@ pub struct ProposalsWithReviewsCollection(Vec<ProposalWithReviews>);
 
 impl ProposalsWithReviewsCollection {
     pub fn from_files<P: AsRef<Path>>(proposals: P, reviews: P) -> Result<Self, TemplateLoad> {
-        let mut proposals: Vec<_> = parse_proposals(proposals.as_ref().to_path_buf())?.into_iter().collect();
-        let mut reviews: Vec<_> = parse_reviews(reviews.as_ref().to_path_buf())?.into_iter().collect();
+        let mut proposals: Vec<_> = parse_proposals(proposals.as_ref().to_path_buf())?
+            .into_iter()
+            .collect();
+        let mut reviews: Vec<_> = parse_reviews(reviews.as_ref().to_path_buf())?
+            .into_iter()
+            .collect();
 
-        proposals.sort_by(|a,b| a.proposal_id.cmp(&b.proposal_id));
-        reviews.sort_by(|a,b| a.id.cmp(&b.id));
+        proposals.sort_by(|a, b| a.proposal_id.cmp(&b.proposal_id));
+        reviews.sort_by(|a, b| a.id.cmp(&b.id));
 
         Ok(Self(
             proposals
@@ -1,9 +1,24 @@
+mod committee;
+mod hasher;
 mod qr;
 mod static_data;
 mod time;
 mod vote_plan;
 
 pub use self::time::{convert_to_blockchain_date, convert_to_human_date};
-pub use qr::{generate_qr_and_hashes, Error as QrError, WalletExtension};
-pub use static_data::{build_current_fund, build_servicing_station_parameters};
+pub use committee::{
+    CommitteeMember, CommitteeMemberSecrets, ElectionCommittee, ElectionCommitteeDef,
+    Error as CommitteeError,
+};
+pub use hasher::{
+    ExternalProposalIdSource, ProposalHashFields, ProposalWithReviews, ProposalsExternalIdMapping,
+    ProposalsWithReviewsCollection, ReviewHashFields,
+};
+pub use qr::{
+    generate_qr_and_hashes, pin_to_bytes, Error as QrError, PinHint, QrEntry, QrExport,
+    SecretFormat, SheetFormat, WalletExtension,
+};
+pub use static_data::{
+    build_current_fund, build_next_funds, build_servicing_station_parameters, VotePlanLayout,
+};
 pub use vote_plan::VitVotePlanDefBuilder;
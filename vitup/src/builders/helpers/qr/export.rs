@@ -0,0 +1,263 @@
+use super::sheet::{to_pdf, to_svg, SheetItem, CELLS_PER_PAGE};
+use super::Error;
+use chain_crypto::bech32::Bech32;
+use chain_crypto::{Ed25519Extended, SecretKey};
+use image::GrayImage;
+use serde::Serialize;
+use std::path::Path;
+
+/// Wallet which qr code was generated for, together with everything
+/// needed to print it or export its secret in other formats
+pub struct QrEntry {
+    pub alias: String,
+    pub pin: String,
+    pub address: String,
+    /// qr file name without extension, also used for secret files
+    pub file_stem: String,
+    pub secret_key: SecretKey<Ed25519Extended>,
+    pub qr: GrayImage,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SheetFormat {
+    Svg,
+    Pdf,
+}
+
+impl std::str::FromStr for SheetFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "svg" => Ok(Self::Svg),
+            "pdf" => Ok(Self::Pdf),
+            other => Err(format!(
+                "unknown sheet format: '{}', expected svg or pdf",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretFormat {
+    /// `{alias}_{pin}.sk` file with bech32 secret key, loadable by iapyx
+    Bech32,
+    /// `{alias}_{pin}.hex` file with hex encoded secret key
+    Hex,
+    /// `index.csv` listing all wallets with their files and secrets
+    Csv,
+}
+
+impl std::str::FromStr for SecretFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "bech32" => Ok(Self::Bech32),
+            "hex" => Ok(Self::Hex),
+            "csv" => Ok(Self::Csv),
+            other => Err(format!(
+                "unknown secret format: '{}', expected bech32, hex or csv",
+                other
+            )),
+        }
+    }
+}
+
+/// How pin is printed under qr code on sheets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PinHint {
+    Full,
+    /// only first digit is printed
+    Masked,
+    Hidden,
+}
+
+impl Default for PinHint {
+    fn default() -> Self {
+        Self::Masked
+    }
+}
+
+impl PinHint {
+    fn apply(&self, pin: &str) -> Option<String> {
+        match self {
+            Self::Full => Some(pin.to_string()),
+            Self::Masked => Some(
+                pin.chars()
+                    .enumerate()
+                    .map(|(idx, c)| if idx == 0 { c } else { '*' })
+                    .collect(),
+            ),
+            Self::Hidden => None,
+        }
+    }
+}
+
+impl std::str::FromStr for PinHint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "full" => Ok(Self::Full),
+            "masked" => Ok(Self::Masked),
+            "hidden" => Ok(Self::Hidden),
+            other => Err(format!(
+                "unknown pin hint: '{}', expected full, masked or hidden",
+                other
+            )),
+        }
+    }
+}
+
+/// Additional outputs produced next to qr png files and hashes
+#[derive(Debug, Clone, Default)]
+pub struct QrExport {
+    pub sheets: Vec<SheetFormat>,
+    pub secrets: Vec<SecretFormat>,
+    pub pin_hint: PinHint,
+}
+
+#[derive(Serialize)]
+struct IndexRecord<'a> {
+    alias: &'a str,
+    pin: &'a str,
+    address: &'a str,
+    qr: String,
+    hash: String,
+    bech32: String,
+    hex: String,
+}
+
+impl QrExport {
+    pub fn is_empty(&self) -> bool {
+        self.sheets.is_empty() && self.secrets.is_empty()
+    }
+
+    pub fn export<P: AsRef<Path>>(&self, entries: &[QrEntry], folder: P) -> Result<(), Error> {
+        let folder = folder.as_ref();
+
+        for entry in entries {
+            if self.secrets.contains(&SecretFormat::Bech32) {
+                std::fs::write(
+                    folder.join(format!("{}.sk", entry.file_stem)),
+                    entry.secret_key.to_bech32_str(),
+                )?;
+            }
+            if self.secrets.contains(&SecretFormat::Hex) {
+                std::fs::write(
+                    folder.join(format!("{}.hex", entry.file_stem)),
+                    hex::encode(entry.secret_key.leak_secret().as_ref()),
+                )?;
+            }
+        }
+
+        if self.secrets.contains(&SecretFormat::Csv) {
+            let index = folder.join("index.csv");
+            let mut writer = csv::Writer::from_path(&index)?;
+            for entry in entries {
+                writer.serialize(IndexRecord {
+                    alias: &entry.alias,
+                    pin: &entry.pin,
+                    address: &entry.address,
+                    qr: format!("{}.png", entry.file_stem),
+                    hash: format!("{}.txt", entry.file_stem),
+                    bech32: entry.secret_key.to_bech32_str(),
+                    hex: hex::encode(entry.secret_key.leak_secret().as_ref()),
+                })?;
+            }
+            writer.flush()?;
+            println!("Wallets index dumped to {:?}", index);
+        }
+
+        if self.sheets.is_empty() || entries.is_empty() {
+            return Ok(());
+        }
+
+        let items: Vec<SheetItem> = entries
+            .iter()
+            .map(|entry| SheetItem {
+                alias: &entry.alias,
+                pin_hint: self.pin_hint.apply(&entry.pin),
+                address: &entry.address,
+                qr: &entry.qr,
+            })
+            .collect();
+
+        for format in &self.sheets {
+            match format {
+                SheetFormat::Svg => {
+                    for (page, chunk) in items.chunks(CELLS_PER_PAGE).enumerate() {
+                        let path = folder.join(format!("qr_sheet_{}.svg", page + 1));
+                        std::fs::write(&path, to_svg(chunk))?;
+                        println!("Qr sheet dumped to {:?}", path);
+                    }
+                }
+                SheetFormat::Pdf => {
+                    let path = folder.join("qr_sheets.pdf");
+                    std::fs::write(&path, to_pdf(&items))?;
+                    println!("Qr sheets dumped to {:?}", path);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{encrypt_key, pin_to_bytes, qr_image};
+    use super::*;
+    use assert_fs::TempDir;
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    #[test]
+    fn exported_secrets_decode_to_key_from_qr_payload() {
+        let mut rng = ChaChaRng::seed_from_u64(1);
+        let secret_key = SecretKey::<Ed25519Extended>::generate(&mut rng);
+        let pin = "1234";
+        let payload = encrypt_key(&secret_key, &pin_to_bytes(pin), &mut rng).unwrap();
+        let entry = QrEntry {
+            alias: "wallet".to_string(),
+            pin: pin.to_string(),
+            address: "ca1address".to_string(),
+            file_stem: "wallet_1234".to_string(),
+            secret_key,
+            qr: qr_image(&payload).unwrap(),
+        };
+
+        let folder = TempDir::new().unwrap();
+        QrExport {
+            secrets: vec![SecretFormat::Bech32, SecretFormat::Hex, SecretFormat::Csv],
+            ..Default::default()
+        }
+        .export(&[entry], folder.path())
+        .unwrap();
+
+        let qr_secret =
+            symmetric_cipher::decrypt(pin_to_bytes(pin), hex::decode(&payload).unwrap()).unwrap();
+        let from_bech32 = |bech32: &str| {
+            SecretKey::<Ed25519Extended>::try_from_bech32_str(bech32.trim()).unwrap()
+        };
+        let from_hex = |encoded: &str| {
+            SecretKey::<Ed25519Extended>::from_binary(&hex::decode(encoded.trim()).unwrap())
+                .unwrap()
+        };
+
+        let read = |name: &str| std::fs::read_to_string(folder.path().join(name)).unwrap();
+        let mut index = csv::Reader::from_path(folder.path().join("index.csv")).unwrap();
+        let record: std::collections::HashMap<String, String> =
+            index.deserialize().next().unwrap().unwrap();
+
+        for decoded in [
+            from_bech32(&read("wallet_1234.sk")),
+            from_hex(&read("wallet_1234.hex")),
+            from_bech32(&record["bech32"]),
+            from_hex(&record["hex"]),
+        ] {
+            assert_eq!(decoded.leak_secret().as_ref(), &*qr_secret);
+        }
+    }
+}
@@ -0,0 +1,167 @@
+mod export;
+mod sheet;
+
+use crate::config::{Config, RandomStream};
+use catalyst_toolbox::kedqr::{generate, KeyQrCode};
+use chain_addr::{Address, AddressReadable, Discrimination, Kind};
+use chain_crypto::{Ed25519Extended, SecretKey};
+use chain_impl_mockchain::key::EitherEd25519SecretKey;
+pub use export::{PinHint, QrEntry, QrExport, SecretFormat, SheetFormat};
+use hersir::config::WalletTemplate;
+use image::{GrayImage, ImageError, Luma};
+use qrcode::{EcLevel, QrCode};
+use rand_core::{CryptoRng, RngCore};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use thiserror::Error;
+use thor::{Wallet, WalletAlias};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Image(#[from] ImageError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+    #[error(transparent)]
+    Qr(#[from] qrcode::types::QrError),
+    #[error(transparent)]
+    Encryption(#[from] symmetric_cipher::Error),
+}
+
+pub fn generate_qr_and_hashes<P: AsRef<Path>>(
+    wallets: Vec<(&WalletAlias, Wallet)>,
+    initials: &HashMap<WalletTemplate, String>,
+    parameters: &Config,
+    qr_export: &QrExport,
+    folder: P,
+) -> Result<(), Error> {
+    let total = wallets.len();
+    let folder = folder.as_ref();
+    let mut entries = Vec::new();
+    let mut encryption_rng = parameters.rng(RandomStream::QrEncryption);
+
+    for (idx, (alias, wallet)) in wallets.iter().enumerate() {
+        let pin = initials
+            .iter()
+            .find_map(|(template, pin)| {
+                if template.alias() == Some(alias.to_string()) {
+                    Some(pin)
+                } else {
+                    None
+                }
+            })
+            .unwrap();
+        let secret_key = wallet.secret_key();
+        let file_stem = format!("{}_{}", alias, pin);
+
+        let png = folder.join(format!("{}.png", file_stem));
+        println!("[{}/{}] Qr dumped to {:?}", idx + 1, total, png);
+        let payload = encrypt_key(&secret_key, &pin_to_bytes(pin), &mut encryption_rng)?;
+        let img = qr_image(&payload)?;
+        img.save(&png)?;
+
+        let hash = folder.join(format!("{}.txt", file_stem));
+        println!("[{}/{}] QR hash dumped to {:?}", idx + 1, total, hash);
+        std::fs::write(hash, format!("{}\n", payload))?;
+
+        if !qr_export.is_empty() {
+            entries.push(QrEntry {
+                alias: alias.to_string(),
+                pin: pin.to_string(),
+                address: wallet.address().to_string(),
+                file_stem,
+                secret_key,
+                qr: img,
+            });
+        }
+    }
+
+    let zero_funds_initial_counts = parameters.initials.block0.zero_funds_count();
+
+    if zero_funds_initial_counts > 0 {
+        let zero_funds_pin = parameters.initials.block0.zero_funds_pin().unwrap();
+        let mut rng = parameters.rng(RandomStream::Qr);
+
+        for i in 1..zero_funds_initial_counts + 1 {
+            let sk = SecretKey::generate(&mut rng);
+            let payload = encrypt_key(&sk, &pin_to_bytes(&zero_funds_pin), &mut encryption_rng)?;
+            let img = qr_image(&payload)?;
+            let png = folder.join(&format!("zero_funds_{}_{}.png", i, zero_funds_pin));
+            img.save(png)?;
+
+            let hash = folder.join(format!("zero_funds_{}.txt", i));
+            std::fs::write(hash, payload)?;
+
+            if !qr_export.is_empty() {
+                let address = Address(Discrimination::Production, Kind::Account(sk.to_public()));
+                entries.push(QrEntry {
+                    alias: format!("zero_funds_{}", i),
+                    pin: zero_funds_pin.clone(),
+                    address: AddressReadable::from_address("ca", &address).to_string(),
+                    file_stem: format!("zero_funds_{}_{}", i, zero_funds_pin),
+                    secret_key: sk,
+                    qr: img,
+                });
+            }
+        }
+    }
+
+    qr_export.export(&entries, folder)
+}
+
+/// Secret key encrypted with pin, hex encoded, as expected by `catalyst_toolbox::kedqr` decoder.
+/// Salt and nonce are drawn from given rng, so seeded deployment gets the same QR codes.
+pub fn encrypt_key<R: RngCore + CryptoRng>(
+    secret_key: &SecretKey<Ed25519Extended>,
+    password: &[u8],
+    rng: R,
+) -> Result<String, Error> {
+    let encrypted = symmetric_cipher::encrypt(password, secret_key.leak_secret().as_ref(), rng)?;
+    Ok(hex::encode(encrypted))
+}
+
+pub fn qr_image(payload: &str) -> Result<GrayImage, Error> {
+    Ok(QrCode::with_error_correction_level(payload, EcLevel::H)?
+        .render::<Luma<u8>>()
+        .build())
+}
+
+pub fn pin_to_bytes(pin: &str) -> Vec<u8> {
+    pin.chars().map(|x| x.to_digit(10).unwrap() as u8).collect()
+}
+
+pub trait WalletExtension {
+    fn secret_key(&self) -> SecretKey<Ed25519Extended>;
+    fn save_qr_code<P: AsRef<Path>>(&self, path: P, password: &[u8]);
+    fn save_qr_code_hash<P: AsRef<Path>>(&self, path: P, password: &[u8]);
+}
+
+impl WalletExtension for Wallet {
+    fn secret_key(&self) -> SecretKey<Ed25519Extended> {
+        match self {
+            Wallet::Account(account) => match account.signing_key().as_ref() {
+                EitherEd25519SecretKey::Extended(secret_key) => secret_key.clone(),
+                EitherEd25519SecretKey::Normal(_) => panic!("unsupported secret key type"),
+            },
+            Wallet::UTxO(utxo) => utxo.last_signing_key().clone().into_secret_key(),
+            Wallet::Delegation(delegation) => {
+                delegation.last_signing_key().clone().into_secret_key()
+            }
+        }
+    }
+
+    fn save_qr_code<P: AsRef<Path>>(&self, path: P, password: &[u8]) {
+        let qr = KeyQrCode::generate(self.secret_key(), password);
+        qr.to_img().save(path).unwrap();
+    }
+
+    fn save_qr_code_hash<P: AsRef<Path>>(&self, path: P, password: &[u8]) {
+        let qr = generate(self.secret_key(), password);
+        let mut file = File::create(path).unwrap();
+        writeln!(file, "{}", qr).unwrap();
+    }
+}
@@ -0,0 +1,298 @@
+use image::GrayImage;
+use std::fmt::Write;
+
+/// A4 page in points
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 36.0;
+const COLUMNS: usize = 2;
+const ROWS: usize = 3;
+const QR_SIZE: f32 = 180.0;
+pub const CELLS_PER_PAGE: usize = COLUMNS * ROWS;
+
+pub struct SheetItem<'a> {
+    pub alias: &'a str,
+    pub pin_hint: Option<String>,
+    pub address: &'a str,
+    pub qr: &'a GrayImage,
+}
+
+/// Run of dark qr pixels, merged with identical runs from following rows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rect {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+fn dark_rects(img: &GrayImage) -> Vec<Rect> {
+    let mut rects: Vec<Rect> = Vec::new();
+    let mut previous_row: Vec<usize> = Vec::new();
+
+    for y in 0..img.height() {
+        let mut current_row = Vec::new();
+        let mut x = 0;
+        while x < img.width() {
+            if img.get_pixel(x, y).0[0] >= 128 {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while x < img.width() && img.get_pixel(x, y).0[0] < 128 {
+                x += 1;
+            }
+            let width = x - start;
+            match previous_row
+                .iter()
+                .find(|idx| rects[**idx].x == start && rects[**idx].width == width)
+            {
+                Some(idx) => {
+                    rects[*idx].height += 1;
+                    current_row.push(*idx);
+                }
+                None => {
+                    rects.push(Rect {
+                        x: start,
+                        y,
+                        width,
+                        height: 1,
+                    });
+                    current_row.push(rects.len() - 1);
+                }
+            }
+        }
+        previous_row = current_row;
+    }
+    rects
+}
+
+/// Position of single wallet on page, in points from top left corner
+struct Cell {
+    qr_x: f32,
+    qr_y: f32,
+    scale: f32,
+    rects: Vec<Rect>,
+    /// baseline, font size and text
+    lines: Vec<(f32, f32, String)>,
+}
+
+fn layout(position: usize, item: &SheetItem) -> Cell {
+    let cell_width = (PAGE_WIDTH - 2.0 * MARGIN) / COLUMNS as f32;
+    let cell_height = (PAGE_HEIGHT - 2.0 * MARGIN) / ROWS as f32;
+    let column = position % COLUMNS;
+    let row = position / COLUMNS;
+
+    let qr_x = MARGIN + column as f32 * cell_width + (cell_width - QR_SIZE) / 2.0;
+    let qr_y = MARGIN + row as f32 * cell_height + 8.0;
+    let text_y = qr_y + QR_SIZE;
+
+    let mut lines = vec![(text_y + 14.0, 10.0, item.alias.to_string())];
+    if let Some(pin_hint) = &item.pin_hint {
+        lines.push((text_y + 26.0, 9.0, format!("PIN: {}", pin_hint)));
+    }
+    lines.push((text_y + 38.0, 6.0, item.address.to_string()));
+
+    Cell {
+        qr_x,
+        qr_y,
+        scale: QR_SIZE / item.qr.width().max(1) as f32,
+        rects: dark_rects(item.qr),
+        lines,
+    }
+}
+
+/// Renders single page (up to `CELLS_PER_PAGE` items)
+pub fn to_svg(items: &[SheetItem]) -> String {
+    let mut svg = String::new();
+    writeln!(svg, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>").unwrap();
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"210mm\" height=\"297mm\" viewBox=\"0 0 {} {}\">",
+        PAGE_WIDTH, PAGE_HEIGHT
+    )
+    .unwrap();
+    writeln!(svg, "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>").unwrap();
+
+    for (position, item) in items.iter().enumerate() {
+        let cell = layout(position, item);
+        writeln!(svg, "<g fill=\"black\" shape-rendering=\"crispEdges\">").unwrap();
+        for rect in &cell.rects {
+            writeln!(
+                svg,
+                "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\"/>",
+                cell.qr_x + rect.x as f32 * cell.scale,
+                cell.qr_y + rect.y as f32 * cell.scale,
+                rect.width as f32 * cell.scale,
+                rect.height as f32 * cell.scale
+            )
+            .unwrap();
+        }
+        writeln!(svg, "</g>").unwrap();
+        for (baseline, size, text) in &cell.lines {
+            writeln!(
+                svg,
+                "<text x=\"{:.2}\" y=\"{:.2}\" font-family=\"Helvetica, Arial, sans-serif\" font-size=\"{}\">{}</text>",
+                cell.qr_x,
+                baseline,
+                size,
+                escape_xml(text)
+            )
+            .unwrap();
+        }
+    }
+    writeln!(svg, "</svg>").unwrap();
+    svg
+}
+
+fn pdf_page_content(items: &[SheetItem]) -> String {
+    let mut content = String::new();
+    for (position, item) in items.iter().enumerate() {
+        let cell = layout(position, item);
+        for rect in &cell.rects {
+            writeln!(
+                content,
+                "{:.2} {:.2} {:.2} {:.2} re",
+                cell.qr_x + rect.x as f32 * cell.scale,
+                PAGE_HEIGHT - (cell.qr_y + (rect.y + rect.height) as f32 * cell.scale),
+                rect.width as f32 * cell.scale,
+                rect.height as f32 * cell.scale
+            )
+            .unwrap();
+        }
+        writeln!(content, "f").unwrap();
+        for (baseline, size, text) in &cell.lines {
+            writeln!(
+                content,
+                "BT /F1 {} Tf {:.2} {:.2} Td ({}) Tj ET",
+                size,
+                cell.qr_x,
+                PAGE_HEIGHT - baseline,
+                escape_pdf(text)
+            )
+            .unwrap();
+        }
+    }
+    content
+}
+
+/// Minimal multi page pdf document using only builtin Helvetica font
+pub fn to_pdf(items: &[SheetItem]) -> Vec<u8> {
+    let pages: Vec<String> = items.chunks(CELLS_PER_PAGE).map(pdf_page_content).collect();
+
+    // 1: catalog, 2: pages, 3: font, then page and its content for each page
+    let page_ids: Vec<usize> = (0..pages.len()).map(|idx| 4 + idx * 2).collect();
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids
+                .iter()
+                .map(|id| format!("{} 0 R", id))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+    ];
+    for (content, page_id) in pages.iter().zip(page_ids.iter()) {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH,
+            PAGE_HEIGHT,
+            page_id + 1
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            content.len(),
+            content
+        ));
+    }
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::new();
+    for (idx, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        writeln!(pdf, "{} 0 obj\n{}\nendobj", idx + 1, object).unwrap();
+    }
+    let xref = pdf.len();
+    writeln!(pdf, "xref\n0 {}\n0000000000 65535 f ", objects.len() + 1).unwrap();
+    for offset in offsets {
+        writeln!(pdf, "{:010} 00000 n ", offset).unwrap();
+    }
+    writeln!(
+        pdf,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF",
+        objects.len() + 1,
+        xref
+    )
+    .unwrap();
+    pdf.into_bytes()
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Builtin fonts cover only ascii reliably, so other characters are replaced
+fn escape_pdf(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\\' | '(' | ')' => format!("\\{}", c),
+            c if c.is_ascii() && !c.is_ascii_control() => c.to_string(),
+            _ => "?".to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Luma;
+
+    #[test]
+    fn qr_is_drawn_with_merged_rects_on_pages() {
+        // 2x2 dark block in top left corner and single dark pixel in bottom right
+        let qr = GrayImage::from_fn(4, 4, |x, y| {
+            if (x < 2 && y < 2) || (x == 3 && y == 3) {
+                Luma([0])
+            } else {
+                Luma([255])
+            }
+        });
+        assert_eq!(
+            dark_rects(&qr),
+            vec![
+                Rect {
+                    x: 0,
+                    y: 0,
+                    width: 2,
+                    height: 2
+                },
+                Rect {
+                    x: 3,
+                    y: 3,
+                    width: 1,
+                    height: 1
+                },
+            ]
+        );
+
+        let items: Vec<SheetItem> = (0..CELLS_PER_PAGE + 1)
+            .map(|_| SheetItem {
+                alias: "wallet_(1)",
+                pin_hint: Some("1***".to_string()),
+                address: "ca1q",
+                qr: &qr,
+            })
+            .collect();
+        let pdf = String::from_utf8(to_pdf(&items)).unwrap();
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.contains("/Count 2"));
+        assert!(pdf.contains("(wallet_\\(1\\)) Tj"));
+        assert!(to_svg(&items[..1]).contains(">PIN: 1***</text>"));
+    }
+}
@@ -6,11 +6,78 @@ use chain_impl_mockchain::{
 };
 use chain_vote::ElectionPublicKey;
 use hersir::builder::{Settings, VotePlanSettings};
-use vit_servicing_station_lib::db::models::{goals::Goal, groups::Group};
+use vit_servicing_station_lib::db::models::{
+    goals::Goal, groups::Group, vote_options::VoteOptions,
+};
 use vit_servicing_station_tests::common::data::{
-    CurrentFund, FundDates, FundInfo, ValidVotePlanParameters,
+    CurrentFund, FundDates, FundInfo, Snapshot, ValidVotePlanParameters,
 };
 
+/// Vote options and challenge of each vote plan from `vote_plan.definitions`.
+/// Servicing station data is generated with fund-wide settings, so layout is
+/// applied on generated data afterwards
+#[derive(Clone, Debug, Default)]
+pub struct VotePlanLayout {
+    plans: Vec<VotePlanLayoutEntry>,
+}
+
+#[derive(Clone, Debug)]
+struct VotePlanLayoutEntry {
+    name: String,
+    options: VoteOptions,
+    chain_proposal_ids: Vec<String>,
+}
+
+impl VotePlanLayout {
+    /// Registers proposals of vote plans built for definition with given name
+    pub fn push(&mut self, name: String, options: VoteOptions, vote_plans: &[VotePlanDef]) {
+        let mut chain_proposal_ids: Vec<String> = vote_plans
+            .iter()
+            .flat_map(|vote_plan| vote_plan.proposals())
+            .map(|proposal| proposal.id().to_string())
+            .collect();
+        chain_proposal_ids.sort();
+        chain_proposal_ids.dedup();
+        self.plans.push(VotePlanLayoutEntry {
+            name,
+            options,
+            chain_proposal_ids,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plans.is_empty()
+    }
+
+    /// Moves proposals of each definition into its own challenge (n-th definition
+    /// gets n-th challenge, renamed after definition) and sets their vote options
+    pub fn apply(&self, snapshot: &mut Snapshot) {
+        if self.is_empty() {
+            return;
+        }
+        for (challenge, plan) in snapshot.challenges_mut().iter_mut().zip(&self.plans) {
+            challenge.title = plan.name.clone();
+        }
+        let challenges = snapshot.challenges();
+
+        for info in snapshot.proposals_mut().iter_mut() {
+            let chain_proposal_id = String::from_utf8_lossy(&info.proposal.chain_proposal_id);
+            let found = self.plans.iter().enumerate().find(|(_, plan)| {
+                plan.chain_proposal_ids
+                    .binary_search_by(|id| id.as_str().cmp(&chain_proposal_id))
+                    .is_ok()
+            });
+            if let Some((index, plan)) = found {
+                info.proposal.chain_vote_options = plan.options.clone();
+                if let Some(challenge) = challenges.get(index) {
+                    info.proposal.challenge_id = challenge.id;
+                    info.challenge_type = challenge.challenge_type.clone();
+                }
+            }
+        }
+    }
+}
+
 pub fn build_current_fund(
     config: &Config,
     vote_plans: Vec<VotePlanDef>,
@@ -129,27 +196,33 @@ pub fn build_next_funds(config: &Config) -> Vec<FundInfo> {
         .collect()
 }
 
+/// Static data for servicing station. `election_key` of external election committee,
+/// if provided, is used for private vote plans instead of generated one
 pub fn build_servicing_station_parameters(
     config: &Config,
     token_list: Vec<(Role, TokenIdentifier)>,
     vote_plans: Vec<VotePlanDef>,
     settings: &Settings,
+    election_key: Option<&str>,
 ) -> ValidVotePlanParameters {
     let mut parameters =
         ValidVotePlanParameters::from(build_current_fund(config, vote_plans, token_list));
     parameters.next_funds = build_next_funds(config);
 
-    if config.vote_plan.private {
+    if config.vote_plan.any_private() {
         for (alias, data) in settings.vote_plans.iter() {
             if let VotePlanSettings::Private {
                 keys,
                 vote_plan: _vote_plan,
             } = data
             {
-                let key: ElectionPublicKey = keys.election_key();
+                let key = election_key.map(ToString::to_string).unwrap_or_else(|| {
+                    let key: ElectionPublicKey = keys.election_key();
+                    key.to_bech32_str()
+                });
                 parameters
                     .current_fund
-                    .set_vote_encryption_key(key.to_bech32_str(), &alias.alias);
+                    .set_vote_encryption_key(key, &alias.alias);
             }
         }
     }
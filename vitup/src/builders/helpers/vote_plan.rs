@@ -1,10 +1,14 @@
 use crate::config::{Role, VoteBlockchainTime};
+use chain_impl_mockchain::certificate::ExternalProposalId;
 use chain_impl_mockchain::testing::scenario::template::VotePlanDef;
 use chain_impl_mockchain::testing::scenario::template::{ProposalDefBuilder, VotePlanDefBuilder};
 use chain_impl_mockchain::testing::TestGen;
 use chain_impl_mockchain::vote::PayloadType;
+use chain_vote::committee::MemberPublicKey;
 pub use jormungandr_lib::interfaces::Initial;
 use jormungandr_lib::interfaces::TokenIdentifier;
+use rand_chacha::ChaChaRng;
+use rand_core::{RngCore, SeedableRng};
 use std::iter;
 use thor::WalletAlias;
 
@@ -16,7 +20,9 @@ pub struct VitVotePlanDefBuilder {
     proposals_count: usize,
     options: u8,
     private: bool,
+    committee_keys: Vec<MemberPublicKey>,
     voting_tokens: Vec<(Role, TokenIdentifier)>,
+    rng: ChaChaRng,
 }
 
 impl Default for VitVotePlanDefBuilder {
@@ -29,7 +35,9 @@ impl Default for VitVotePlanDefBuilder {
             committee_wallet: "undefined".to_string(),
             options: 0,
             private: false,
+            committee_keys: Vec::new(),
             voting_tokens: vec![(Default::default(), TestGen::token_id().into())],
+            rng: ChaChaRng::from_entropy(),
         }
     }
 }
@@ -40,6 +48,12 @@ impl VitVotePlanDefBuilder {
         self
     }
 
+    /// random generator used for external proposal ids
+    pub fn rng(mut self, rng: ChaChaRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
     pub fn options(mut self, options: u8) -> Self {
         self.options = options;
         self
@@ -60,6 +74,13 @@ impl VitVotePlanDefBuilder {
         self
     }
 
+    /// member keys of external election committee used by private vote plans.
+    /// If empty, keys are generated for committee wallet
+    pub fn committee_keys(mut self, committee_keys: Vec<MemberPublicKey>) -> Self {
+        self.committee_keys = committee_keys;
+        self
+    }
+
     pub fn proposals_count(mut self, proposals_count: usize) -> Self {
         self.proposals_count = proposals_count;
         self
@@ -81,14 +102,15 @@ impl VitVotePlanDefBuilder {
     }
 
     pub fn build(self) -> Vec<VotePlanDef> {
+        let mut rng = self.rng.clone();
         iter::from_fn(|| {
+            let mut document = vec![0u8; 32];
+            rng.fill_bytes(&mut document);
             Some(
-                ProposalDefBuilder::new(
-                    chain_impl_mockchain::testing::VoteTestGen::external_proposal_id(),
-                )
-                .options(self.options)
-                .action_off_chain()
-                .clone(),
+                ProposalDefBuilder::new(ExternalProposalId::digest(&document))
+                    .options(self.options)
+                    .action_off_chain()
+                    .clone(),
             )
         })
         .take(self.proposals_count)
@@ -123,6 +145,9 @@ impl VitVotePlanDefBuilder {
 
                     if self.private {
                         vote_plan_builder.payload_type(PayloadType::Private);
+                        if !self.committee_keys.is_empty() {
+                            vote_plan_builder.committee_keys(self.committee_keys.clone());
+                        }
                     }
                     proposal_builders.to_vec().iter_mut().for_each(|proposal| {
                         vote_plan_builder.with_proposal(proposal);
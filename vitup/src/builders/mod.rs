@@ -1,20 +1,28 @@
 mod helpers;
 mod reviews;
+mod templates;
 pub mod utils;
 
-pub use crate::builders::helpers::{build_current_fund, build_servicing_station_parameters};
+pub use crate::builders::helpers::{
+    build_current_fund, build_next_funds, build_servicing_station_parameters,
+};
 use crate::builders::utils::DeploymentTree;
-use crate::config::{Config, Role};
+use crate::config::certs::export_ca;
+use crate::config::{Config, RandomStream, Role, VoteBlockchainTime};
 use crate::mode::standard::{VitController, VitControllerBuilder};
 use assert_fs::fixture::ChildPath;
 use chain_impl_mockchain::chaintypes::ConsensusVersion;
-use chain_impl_mockchain::testing::TestGen;
+use chain_impl_mockchain::testing::scenario::template::VotePlanDef;
 use chain_impl_mockchain::tokens::identifier::TokenIdentifier;
 use chain_impl_mockchain::tokens::minting_policy::MintingPolicy;
+use chain_impl_mockchain::tokens::name::{TokenName, TOKEN_NAME_MAX_SIZE};
 use chain_impl_mockchain::value::Value;
+use chain_vote::committee::MemberPublicKey;
 pub use helpers::{
-    convert_to_blockchain_date, convert_to_human_date, generate_qr_and_hashes,
-    VitVotePlanDefBuilder, WalletExtension,
+    convert_to_blockchain_date, convert_to_human_date, generate_qr_and_hashes, pin_to_bytes,
+    CommitteeError, CommitteeMemberSecrets, ElectionCommittee, ElectionCommitteeDef, PinHint,
+    ProposalHashFields, QrExport, ReviewHashFields, SecretFormat, SheetFormat,
+    VitVotePlanDefBuilder, VotePlanLayout, WalletExtension,
 };
 use hersir::builder::Node;
 use hersir::builder::Topology;
@@ -23,12 +31,18 @@ pub use jormungandr_lib::interfaces::Initial;
 use jormungandr_lib::interfaces::NumberOfSlotsPerEpoch;
 use jormungandr_lib::interfaces::SlotDuration;
 use jormungandr_lib::interfaces::TokenIdentifier as TokenIdentifierLib;
+use rand::RngCore;
+use rand_chacha::ChaChaRng;
+use rand_core::SeedableRng;
 pub use reviews::ReviewGenerator;
 use std::collections::HashMap;
 use std::io::Write;
 use std::path::Path;
 use std::str::FromStr;
+pub use templates::{Error as TemplatesError, TemplatesGenerator};
 use thiserror::Error;
+use valgrind::Protocol;
+use vit_servicing_station_lib::db::models::vote_options::VoteOptions;
 use vit_servicing_station_tests::common::data::ValidVotePlanParameters;
 
 pub const LEADER_1: &str = "Leader1";
@@ -43,6 +57,8 @@ pub struct VitBackendSettingsBuilder {
     committee_wallet: String,
     //needed for load tests when we rely on secret keys instead of qrs
     skip_qr_generation: bool,
+    qr_export: QrExport,
+    protocol: Protocol,
 }
 
 impl Default for VitBackendSettingsBuilder {
@@ -50,8 +66,10 @@ impl Default for VitBackendSettingsBuilder {
         Self {
             committee_wallet: "committee_1".to_owned(),
             skip_qr_generation: false,
+            qr_export: QrExport::default(),
             config: Default::default(),
             session_settings: SessionSettings::default(),
+            protocol: Protocol::Http,
         }
     }
 }
@@ -62,6 +80,12 @@ impl VitBackendSettingsBuilder {
         self
     }
 
+    /// printable sheets and secret formats written next to qr codes
+    pub fn qr_export(mut self, qr_export: QrExport) -> Self {
+        self.qr_export = qr_export;
+        self
+    }
+
     pub fn config(mut self, config: &Config) -> Self {
         self.config = config.clone();
         self
@@ -72,31 +96,22 @@ impl VitBackendSettingsBuilder {
         self
     }
 
-    pub fn build_topology(&self) -> Topology {
-        let topology = Topology::default();
-
-        // Leader 1
-        let leader_1 = Node::new(LEADER_1);
-
-        // leader 2
-        let leader_2 = Node::new(LEADER_2).with_trusted_peer(LEADER_1);
-
-        // leader 3
-        let leader_3 = Node::new(LEADER_3)
-            .with_trusted_peer(LEADER_1)
-            .with_trusted_peer(LEADER_2);
+    pub fn protocol(mut self, protocol: Protocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
 
-        // passive
-        let passive = Node::new(WALLET_NODE)
-            .with_trusted_peer(LEADER_1)
-            .with_trusted_peer(LEADER_2)
-            .with_trusted_peer(LEADER_3);
+    pub fn build_topology(&self) -> Topology {
+        let mut topology = Topology::default();
 
+        for node_setup in self.config.topology.nodes() {
+            let mut node = Node::new(&node_setup.alias);
+            for trusted_peer in &node_setup.trusted_peers {
+                node = node.with_trusted_peer(trusted_peer);
+            }
+            topology = topology.with_node(node);
+        }
         topology
-            .with_node(leader_1)
-            .with_node(leader_2)
-            .with_node(leader_3)
-            .with_node(passive)
     }
 
     pub fn dump_qrs<P: AsRef<Path>>(
@@ -122,7 +137,8 @@ impl VitBackendSettingsBuilder {
             })
             .collect();
 
-        generate_qr_and_hashes(wallets, initials, &self.config, &folder).map_err(Into::into)
+        generate_qr_and_hashes(wallets, initials, &self.config, &self.qr_export, &folder)
+            .map_err(Into::into)
     }
 
     fn write_token<P: AsRef<Path>>(
@@ -140,8 +156,96 @@ impl VitBackendSettingsBuilder {
             .map_err(Into::into)
     }
 
+    /// Vote plans either split from fund-wide proposals count or built per
+    /// challenge/category definition from `vote_plan.definitions`, together with
+    /// layout which brings definitions into servicing station data
+    fn build_vote_plans(
+        &self,
+        vote_phases: VoteBlockchainTime,
+        token_list: &[(Role, TokenIdentifier)],
+        committee_keys: &[MemberPublicKey],
+    ) -> Result<(Vec<VotePlanDef>, VotePlanLayout), Error> {
+        let current_fund = &self.config.data.current_fund;
+        let voting_tokens: Vec<(Role, TokenIdentifierLib)> = token_list
+            .iter()
+            .cloned()
+            .map(|(a, b)| (a, b.into()))
+            .collect();
+
+        let definitions = &self.config.vote_plan.definitions;
+        if definitions.is_empty() {
+            let vote_plans = VitVotePlanDefBuilder::default()
+                .rng(self.config.rng(RandomStream::VotePlans))
+                .vote_phases(vote_phases)
+                .options(options_count(&current_fund.options)?)
+                .split_by(255)
+                .fund_name(current_fund.fund_info.fund_name.to_string())
+                .committee(self.committee_wallet.clone())
+                .private(self.config.vote_plan.private)
+                .committee_keys(committee_keys.to_vec())
+                .proposals_count(current_fund.proposals as usize)
+                .voting_tokens(voting_tokens)
+                .build();
+            return Ok((vote_plans, VotePlanLayout::default()));
+        }
+
+        let defined: usize = definitions
+            .iter()
+            .map(|definition| definition.proposals)
+            .sum();
+        if defined != current_fund.proposals as usize {
+            return Err(Error::VotePlanProposalsMismatch {
+                defined,
+                expected: current_fund.proposals as usize,
+            });
+        }
+        if definitions.len() > current_fund.challenges {
+            return Err(Error::NotEnoughChallenges {
+                definitions: definitions.len(),
+                challenges: current_fund.challenges,
+            });
+        }
+
+        let mut rng = self.config.rng(RandomStream::VotePlans);
+        let mut vote_plans = Vec::new();
+        let mut layout = VotePlanLayout::default();
+        for definition in definitions {
+            let voting_tokens: Vec<(Role, TokenIdentifierLib)> = voting_tokens
+                .iter()
+                .filter(|(role, _)| definition.role.map(|r| r == *role).unwrap_or(true))
+                .cloned()
+                .collect();
+
+            if voting_tokens.is_empty() {
+                return Err(Error::NoVotingTokenForVotePlan(definition.name.clone()));
+            }
+
+            let options = definition.options(&current_fund.options);
+            let definition_vote_plans = VitVotePlanDefBuilder::default()
+                .rng(ChaChaRng::from_rng(&mut rng).unwrap())
+                .vote_phases(vote_phases)
+                .options(options_count(&options)?)
+                .split_by(255)
+                .fund_name(definition.name.clone())
+                .committee(
+                    definition
+                        .committee
+                        .clone()
+                        .unwrap_or_else(|| self.committee_wallet.clone()),
+                )
+                .private(definition.private(self.config.vote_plan.private))
+                .committee_keys(committee_keys.to_vec())
+                .proposals_count(definition.proposals)
+                .voting_tokens(voting_tokens)
+                .build();
+            layout.push(definition.name.clone(), options, &definition_vote_plans);
+            vote_plans.extend(definition_vote_plans);
+        }
+        Ok((vote_plans, layout))
+    }
+
     pub fn build(self) -> Result<(VitController, ValidVotePlanParameters), Error> {
-        let mut builder = VitControllerBuilder::new();
+        let mut builder = VitControllerBuilder::new().rng(self.config.rng(RandomStream::Network));
 
         let vote_blockchain_time = convert_to_blockchain_date(&self.config);
 
@@ -154,11 +258,12 @@ impl VitBackendSettingsBuilder {
 
         println!("building topology..");
 
+        self.config.topology.validate()?;
+        self.config.reviews.validate()?;
         builder = builder.topology(self.build_topology());
-        blockchain = blockchain
-            .with_leader(LEADER_1)
-            .with_leader(LEADER_2)
-            .with_leader(LEADER_3);
+        for leader in self.config.topology.leaders_aliases() {
+            blockchain = blockchain.with_leader(leader);
+        }
 
         println!("building blockchain parameters..");
 
@@ -175,8 +280,20 @@ impl VitBackendSettingsBuilder {
             );
         }
 
-        if !self.config.blockchain.committees.is_empty() {
-            blockchain = blockchain.with_committees(self.config.blockchain.committees.clone());
+        let election_committee = self
+            .config
+            .vote_plan
+            .election_committee
+            .as_ref()
+            .map(ElectionCommitteeDef::from_file)
+            .transpose()?;
+
+        let mut committees = self.config.blockchain.committees.clone();
+        if let Some(election_committee) = &election_committee {
+            committees.extend(election_committee.committee_ids.iter().cloned());
+        }
+        if !committees.is_empty() {
+            blockchain = blockchain.with_committees(committees);
         }
 
         let committe = CommitteeTemplate::Generated {
@@ -187,10 +304,19 @@ impl VitBackendSettingsBuilder {
 
         builder = builder.committee(committe);
 
+        for committee in self.config.vote_plan.committees(&self.committee_wallet) {
+            builder = builder.committee(CommitteeTemplate::Generated {
+                alias: committee,
+                member_pk: None,
+                communication_pk: None,
+            });
+        }
+
         println!("building voting token..");
 
         let root = self.session_settings.root.path().to_path_buf();
         std::fs::create_dir_all(&root)?;
+        let mut rng = self.config.rng(RandomStream::Block0);
         let policy = MintingPolicy::new();
         let token_list: Vec<(Role, TokenIdentifier)> = self
             .config
@@ -204,7 +330,7 @@ impl VitBackendSettingsBuilder {
                     Role::from_str(role).unwrap(),
                     TokenIdentifier {
                         policy_hash: policy.hash(),
-                        token_name: TestGen::token_name(),
+                        token_name: random_token_name(&mut rng),
                     },
                 )
             })
@@ -229,50 +355,29 @@ impl VitBackendSettingsBuilder {
                 self.config.data.current_fund.voting_power,
                 blockchain.discrimination(),
                 tokens_map,
-            );
-            for (wallet, _) in generated_wallet_templates
-                .iter()
-                .filter(|(x, _)| *x.value() > Value::zero())
-            {
+                &mut rng,
+            )?;
+            // sort wallets, so block0 content does not depend on hash map ordering
+            let mut wallets: Vec<&WalletTemplate> = generated_wallet_templates
+                .keys()
+                .filter(|x| *x.value() > Value::zero())
+                .collect();
+            wallets.sort_by_key(|wallet| wallet.alias());
+            for wallet in wallets {
                 builder = builder.wallet(wallet.clone());
             }
         }
         println!("building direct voteplan..");
 
-        for vote_plan_def in VitVotePlanDefBuilder::default()
-            .vote_phases(vote_blockchain_time)
-            .options(
-                self.config
-                    .data
-                    .current_fund
-                    .options
-                    .0
-                    .len()
-                    .try_into()
-                    .map_err(|_| Error::TooManyOptions)?,
-            )
-            .split_by(255)
-            .fund_name(
-                self.config
-                    .data
-                    .current_fund
-                    .fund_info
-                    .fund_name
-                    .to_string(),
-            )
-            .committee(self.committee_wallet.clone())
-            .private(self.config.vote_plan.private)
-            .proposals_count(self.config.data.current_fund.proposals as usize)
-            .voting_tokens(
-                token_list
-                    .iter()
-                    .cloned()
-                    .map(|(a, b)| (a, b.into()))
-                    .collect(),
-            )
-            .build()
-            .into_iter()
-        {
+        let committee_keys = election_committee
+            .as_ref()
+            .map(ElectionCommitteeDef::member_public_keys)
+            .transpose()?
+            .unwrap_or_default();
+
+        let (vote_plans, vote_plan_layout) =
+            self.build_vote_plans(vote_blockchain_time, &token_list, &committee_keys)?;
+        for vote_plan_def in vote_plans {
             blockchain = blockchain.with_vote_plan(
                 vote_plan_def.alias(),
                 vote_plan_def.owner(),
@@ -280,7 +385,9 @@ impl VitBackendSettingsBuilder {
             );
         }
 
-        builder = builder.blockchain(blockchain);
+        builder = builder
+            .blockchain(blockchain)
+            .vote_plan_layout(vote_plan_layout);
         println!("building controllers..");
 
         let controller = builder.build(self.session_settings.clone())?;
@@ -289,6 +396,34 @@ impl VitBackendSettingsBuilder {
             self.dump_qrs(&controller, &generated_wallet_templates, &root)?;
         }
 
+        if let Protocol::Https(certs) = &self.protocol {
+            if let Some(ca_path) = export_ca(certs, DeploymentTree::new(&root).qr_codes_path())? {
+                println!("CA certificate exported to: {}", ca_path.display());
+            }
+        }
+
+        if let Some(snapshot) = &self.config.initials.snapshot {
+            if snapshot.delegation.is_some() {
+                let registrations = snapshot.registrations(&controller.defined_wallets())?;
+                let path = root.join("registrations.json");
+                std::fs::write(&path, serde_json::to_string_pretty(&registrations)?)?;
+                println!("voter registrations dumped to {:?}", path);
+            }
+        }
+
+        println!("dumping nodes configuration..");
+
+        let tree = DeploymentTree::new(&root);
+        for (alias, node) in controller.defined_nodes() {
+            let config_path = tree.node_config(alias);
+            std::fs::create_dir_all(config_path.parent().unwrap())?;
+            std::fs::write(config_path, serde_yaml::to_string(&node.config)?)?;
+            std::fs::write(
+                tree.node_secret(alias),
+                serde_yaml::to_string(&node.secret)?,
+            )?;
+        }
+
         println!("dumping vote keys..");
 
         controller
@@ -302,11 +437,28 @@ impl VitBackendSettingsBuilder {
             token_list,
             controller.defined_vote_plans(),
             &controller.settings(),
+            election_committee
+                .as_ref()
+                .map(|committee| committee.election_key.as_str()),
         );
         Ok((controller, parameters))
     }
 }
 
+fn random_token_name<R: RngCore>(rng: &mut R) -> TokenName {
+    let mut bytes = [0u8; TOKEN_NAME_MAX_SIZE];
+    rng.fill_bytes(&mut bytes);
+    TokenName::try_from(bytes.to_vec()).unwrap()
+}
+
+fn options_count(options: &VoteOptions) -> Result<u8, Error> {
+    options
+        .0
+        .len()
+        .try_into()
+        .map_err(|_| Error::TooManyOptions)
+}
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error(transparent)]
@@ -314,9 +466,258 @@ pub enum Error {
     #[error(transparent)]
     Qr(#[from] helpers::QrError),
     #[error(transparent)]
+    Committee(#[from] helpers::CommitteeError),
+    #[error(transparent)]
+    Distribution(#[from] crate::config::DistributionError),
+    #[error(transparent)]
+    Snapshot(#[from] crate::config::SnapshotError),
+    #[error(transparent)]
     Controller(#[from] crate::mode::standard::VitControllerError),
+    #[error(transparent)]
+    Topology(#[from] crate::config::TopologyError),
+    #[error(transparent)]
+    Reviews(#[from] crate::config::ReviewsError),
     #[error("too many vote options provided, only 128 are supported")]
     TooManyOptions,
     #[error(transparent)]
     Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Yaml(#[from] serde_yaml::Error),
+    #[error(transparent)]
+    Certs(#[from] crate::config::certs::Error),
+    #[error("no voting token defined for role used by vote plan: {0}")]
+    NoVotingTokenForVotePlan(String),
+    #[error("vote plan definitions contain {defined} proposals in total, while data.current_fund.proposals is {expected}")]
+    VotePlanProposalsMismatch { defined: usize, expected: usize },
+    #[error("each of {definitions} vote plan definitions needs its own challenge, but only {challenges} challenges are defined")]
+    NotEnoughChallenges {
+        definitions: usize,
+        challenges: usize,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::utils::SessionSettingsExtension;
+    use crate::config::{ConfigBuilder, Delegation, SnapshotInitials, VotePlanDefinition};
+    use crate::mode::standard::generate_random_database;
+    use crate::testing::vitup_setup;
+    use assert_fs::TempDir;
+    use chain_impl_mockchain::certificate::VotePlan;
+    use snapshot_lib::registration::{Delegations, VotingRegistration};
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+    use vit_servicing_station_tests::common::data::{
+        ArbitraryValidVotingTemplateGenerator, ValidVotePlanGenerator,
+    };
+
+    fn definition(name: &str, proposals: usize, options: &str) -> VotePlanDefinition {
+        VotePlanDefinition {
+            name: name.to_string(),
+            proposals,
+            options: Some(options.to_string()),
+            private: None,
+            role: None,
+            committee: None,
+        }
+    }
+
+    fn config(definitions: Vec<VotePlanDefinition>) -> Config {
+        let mut config = ConfigBuilder::default().proposals_count(5).build();
+        config.vote_plan.definitions = definitions;
+        config
+    }
+
+    #[test]
+    fn vote_plan_definitions_reach_block0_and_servicing_station_data() {
+        let config = config(vec![
+            definition("defi", 3, "yes,no"),
+            definition("dao", 2, "yes,no,abstain"),
+        ]);
+        let testing_directory = TempDir::new().unwrap();
+        let (controller, vit_parameters, _) =
+            vitup_setup(&config, testing_directory.path().join("vitup")).unwrap();
+
+        let mut definition_by_vote_plan = HashMap::new();
+        for vote_plan_def in controller.defined_vote_plans() {
+            let name = if vote_plan_def.alias().starts_with("defi") {
+                "defi"
+            } else {
+                "dao"
+            };
+            definition_by_vote_plan.insert(vote_plan_def.id(), name);
+
+            let vote_plan: VotePlan = vote_plan_def.into();
+            let (proposals, options) = if name == "defi" { (3, 2) } else { (2, 3) };
+            assert_eq!(vote_plan.proposals().len(), proposals);
+            for proposal in vote_plan.proposals().iter() {
+                assert_eq!(proposal.options().choice_range().len(), options);
+            }
+        }
+
+        let mut snapshot = ValidVotePlanGenerator::new(vit_parameters)
+            .build(&mut ArbitraryValidVotingTemplateGenerator::new());
+        controller.vote_plan_layout().apply(&mut snapshot);
+
+        let challenges = snapshot.challenges();
+        let mut chain_proposal_ids: HashMap<&str, HashSet<Vec<u8>>> = HashMap::new();
+        for info in snapshot.proposals() {
+            let name = definition_by_vote_plan[&info.voteplan.chain_voteplan_id];
+            let challenge = challenges
+                .iter()
+                .find(|challenge| challenge.id == info.proposal.challenge_id)
+                .unwrap();
+            assert_eq!(challenge.title, name);
+            let options = if name == "defi" {
+                "yes,no"
+            } else {
+                "yes,no,abstain"
+            };
+            assert_eq!(info.proposal.chain_vote_options.as_csv_string(), options);
+            chain_proposal_ids
+                .entry(name)
+                .or_default()
+                .insert(info.proposal.chain_proposal_id.clone());
+        }
+        assert_eq!(chain_proposal_ids["defi"].len(), 3);
+        assert_eq!(chain_proposal_ids["dao"].len(), 2);
+    }
+
+    /// Block0, every file from qr codes folder and servicing station database
+    fn seeded_outputs(config: &Config, root: PathBuf) -> Vec<(PathBuf, Vec<u8>)> {
+        let (controller, vit_parameters, _) = vitup_setup(config, root.clone()).unwrap();
+        let tree = DeploymentTree::new(&root);
+        generate_random_database(&tree, vit_parameters, controller.vote_plan_layout(), config)
+            .unwrap();
+
+        let mut qr_codes: Vec<PathBuf> = std::fs::read_dir(tree.qr_codes_path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        qr_codes.sort();
+
+        std::iter::once(tree.block0_path())
+            .chain(qr_codes)
+            .chain(std::iter::once(tree.database_path()))
+            .map(|path| {
+                let content = std::fs::read(&path).unwrap();
+                (path.strip_prefix(&root).unwrap().to_path_buf(), content)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_produces_same_deployment_data() {
+        // dates default to current time, so all builds share single config
+        let mut config = ConfigBuilder::default()
+            .block0_initials_count(10, "1234")
+            .proposals_count(300)
+            .seed(42)
+            .build();
+        let testing_directory = TempDir::new().unwrap();
+
+        let first = seeded_outputs(&config, testing_directory.path().join("first"));
+        let second = seeded_outputs(&config, testing_directory.path().join("second"));
+        for extension in ["png", "txt"] {
+            assert!(first
+                .iter()
+                .any(|(path, _)| path.extension().unwrap_or_default() == extension));
+        }
+        assert_eq!(
+            first.iter().map(|(path, _)| path).collect::<Vec<_>>(),
+            second.iter().map(|(path, _)| path).collect::<Vec<_>>()
+        );
+        for ((path, first), (_, second)) in first.iter().zip(second.iter()) {
+            assert!(
+                first == second,
+                "{} differs between deployments with the same seed",
+                path.display()
+            );
+        }
+
+        config.seed = Some(43);
+        let other = seeded_outputs(&config, testing_directory.path().join("other"));
+        // block0 and database
+        assert_ne!(first.first(), other.first());
+        assert_ne!(first.last(), other.last());
+    }
+
+    #[test]
+    fn registrations_delegate_only_to_representatives() {
+        let delegation: Delegation = serde_yaml::from_str(
+            r#"
+representatives:
+  - name: alice
+    funds: 100
+  - name: clarice
+delegators:
+  - name: bob
+    funds: 10000
+    direct: 1000
+    delegations:
+      - { representative: alice, weight: 1 }
+      - { representative: clarice, weight: 3 }
+"#,
+        )
+        .unwrap();
+        let mut config = ConfigBuilder::default().build();
+        config.initials.snapshot = Some(SnapshotInitials {
+            delegation: Some(delegation.clone()),
+            ..Default::default()
+        });
+        let testing_directory = TempDir::new().unwrap();
+        let root = testing_directory.path().join("vitup");
+        let (controller, _, _) = vitup_setup(&config, root.clone()).unwrap();
+
+        let registrations: Vec<VotingRegistration> = serde_json::from_str(
+            &std::fs::read_to_string(root.join("registrations.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(registrations.len(), 3);
+        assert_eq!(
+            registrations
+                .iter()
+                .map(|registration| u64::from(registration.voting_power))
+                .sum::<u64>(),
+            10100
+        );
+
+        let representatives: HashSet<_> = delegation
+            .voter_hirs(&controller.defined_wallets())
+            .unwrap()
+            .into_iter()
+            .filter(|hir| hir.voting_group == Role::Representative.to_string())
+            .map(|hir| hir.voting_key)
+            .collect();
+        assert_eq!(representatives.len(), 2);
+        for registration in &registrations {
+            if let Delegations::New(weights) = &registration.delegations {
+                assert_eq!(weights.len(), 2);
+                assert!(weights.iter().all(|(key, _)| representatives.contains(key)));
+            }
+        }
+    }
+
+    #[test]
+    fn vote_plan_definitions_must_cover_all_proposals() {
+        let config = config(vec![
+            definition("defi", 3, "yes,no"),
+            definition("dao", 1, "yes,no"),
+        ]);
+        let testing_directory = TempDir::new().unwrap();
+
+        let result = VitBackendSettingsBuilder::default()
+            .config(&config)
+            .session_settings(SessionSettings::from_dir(testing_directory.path()))
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(Error::VotePlanProposalsMismatch {
+                defined: 4,
+                expected: 5
+            })
+        ));
+    }
 }
@@ -1,48 +1,83 @@
+use crate::config::{Config, RandomStream, ReviewsProfile};
 use crate::Result;
+use fake::faker::lorem::en::Sentence;
 use fake::faker::name::en::Name;
 use fake::Fake;
-use rand::rngs::OsRng;
 use rand::RngCore;
+use rand_chacha::ChaChaRng;
+use rand_core::SeedableRng;
+use std::collections::VecDeque;
 use vit_servicing_station_lib::db::models::community_advisors_reviews::ReviewRanking;
-use vit_servicing_station_tests::common::data::ProposalTemplate;
-use vit_servicing_station_tests::common::data::ReviewTemplate;
+use vit_servicing_station_tests::common::data::{
+    ChallengeTemplate, FundTemplate, ProposalTemplate, ReviewTemplate, ValidVotingTemplateGenerator,
+};
 
+#[derive(Clone)]
 pub struct ReviewGenerator {
-    generator: OsRng,
+    generator: ChaChaRng,
     current_id: u32,
-    assessors_per_proposal_count: u32,
+    profile: ReviewsProfile,
 }
 
 impl ReviewGenerator {
-    pub fn new(assessors_per_proposal_count: u32) -> ReviewGenerator {
+    pub fn new(profile: ReviewsProfile) -> ReviewGenerator {
         ReviewGenerator {
-            generator: OsRng,
+            generator: ChaChaRng::from_entropy(),
             current_id: 1,
-            assessors_per_proposal_count,
+            profile,
+        }
+    }
+
+    /// Generator using `reviews` profile and seed from config
+    pub fn from_config(config: &Config) -> ReviewGenerator {
+        Self::new(config.reviews.clone()).with_rng(config.rng(RandomStream::Reviews))
+    }
+
+    pub fn with_rng(mut self, generator: ChaChaRng) -> Self {
+        self.generator = generator;
+        self
+    }
+
+    /// Wraps template generator, so reviews in servicing station data follow the profile
+    pub fn templates<G: ValidVotingTemplateGenerator>(
+        self,
+        inner: G,
+    ) -> ProfiledTemplateGenerator<G> {
+        ProfiledTemplateGenerator {
+            inner,
+            reviews: self,
+            proposals: Vec::new(),
+            next_proposal: 0,
+            pending: VecDeque::new(),
         }
     }
 
     pub fn generate(&mut self, proposals: Vec<ProposalTemplate>) -> Result<Vec<ReviewTemplate>> {
-        let mut reviews = Vec::new();
+        self.profile.validate()?;
         self.current_id = 1;
-        for proposal in proposals.iter() {
-            for _ in 0..self.assessors_per_proposal_count {
-                let assessor = self.generate_assessor_name();
-                if let Some(ref challenge_id) = &proposal.challenge_id {
-                    if challenge_id.parse::<u32>().unwrap() == 1 {
-                        reviews.push(self.generate_community_reviews(proposal, assessor));
-                    } else {
-                        reviews.push(self.generate_standard_reviews(proposal, assessor));
-                    }
-                    self.increment_id();
-                } else {
-                    return Err(crate::error::Error::NoChallengeIdFound {
-                        proposal_id: proposal.proposal_id.clone(),
-                    });
-                }
-            }
+        Ok(proposals
+            .iter()
+            .flat_map(|proposal| self.reviews_for(proposal))
+            .collect())
+    }
+
+    /// Reviews for single proposal. Proposals from challenge types listed in profile
+    /// get community advisors reviews, others get standard ones
+    fn reviews_for(&mut self, proposal: &ProposalTemplate) -> Vec<ReviewTemplate> {
+        let community_advisors = self
+            .profile
+            .is_reviewed(&proposal.challenge_type.to_string());
+        let mut reviews = Vec::new();
+        for _ in 0..self.profile.count.sample(&mut self.generator) {
+            let assessor = self.generate_assessor_name();
+            reviews.push(if community_advisors {
+                self.generate_community_review(proposal, assessor)
+            } else {
+                self.generate_standard_review(proposal, assessor)
+            });
+            self.increment_id();
         }
-        Ok(reviews)
+        reviews
     }
 
     fn increment_id(&mut self) {
@@ -52,30 +87,43 @@ impl ReviewGenerator {
     fn generate_assessor_name(&mut self) -> String {
         format!(
             "{}_{}",
-            Name().fake::<String>().to_lowercase().replace("' '", "_"),
+            Name()
+                .fake_with_rng::<String, _>(&mut self.generator)
+                .to_lowercase()
+                .replace("' '", "_"),
             (self.generator.next_u32() % 100)
         )
     }
 
-    fn generate_community_reviews<S: Into<String>>(
+    fn generate_community_review<S: Into<String>>(
         &mut self,
         proposal: &ProposalTemplate,
         assessor: S,
     ) -> ReviewTemplate {
-        let assessor = assessor.into();
-        self.generate_review(proposal, &assessor)
-    }
-
-    fn generate_standard_reviews<S: Into<String>>(
-        &mut self,
-        proposal: &ProposalTemplate,
-        assessor: S,
-    ) -> ReviewTemplate {
-        let assessor = assessor.into();
-        self.generate_review(proposal, &assessor)
+        ReviewTemplate {
+            id: Some(self.current_id.to_string()),
+            proposal_id: proposal.proposal_id.parse().unwrap(),
+            impact_alignment_rating_given: ReviewsProfile::sample_rating(
+                &self.profile.impact_alignment_rating,
+                &mut self.generator,
+            ),
+            impact_alignment_note: self.generate_note(),
+            assessor: assessor.into(),
+            auditability_note: self.generate_note(),
+            auditability_rating_given: ReviewsProfile::sample_rating(
+                &self.profile.auditability_rating,
+                &mut self.generator,
+            ),
+            feasibility_note: self.generate_note(),
+            feasibility_rating_given: ReviewsProfile::sample_rating(
+                &self.profile.feasibility_rating,
+                &mut self.generator,
+            ),
+            ranking: self.profile.ranking.sample(&mut self.generator),
+        }
     }
 
-    fn generate_review<S: Into<String>>(
+    fn generate_standard_review<S: Into<String>>(
         &mut self,
         proposal: &ProposalTemplate,
         assessor: S,
@@ -84,11 +132,11 @@ impl ReviewGenerator {
             id: Some(self.current_id.to_string()),
             proposal_id: proposal.proposal_id.parse().unwrap(),
             impact_alignment_rating_given: (self.generator.next_u32() % 5) as i32,
-            impact_alignment_note: fake::faker::lorem::en::Sentence(0..10).fake::<String>(),
+            impact_alignment_note: Sentence(0..10).fake_with_rng::<String, _>(&mut self.generator),
             assessor: assessor.into(),
-            auditability_note: fake::faker::lorem::en::Sentence(0..10).fake::<String>(),
+            auditability_note: Sentence(0..10).fake_with_rng::<String, _>(&mut self.generator),
             auditability_rating_given: (self.generator.next_u32() % 5) as i32,
-            feasibility_note: fake::faker::lorem::en::Sentence(0..10).fake::<String>(),
+            feasibility_note: Sentence(0..10).fake_with_rng::<String, _>(&mut self.generator),
             feasibility_rating_given: (self.generator.next_u32() % 5) as i32,
             ranking: match self.generator.next_u32() % 2 {
                 0 => ReviewRanking::Excellent,
@@ -96,4 +144,97 @@ impl ReviewGenerator {
             },
         }
     }
+
+    fn generate_note(&mut self) -> String {
+        let words = self.profile.note_length.sample(&mut self.generator);
+        Sentence(words..words + 1).fake_with_rng::<String, _>(&mut self.generator)
+    }
+}
+
+/// Template generator which replaces reviews of wrapped generator with ones from
+/// [`ReviewGenerator`]. Total number of reviews is still driven by caller (`current_fund.reviews`),
+/// while every proposal receives `count` reviews from profile in turns.
+pub struct ProfiledTemplateGenerator<G: ValidVotingTemplateGenerator> {
+    inner: G,
+    reviews: ReviewGenerator,
+    proposals: Vec<ProposalTemplate>,
+    next_proposal: usize,
+    pending: VecDeque<ReviewTemplate>,
+}
+
+impl<G: ValidVotingTemplateGenerator> ValidVotingTemplateGenerator
+    for ProfiledTemplateGenerator<G>
+{
+    fn next_proposal(&mut self) -> ProposalTemplate {
+        let proposal = self.inner.next_proposal();
+        self.proposals.push(proposal.clone());
+        proposal
+    }
+
+    fn next_challenge(&mut self) -> ChallengeTemplate {
+        self.inner.next_challenge()
+    }
+
+    fn next_fund(&mut self) -> FundTemplate {
+        self.inner.next_fund()
+    }
+
+    fn next_review(&mut self) -> ReviewTemplate {
+        // every proposal may be drawn zero reviews, so give up after one full round
+        for _ in 0..self.proposals.len() {
+            if let Some(review) = self.pending.pop_front() {
+                return review;
+            }
+            let proposal = self.proposals[self.next_proposal % self.proposals.len()].clone();
+            self.next_proposal += 1;
+            self.pending.extend(self.reviews.reviews_for(&proposal));
+        }
+        self.pending
+            .pop_front()
+            .unwrap_or_else(|| self.inner.next_review())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Range;
+    use std::collections::HashMap;
+    use vit_servicing_station_tests::common::data::ArbitraryValidVotingTemplateGenerator;
+
+    #[test]
+    fn reviews_follow_profile_and_challenge_type() {
+        let profile = ReviewsProfile {
+            count: Range::fixed(2),
+            impact_alignment_rating: [(0, 1)].into_iter().collect(),
+            feasibility_rating: [(0, 1)].into_iter().collect(),
+            auditability_rating: [(0, 1)].into_iter().collect(),
+            challenge_types: vec!["community-choice".to_string()],
+            ..Default::default()
+        };
+        let mut generator =
+            ReviewGenerator::new(profile).templates(ArbitraryValidVotingTemplateGenerator::new());
+        let _ = generator.next_fund();
+        let _ = generator.next_challenge();
+        let proposals: Vec<ProposalTemplate> = (0..3).map(|_| generator.next_proposal()).collect();
+
+        let mut reviews_per_proposal: HashMap<String, usize> = HashMap::new();
+        for _ in 0..6 {
+            let review = generator.next_review();
+            let proposal = proposals
+                .iter()
+                .find(|proposal| proposal.proposal_id == review.proposal_id.to_string())
+                .unwrap();
+            if proposal.challenge_type.to_string() == "community-choice" {
+                assert_eq!(review.impact_alignment_rating_given, 0);
+                assert_eq!(review.feasibility_rating_given, 0);
+                assert_eq!(review.auditability_rating_given, 0);
+            }
+            *reviews_per_proposal
+                .entry(proposal.proposal_id.clone())
+                .or_default() += 1;
+        }
+        assert_eq!(reviews_per_proposal.len(), 3);
+        assert!(reviews_per_proposal.values().all(|count| *count == 2));
+    }
 }
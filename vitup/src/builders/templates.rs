@@ -0,0 +1,258 @@
+use crate::builders::reviews::{ProfiledTemplateGenerator, ReviewGenerator};
+use crate::config::{Config, RandomStream};
+use assert_fs::fixture::FixtureError;
+use assert_fs::TempDir;
+use fake::faker::internet::en::SafeEmail;
+use fake::faker::lorem::en::{Paragraph, Sentence};
+use fake::faker::name::en::Name;
+use fake::Fake;
+use rand::{Rng, RngCore};
+use rand_chacha::ChaChaRng;
+use serde_json::{json, Value};
+use std::path::Path;
+use thiserror::Error;
+use vit_servicing_station_tests::common::data::{
+    ExternalValidVotingTemplateGenerator, TemplateLoad,
+};
+
+const COMMUNITY_CHOICE: &str = "community-choice";
+const SIMPLE: &str = "simple";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    TemplateLoad(#[from] TemplateLoad),
+}
+
+/// Servicing station data (funds, challenges, proposals and reviews) drawn from `data` random
+/// stream of config. Data is written in the same format as external files accepted by
+/// `--proposals`, `--challenges` etc. (see `resources/example`), so seeded deployment
+/// produces the same database on each run.
+pub struct TemplatesGenerator {
+    generator: ChaChaRng,
+    config: Config,
+}
+
+impl TemplatesGenerator {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            generator: config.rng(RandomStream::Data),
+            config: config.clone(),
+        }
+    }
+
+    /// Template generator for servicing station data with reviews following `reviews` profile
+    pub fn build(
+        self,
+    ) -> Result<ProfiledTemplateGenerator<ExternalValidVotingTemplateGenerator>, Error> {
+        let reviews = ReviewGenerator::from_config(&self.config);
+        let folder = TempDir::new()?;
+        Ok(reviews.templates(self.write(folder.path())?))
+    }
+
+    /// Dumps data into `funds.json`, `challenges.json`, `proposals.json` and `review.json`
+    /// in given folder and loads them back
+    pub fn write<P: AsRef<Path>>(
+        mut self,
+        folder: P,
+    ) -> Result<ExternalValidVotingTemplateGenerator, Error> {
+        let folder = folder.as_ref();
+        let challenges = self.challenges();
+        let proposals = self.proposals(&challenges);
+        let reviews = self.reviews(&proposals);
+        let funds = self.funds();
+
+        let files: Vec<_> = [
+            ("proposals.json", proposals),
+            ("challenges.json", challenges),
+            ("funds.json", funds),
+            ("review.json", reviews),
+        ]
+        .into_iter()
+        .map(|(name, content)| {
+            let path = folder.join(name);
+            std::fs::write(&path, serde_json::to_string_pretty(&content)?)?;
+            Ok(path)
+        })
+        .collect::<Result<_, Error>>()?;
+
+        ExternalValidVotingTemplateGenerator::new(
+            files[0].clone(),
+            files[1].clone(),
+            files[2].clone(),
+            files[3].clone(),
+        )
+        .map_err(Into::into)
+    }
+
+    fn funds(&mut self) -> Vec<Value> {
+        let current = &self.config.data.current_fund.fund_info;
+        let ids: Vec<i32> = std::iter::once(current.fund_id)
+            .chain(
+                self.config
+                    .data
+                    .next_funds
+                    .iter()
+                    .map(|fund| fund.fund_info.fund_id),
+            )
+            .collect();
+        ids.into_iter()
+            .map(|id| {
+                json!({
+                    "id": id,
+                    "goal": self.sentence(),
+                    "rewards_info": self.sentence(),
+                    "threshold": self.generator.gen_range(100..1000),
+                })
+            })
+            .collect()
+    }
+
+    /// First challenge is community choice one, others are simple
+    fn challenges(&mut self) -> Vec<Value> {
+        let fund_id = self.config.data.current_fund.fund_info.fund_id;
+        (1..=self.config.data.current_fund.challenges)
+            .map(|id| {
+                let challenge_type = if id == 1 { COMMUNITY_CHOICE } else { SIMPLE };
+                let rewards_total = self.generator.gen_range(10..1000) * 1000;
+                json!({
+                    "challenge_type": challenge_type,
+                    "challenge_url": format!("https://cardano.ideascale.com/a/campaign-home/{}", id),
+                    "description": self.paragraph(),
+                    "fund_id": fund_id.to_string(),
+                    "id": id.to_string(),
+                    "internal_id": id,
+                    "proposers_rewards": (rewards_total / 10).to_string(),
+                    "rewards_total": rewards_total.to_string(),
+                    "title": self.title(),
+                })
+            })
+            .collect()
+    }
+
+    /// Proposals are assigned to challenges in turns
+    fn proposals(&mut self, challenges: &[Value]) -> Vec<Value> {
+        let current_fund = &self.config.data.current_fund;
+        let category_name = current_fund.fund_info.fund_name.clone();
+        let chain_vote_options = current_fund.options.as_csv_string();
+        let chain_vote_type = if self.config.vote_plan.private {
+            "private"
+        } else {
+            "public"
+        };
+
+        (0..current_fund.proposals as usize)
+            .map(|internal_id| {
+                let challenge = &challenges[internal_id % challenges.len()];
+                let challenge_type = challenge["challenge_type"].as_str().unwrap();
+                let mut proposal = json!({
+                    "category_name": category_name,
+                    "chain_vote_options": chain_vote_options,
+                    "chain_vote_type": chain_vote_type,
+                    "challenge_id": challenge["id"],
+                    "challenge_type": challenge_type,
+                    "internal_id": internal_id.to_string(),
+                    "proposal_funds": (self.generator.gen_range(1..100) * 1000).to_string(),
+                    "proposal_id": (internal_id + 1).to_string(),
+                    "proposal_impact_score": self.generator.gen_range(100..500).to_string(),
+                    "proposal_summary": self.sentence(),
+                    "proposal_title": self.title(),
+                    "proposal_url": format!("http://ideascale.com/t/{}", internal_id),
+                    "proposer_email": SafeEmail().fake_with_rng::<String, _>(&mut self.generator),
+                    "proposer_name": Name().fake_with_rng::<String, _>(&mut self.generator),
+                    "proposer_url": format!("http://proposer.com/{}", internal_id),
+                });
+
+                let specific = if challenge_type == COMMUNITY_CHOICE {
+                    json!({
+                        "proposal_brief": self.paragraph(),
+                        "proposal_importance": self.sentence(),
+                        "proposal_goal": self.sentence(),
+                        "proposal_metrics": self.sentence(),
+                    })
+                } else {
+                    json!({
+                        "proposal_solution": self.sentence(),
+                        "proposer_relevant_experience": self.sentence(),
+                    })
+                };
+                let proposal_fields = proposal.as_object_mut().unwrap();
+                for (key, value) in specific.as_object().unwrap() {
+                    proposal_fields.insert(key.clone(), value.clone());
+                }
+                proposal
+            })
+            .collect()
+    }
+
+    /// Reviews are assigned to proposals in turns
+    fn reviews(&mut self, proposals: &[Value]) -> Vec<Value> {
+        (1..=self.config.data.current_fund.reviews)
+            .map(|id| {
+                let proposal = &proposals[(id - 1) % proposals.len()];
+                json!({
+                    "id": id.to_string(),
+                    "proposal_id": proposal["proposal_id"],
+                    "assessor": format!(
+                        "{}_{}",
+                        Name()
+                            .fake_with_rng::<String, _>(&mut self.generator)
+                            .to_lowercase()
+                            .replace(' ', "_"),
+                        self.generator.next_u32() % 100
+                    ),
+                    "impact_alignment_rating_given": self.generator.gen_range(1..=5),
+                    "impact_alignment_note": self.sentence(),
+                    "feasibility_rating_given": self.generator.gen_range(1..=5),
+                    "feasibility_note": self.sentence(),
+                    "auditability_rating_given": self.generator.gen_range(1..=5),
+                    "auditability_note": self.sentence(),
+                    "ranking": if self.generator.gen_bool(0.5) { "Excellent" } else { "Good" },
+                })
+            })
+            .collect()
+    }
+
+    fn title(&mut self) -> String {
+        let title: String = Sentence(2..5).fake_with_rng(&mut self.generator);
+        title.trim_end_matches('.').to_string()
+    }
+
+    fn sentence(&mut self) -> String {
+        Sentence(5..15).fake_with_rng(&mut self.generator)
+    }
+
+    fn paragraph(&mut self) -> String {
+        Paragraph(2..4).fake_with_rng(&mut self.generator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigBuilder;
+    use vit_servicing_station_tests::common::data::ValidVotingTemplateGenerator;
+
+    fn proposal_titles(seed: u64) -> Vec<String> {
+        let config = ConfigBuilder::default()
+            .proposals_count(10)
+            .seed(seed)
+            .build();
+        let mut generator = TemplatesGenerator::from_config(&config).build().unwrap();
+        (0..10)
+            .map(|_| generator.next_proposal().proposal_title)
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_produces_same_templates() {
+        assert_eq!(proposal_titles(42), proposal_titles(42));
+        assert_ne!(proposal_titles(42), proposal_titles(43));
+    }
+}
@@ -0,0 +1,17 @@
+use chain_core::packer::Codec;
+use chain_core::property::{Deserialize as _, ReadError};
+use chain_impl_mockchain::block::Block;
+use chain_impl_mockchain::certificate::VotePlan;
+use chain_impl_mockchain::fragment::Fragment;
+
+/// Vote plans registered in serialized block0, in order of their fragments
+pub fn block0_vote_plans(block0: &[u8]) -> Result<Vec<VotePlan>, ReadError> {
+    let block = Block::deserialize(&mut Codec::new(block0))?;
+    Ok(block
+        .fragments()
+        .filter_map(|fragment| match fragment {
+            Fragment::VotePlan(tx) => Some(tx.as_slice().payload().into_payload()),
+            _ => None,
+        })
+        .collect())
+}
@@ -0,0 +1,299 @@
+use crate::config::Config;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use time::OffsetDateTime;
+use walkdir::WalkDir;
+
+pub const MANIFEST: &str = "manifest.json";
+pub const DEPLOYMENT_DIR: &str = "deployment";
+pub const DATA_DIR: &str = "data";
+const FORMAT_VERSION: u32 = 1;
+
+/// Description of bundle content, stored as first entry of the archive.
+/// File paths are relative to archive root and always use `/` as separator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format_version: u32,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created: OffsetDateTime,
+    pub seed: Option<u64>,
+    pub tools: BTreeMap<String, String>,
+    pub config: Config,
+    pub files: BTreeMap<String, String>,
+}
+
+impl BundleManifest {
+    /// Path of static data file (e.g. `proposals.json`) if it was bundled
+    pub fn data_file<P: AsRef<Path>>(&self, root: P, name: &str) -> Option<PathBuf> {
+        let entry = format!("{}/{}", DATA_DIR, name);
+        self.files
+            .contains_key(&entry)
+            .then(|| root.as_ref().join(entry))
+    }
+
+    /// Path of deployment artifact (relative to deployment folder) which has to be bundled
+    pub fn deployment_file<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        root: P,
+        relative: Q,
+    ) -> Result<PathBuf, Error> {
+        let entry = format!("{}/{}", DEPLOYMENT_DIR, archive_path(relative.as_ref()));
+        if self.files.contains_key(&entry) {
+            Ok(root.as_ref().join(entry))
+        } else {
+            Err(Error::MissingArtifact(entry))
+        }
+    }
+
+    /// Whether any file is bundled under deployment folder (e.g. `qr-codes`)
+    pub fn has_deployment_dir<P: AsRef<Path>>(&self, relative: P) -> bool {
+        let prefix = format!("{}/{}/", DEPLOYMENT_DIR, archive_path(relative.as_ref()));
+        self.files.keys().any(|name| name.starts_with(&prefix))
+    }
+
+    /// Checks that extracted bundle contains exactly files listed in manifest
+    /// with expected checksums
+    pub fn verify<P: AsRef<Path>>(&self, root: P) -> Result<(), Error> {
+        let root = root.as_ref();
+        let actual = checksums(root)?;
+
+        for (name, expected) in &self.files {
+            match actual.get(name) {
+                None => return Err(Error::MissingFile(name.clone())),
+                Some(checksum) if checksum != expected => {
+                    return Err(Error::ChecksumMismatch {
+                        file: name.clone(),
+                        expected: expected.clone(),
+                        actual: checksum.clone(),
+                    })
+                }
+                Some(_) => (),
+            }
+        }
+
+        if let Some(name) = actual
+            .keys()
+            .find(|name| *name != MANIFEST && !self.files.contains_key(*name))
+        {
+            return Err(Error::UnexpectedFile(name.clone()));
+        }
+        Ok(())
+    }
+}
+
+/// Packs deployment folder (see [`DeploymentTree`](super::DeploymentTree)) together with
+/// configuration and optional static data files into single `tar.gz` archive
+pub struct BundleBuilder {
+    deployment: PathBuf,
+    config: Config,
+    data: Vec<(String, PathBuf)>,
+    tools: BTreeMap<String, String>,
+}
+
+impl BundleBuilder {
+    pub fn new<P: AsRef<Path>>(deployment: P, config: Config) -> Self {
+        let mut tools = BTreeMap::new();
+        tools.insert("vitup".to_string(), env!("CARGO_PKG_VERSION").to_string());
+        Self {
+            deployment: deployment.as_ref().to_path_buf(),
+            config,
+            data: Vec::new(),
+            tools,
+        }
+    }
+
+    pub fn data_file<S: Into<String>, P: AsRef<Path>>(mut self, name: S, path: P) -> Self {
+        self.data.push((name.into(), path.as_ref().to_path_buf()));
+        self
+    }
+
+    pub fn tool<S: Into<String>>(mut self, name: S, version: S) -> Self {
+        self.tools.insert(name.into(), version.into());
+        self
+    }
+
+    pub fn build<P: AsRef<Path>>(self, output: P) -> Result<BundleManifest, Error> {
+        if !self.deployment.is_dir() {
+            return Err(Error::NotADirectory(self.deployment));
+        }
+
+        let mut entries: BTreeMap<String, PathBuf> = BTreeMap::new();
+        for entry in WalkDir::new(&self.deployment).sort_by_file_name() {
+            let entry = entry?;
+            if entry.file_type().is_file() {
+                let relative = entry.path().strip_prefix(&self.deployment).unwrap();
+                entries.insert(
+                    format!("{}/{}", DEPLOYMENT_DIR, archive_path(relative)),
+                    entry.path().to_path_buf(),
+                );
+            }
+        }
+        for (name, path) in &self.data {
+            entries.insert(format!("{}/{}", DATA_DIR, name), path.clone());
+        }
+
+        let mut files = BTreeMap::new();
+        for (name, path) in &entries {
+            files.insert(name.clone(), checksum(path)?);
+        }
+
+        let manifest = BundleManifest {
+            format_version: FORMAT_VERSION,
+            created: OffsetDateTime::now_utc(),
+            seed: self.config.seed,
+            tools: self.tools,
+            config: self.config,
+            files,
+        };
+
+        let encoder = GzEncoder::new(File::create(output.as_ref())?, Compression::default());
+        let mut archive = tar::Builder::new(encoder);
+
+        let content = serde_json::to_vec_pretty(&manifest)?;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        archive.append_data(&mut header, MANIFEST, content.as_slice())?;
+
+        for (name, path) in &entries {
+            archive.append_path_with_name(path, name)?;
+        }
+        archive.into_inner()?.finish()?;
+        Ok(manifest)
+    }
+}
+
+/// Extracts bundle into target folder and verifies its content against manifest
+pub fn unpack_bundle<P: AsRef<Path>, Q: AsRef<Path>>(
+    bundle: P,
+    target: Q,
+) -> Result<BundleManifest, Error> {
+    let target = target.as_ref();
+    std::fs::create_dir_all(target)?;
+    tar::Archive::new(GzDecoder::new(File::open(bundle.as_ref())?)).unpack(target)?;
+
+    let manifest_path = target.join(MANIFEST);
+    if !manifest_path.exists() {
+        return Err(Error::MissingFile(MANIFEST.to_string()));
+    }
+    let manifest: BundleManifest = serde_json::from_str(&std::fs::read_to_string(manifest_path)?)?;
+    if manifest.format_version != FORMAT_VERSION {
+        return Err(Error::UnsupportedFormatVersion(manifest.format_version));
+    }
+    manifest.verify(target)?;
+    Ok(manifest)
+}
+
+fn checksums(root: &Path) -> Result<BTreeMap<String, String>, Error> {
+    let mut checksums = BTreeMap::new();
+    for entry in WalkDir::new(root) {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            let relative = entry.path().strip_prefix(root).unwrap();
+            checksums.insert(archive_path(relative), checksum(entry.path())?);
+        }
+    }
+    Ok(checksums)
+}
+
+fn checksum(path: &Path) -> Result<String, Error> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+fn archive_path(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("deployment should be a directory: {0}")]
+    NotADirectory(PathBuf),
+    #[error("file '{0}' listed in manifest is missing in bundle")]
+    MissingFile(String),
+    #[error("bundle does not contain '{0}' required to start deployment")]
+    MissingArtifact(String),
+    #[error("file '{0}' is not listed in bundle manifest")]
+    UnexpectedFile(String),
+    #[error("checksum mismatch for '{file}': expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        file: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("unsupported bundle format version: {0}")]
+    UnsupportedFormatVersion(u32),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    WalkDir(#[from] walkdir::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::fixture::PathChild;
+    use assert_fs::prelude::FileWriteStr;
+    use assert_fs::TempDir;
+
+    #[test]
+    fn bundle_roundtrip_detects_tampering() {
+        let temp_dir = TempDir::new().unwrap();
+        let deployment = temp_dir.child("deployment");
+        deployment.child("block0.bin").write_str("block0").unwrap();
+        deployment
+            .child("qr-codes/wallet_alice_1234.png")
+            .write_str("qr")
+            .unwrap();
+        temp_dir.child("proposals.json").write_str("[]").unwrap();
+
+        let bundle = temp_dir.child("bundle.tar.gz");
+        let manifest = BundleBuilder::new(deployment.path(), Config::default())
+            .data_file("proposals.json", temp_dir.child("proposals.json").path())
+            .build(bundle.path())
+            .unwrap();
+        assert_eq!(manifest.files.len(), 3);
+
+        let target = temp_dir.child("extracted");
+        let unpacked = unpack_bundle(bundle.path(), target.path()).unwrap();
+        assert_eq!(unpacked.files, manifest.files);
+        assert!(unpacked
+            .data_file(target.path(), "proposals.json")
+            .is_some());
+        assert_eq!(
+            unpacked
+                .deployment_file(target.path(), "block0.bin")
+                .unwrap(),
+            target.path().join("deployment/block0.bin")
+        );
+        assert!(unpacked.has_deployment_dir("qr-codes"));
+        assert!(matches!(
+            unpacked.deployment_file(target.path(), "database.sqlite3"),
+            Err(Error::MissingArtifact(_))
+        ));
+
+        target
+            .child("deployment/block0.bin")
+            .write_str("changed")
+            .unwrap();
+        assert!(matches!(
+            unpacked.verify(target.path()),
+            Err(Error::ChecksumMismatch { .. })
+        ));
+    }
+}
@@ -1,5 +1,12 @@
+mod block0;
+mod bundle;
 mod session_settings;
 mod tree;
 
+pub use block0::block0_vote_plans;
+pub use bundle::{
+    unpack_bundle, BundleBuilder, BundleManifest, Error as BundleError, DATA_DIR, DEPLOYMENT_DIR,
+    MANIFEST,
+};
 pub use session_settings::SessionSettingsExtension;
 pub use tree::DeploymentTree;
@@ -43,6 +43,22 @@ impl DeploymentTree {
     pub fn voting_token(&self) -> PathBuf {
         self.root.join("voting_token.txt")
     }
+
+    /// Node configuration dumped while building deployment
+    pub fn node_config<S: AsRef<str>>(&self, alias: S) -> PathBuf {
+        self.root
+            .join("nodes")
+            .join(alias.as_ref())
+            .join("config.yaml")
+    }
+
+    /// Node secret dumped while building deployment
+    pub fn node_secret<S: AsRef<str>>(&self, alias: S) -> PathBuf {
+        self.root
+            .join("nodes")
+            .join(alias.as_ref())
+            .join("secret.yaml")
+    }
 }
 
 impl From<&TempDir> for DeploymentTree {
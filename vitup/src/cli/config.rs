@@ -0,0 +1,43 @@
+use crate::config::{read_config_with_overrides, ConfigFormat, ConfigOverride};
+use crate::Result;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub enum ConfigCommand {
+    /// print final config after resolving 'extends' and applying overrides
+    Show(ConfigShowCommand),
+}
+
+impl ConfigCommand {
+    pub fn exec(self) -> Result<()> {
+        match self {
+            Self::Show(show) => show.exec(),
+        }
+    }
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct ConfigShowCommand {
+    /// configuration (json, yaml or toml)
+    #[structopt(long = "config")]
+    pub config: PathBuf,
+
+    /// override config value, e.g. --set data.current_fund.proposals=500
+    #[structopt(long = "set")]
+    pub overrides: Vec<ConfigOverride>,
+
+    /// output format: json, yaml or toml
+    #[structopt(long = "format", default_value = "json")]
+    pub format: ConfigFormat,
+}
+
+impl ConfigShowCommand {
+    pub fn exec(self) -> Result<()> {
+        let config = read_config_with_overrides(&self.config, &self.overrides)?;
+        println!("{}", self.format.format(&config)?);
+        Ok(())
+    }
+}
@@ -1,17 +1,21 @@
-use crate::builders::utils::DeploymentTree;
+use crate::builders::utils::{block0_vote_plans, DeploymentTree};
 use crate::Result;
 use assert_fs::TempDir;
-use diffy::create_patch;
-use diffy::PatchFormatter;
+use console::Style;
 use jormungandr_automation::testing::block0::decode_block0;
 use jortestkit::prelude::read_file;
-use std::fs::File;
-use std::io::Write;
-use std::path::PathBuf;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
 use structopt::StructOpt;
+use thiserror::Error;
 use valgrind::ValgrindClient;
 use vit_servicing_station_tests::common::startup::server::ServerBootstrapper;
 
+/// exit code returned when local deployment differs from target environment
+pub const DRIFT_EXIT_CODE: i32 = 2;
+
 #[derive(StructOpt, Debug)]
 #[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
 pub struct DiffCommand {
@@ -28,97 +32,451 @@ pub struct DiffCommand {
     #[structopt(short = "o", long = "output")]
     pub output: Option<PathBuf>,
 
+    /// highlight local values in red and remote values in green
     #[structopt(short = "c", long = "color")]
     pub color: bool,
 }
 
 impl DiffCommand {
-    pub fn exec(self) -> Result<()> {
+    /// Returns exit code: `0` when deployments are the same, [`DRIFT_EXIT_CODE`] otherwise.
+    /// Caller should exit with it only after this function returns, so local vit-ss
+    /// and temporary folder are cleaned up
+    pub fn exec(self) -> Result<i32> {
         std::env::set_var("RUST_BACKTRACE", "full");
-        let remote = TempDir::new().unwrap();
+        let temp_dir = TempDir::new().unwrap();
         let deployment_tree = DeploymentTree::new(&self.local);
-        let local_genesis_yaml = deployment_tree.genesis_path();
-        let local_vit_db = deployment_tree.database_path();
         let remote_client = ValgrindClient::new(self.target.clone(), Default::default())?;
 
-        let remote_genesis_yaml = remote.path().join("genesis_remote.yaml");
-
-        decode_block0(remote_client.block0()?, remote_genesis_yaml.clone())?;
-
-        let local_genesis_content = read_file(&local_genesis_yaml)?;
-        let remote_genesis_content = read_file(remote_genesis_yaml)?;
+        let local_block0 = std::fs::read(deployment_tree.block0_path())?;
+        let remote_block0 = remote_client.block0()?;
 
         let server = ServerBootstrapper::new()
-            .with_db_path(local_vit_db.to_str().unwrap())
-            .start_with_exe(&remote, self.vit_station.clone())?;
+            .with_db_path(deployment_tree.database_path().to_str().unwrap())
+            .start_with_exe(&temp_dir, self.vit_station.clone())?;
+        let local_client = server.rest_client();
 
-        let local_funds = server.rest_client().funds()?;
-        let remote_funds = remote_client.funds()?;
+        let mut report = DiffReport::new(
+            deployment_tree.root_path().display().to_string(),
+            self.target.clone(),
+        );
 
-        let local_funds_content = serde_json::to_string_pretty(&local_funds)?;
-        let remote_funds_content = serde_json::to_string_pretty(&remote_funds)?;
+        let local_genesis = genesis(&local_block0, &temp_dir.path().join("genesis_local.yaml"))?;
+        let remote_genesis = genesis(&remote_block0, &temp_dir.path().join("genesis_remote.yaml"))?;
+        for (section, local, remote) in [
+            (
+                "block0.settings",
+                block0_settings(&local_genesis),
+                block0_settings(&remote_genesis),
+            ),
+            (
+                "block0.fees",
+                local_genesis["blockchain_configuration"]["linear_fees"].clone(),
+                remote_genesis["blockchain_configuration"]["linear_fees"].clone(),
+            ),
+            (
+                "block0.committees",
+                block0_committees(&local_genesis),
+                block0_committees(&remote_genesis),
+            ),
+            (
+                "block0.initials",
+                block0_initials(&local_genesis),
+                block0_initials(&remote_genesis),
+            ),
+            (
+                "block0.vote_plans",
+                vote_plans(&local_block0)?,
+                vote_plans(&remote_block0)?,
+            ),
+        ] {
+            report.compare(section, &local, &remote);
+        }
 
-        let group = "group";
-        let mut local_proposals = server.rest_client().proposals(group)?;
-        let mut remote_proposals = remote_client.proposals(group)?;
+        let mut local_fund = serde_json::to_value(local_client.funds()?)?;
+        let mut remote_fund = serde_json::to_value(remote_client.funds()?)?;
+        let groups: BTreeSet<String> = fund_groups(&local_fund)
+            .into_iter()
+            .chain(fund_groups(&remote_fund))
+            .collect();
+        for fund in [&mut local_fund, &mut remote_fund] {
+            if let Some(fund) = fund.as_object_mut() {
+                fund.remove("challenges");
+            }
+        }
+        report.compare("funds", &local_fund, &remote_fund);
 
-        let local_proposals_content = serde_json::to_string_pretty(
-            &local_proposals.sort_by(|a, b| b.proposal.internal_id.cmp(&a.proposal.internal_id)),
-        )?;
-        let remote_proposals_content = serde_json::to_string_pretty(
-            &remote_proposals.sort_by(|a, b| b.proposal.internal_id.cmp(&a.proposal.internal_id)),
-        )?;
+        report.compare(
+            "challenges",
+            &keyed_by(serde_json::to_value(local_client.challenges()?)?, "id"),
+            &keyed_by(serde_json::to_value(remote_client.challenges()?)?, "id"),
+        );
 
-        let address = server.settings().address;
+        let mut proposal_ids = BTreeSet::new();
+        for group in &groups {
+            let local = keyed_by(
+                serde_json::to_value(local_client.proposals(group)?)?,
+                "proposal_id",
+            );
+            let remote = keyed_by(
+                serde_json::to_value(remote_client.proposals(group)?)?,
+                "proposal_id",
+            );
+            for proposals in [&local, &remote] {
+                proposal_ids.extend(proposals.as_object().unwrap().keys().cloned());
+            }
+            report.compare(&format!("proposals.{}", group), &local, &remote);
+        }
 
-        let patch = create_patch(&remote_genesis_content, &local_genesis_content);
-        let f = get_formatter(self.color);
-        let block0_diff_header = format!(
-            "diff http://{}/api/v0/block0 {:?}\n",
-            self.target, local_genesis_yaml
+        let mut local_reviews = Map::new();
+        let mut remote_reviews = Map::new();
+        for proposal_id in &proposal_ids {
+            local_reviews.insert(
+                proposal_id.clone(),
+                reviews(serde_json::to_value(
+                    local_client.advisor_reviews(proposal_id)?,
+                )?),
+            );
+            remote_reviews.insert(
+                proposal_id.clone(),
+                reviews(serde_json::to_value(remote_client.review(proposal_id)?)?),
+            );
+        }
+        report.compare(
+            "reviews",
+            &Value::Object(local_reviews),
+            &Value::Object(remote_reviews),
         );
-        let block0_diff = format!("{}\n", f.fmt_patch(&patch));
 
-        let patch = create_patch(&local_funds_content, &remote_funds_content);
-        let f = get_formatter(self.color);
-        let fund_diff_header = format!(
-            "diff http://{}/api/v0/fund {}/api/v0/fund\n",
-            self.target, address
+        report.compare(
+            "vit_version",
+            &version(&format!("http://{}", server.settings().address))?,
+            &version(&self.target)?,
         );
-        let fund_diff = format!("{}\n", f.fmt_patch(&patch));
 
-        let patch = create_patch(&local_proposals_content, &remote_proposals_content);
-        let f = get_formatter(self.color);
-        let proposals_diff_header = format!(
-            "diff http://{}/api/v0/block0 {}/api/v0/proposals\n",
-            self.target, address
-        );
-        let proposals_diff = format!("{}\n", f.fmt_patch(&patch));
-
-        if let Some(output) = self.output {
-            let mut file = File::create(output)?;
-            file.write_all(block0_diff_header.as_bytes())?;
-            file.write_all(block0_diff.as_bytes())?;
-            file.write_all(fund_diff_header.as_bytes())?;
-            file.write_all(fund_diff.as_bytes())?;
-            file.write_all(proposals_diff_header.as_bytes())?;
-            file.write_all(proposals_diff.as_bytes())?;
-        } else {
-            print!("{}", block0_diff_header);
-            print!("{}", block0_diff);
-            print!("{}", fund_diff_header);
-            print!("{}", fund_diff);
-            print!("{}", proposals_diff_header);
-            print!("{}", proposals_diff);
+        let mut content = serde_json::to_string_pretty(&report)?;
+        if self.color {
+            content = colorize(&content);
+        }
+        match &self.output {
+            Some(output) => std::fs::write(output, content)?,
+            None => println!("{}", content),
+        }
+
+        if report.drift {
+            return Ok(DRIFT_EXIT_CODE);
+        }
+        Ok(0)
+    }
+}
+
+/// Single difference between local deployment and target environment.
+/// Missing `local` or `remote` means that value exists only on the other side.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Change {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiffReport {
+    pub local: String,
+    pub target: String,
+    pub drift: bool,
+    pub sections: BTreeMap<String, Vec<Change>>,
+}
+
+impl DiffReport {
+    pub fn new(local: String, target: String) -> Self {
+        Self {
+            local,
+            target,
+            drift: false,
+            sections: BTreeMap::new(),
+        }
+    }
+
+    pub fn compare(&mut self, section: &str, local: &Value, remote: &Value) {
+        let mut changes = Vec::new();
+        diff_values("", Some(local), Some(remote), &mut changes);
+        self.drift |= !changes.is_empty();
+        self.sections.insert(section.to_string(), changes);
+    }
+}
+
+fn diff_values(path: &str, local: Option<&Value>, remote: Option<&Value>, out: &mut Vec<Change>) {
+    match (local, remote) {
+        (Some(Value::Object(local)), Some(Value::Object(remote))) => {
+            let keys: BTreeSet<&String> = local.keys().chain(remote.keys()).collect();
+            for key in keys {
+                diff_values(&join(path, key), local.get(key), remote.get(key), out);
+            }
+        }
+        (Some(Value::Array(local)), Some(Value::Array(remote))) => {
+            for index in 0..local.len().max(remote.len()) {
+                diff_values(
+                    &join(path, &index.to_string()),
+                    local.get(index),
+                    remote.get(index),
+                    out,
+                );
+            }
+        }
+        (local, remote) if local != remote => out.push(Change {
+            path: path.to_string(),
+            local: local.cloned(),
+            remote: remote.cloned(),
+        }),
+        _ => (),
+    }
+}
+
+/// Styles `local` values of pretty printed report in red and `remote` ones in green
+fn colorize(content: &str) -> String {
+    let local = Style::new().red().force_styling(true);
+    let remote = Style::new().green().force_styling(true);
+    // indentation of multiline value which is currently styled
+    let mut current: Option<(usize, &Style)> = None;
+    let mut lines = Vec::new();
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        let style = match current {
+            Some((level, style)) => {
+                if indent == level {
+                    current = None;
+                }
+                Some(style)
+            }
+            None => {
+                let style = if trimmed.starts_with("\"local\":") {
+                    Some(&local)
+                } else if trimmed.starts_with("\"remote\":") {
+                    Some(&remote)
+                } else {
+                    None
+                };
+                if style.is_some() && (trimmed.ends_with('{') || trimmed.ends_with('[')) {
+                    current = style.map(|style| (indent, style));
+                }
+                style
+            }
+        };
+        lines.push(match style {
+            Some(style) => style.apply_to(line).to_string(),
+            None => line.to_string(),
+        });
+    }
+    lines.join("\n")
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// Converts list of objects into object keyed by given field, so items are matched
+/// by their identity instead of position
+fn keyed_by(list: Value, field: &str) -> Value {
+    Value::Object(
+        list.as_array()
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|item| (key_of(&item[field]), item))
+            .collect(),
+    )
+}
+
+fn key_of(value: &Value) -> String {
+    value
+        .as_str()
+        .map(ToString::to_string)
+        .unwrap_or_else(|| value.to_string())
+}
+
+fn genesis(block0: &[u8], path: &Path) -> Result<Value> {
+    decode_block0(block0.to_vec(), path.to_path_buf())?;
+    serde_yaml::from_str(&read_file(path)?).map_err(Into::into)
+}
+
+fn block0_settings(genesis: &Value) -> Value {
+    let mut settings = genesis["blockchain_configuration"].clone();
+    if let Some(settings) = settings.as_object_mut() {
+        settings.remove("linear_fees");
+        settings.remove("committees");
+    }
+    settings
+}
+
+fn block0_committees(genesis: &Value) -> Value {
+    Value::Object(
+        genesis["blockchain_configuration"]["committees"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+            .iter()
+            .map(|committee| (key_of(committee), Value::Bool(true)))
+            .collect(),
+    )
+}
+
+/// Funds and tokens from block0 initials, keyed by address (and token id).
+/// Certificates are compared separately as decoded vote plans.
+fn block0_initials(genesis: &Value) -> Value {
+    let mut initials = Map::new();
+    for initial in genesis["initial"].as_array().cloned().unwrap_or_default() {
+        for fund in initial["fund"].as_array().cloned().unwrap_or_default() {
+            initials.insert(
+                format!("fund/{}", key_of(&fund["address"])),
+                fund["value"].clone(),
+            );
+        }
+        let token_id = key_of(&initial["token"]["token_id"]);
+        for destination in initial["token"]["to"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+        {
+            initials.insert(
+                format!("token/{}/{}", token_id, key_of(&destination["address"])),
+                destination["value"].clone(),
+            );
         }
-        Ok(())
     }
+    Value::Object(initials)
+}
+
+fn vote_plans(block0: &[u8]) -> Result<Value> {
+    let vote_plans = block0_vote_plans(block0).map_err(|e| Error::Block0Read(e.to_string()))?;
+    Ok(Value::Object(
+        vote_plans
+            .iter()
+            .map(|vote_plan| {
+                (
+                    vote_plan.to_id().to_string(),
+                    json!({
+                        "vote_start": vote_plan.vote_start().to_string(),
+                        "vote_end": vote_plan.vote_end().to_string(),
+                        "committee_end": vote_plan.committee_end().to_string(),
+                        "payload_type": format!("{:?}", vote_plan.payload_type()),
+                        "proposals": vote_plan
+                            .proposals()
+                            .iter()
+                            .map(|proposal| json!({
+                                "external_id": proposal.external_id().to_string(),
+                                "options": proposal.options().choice_range().len(),
+                            }))
+                            .collect::<Vec<_>>(),
+                    }),
+                )
+            })
+            .collect(),
+    ))
 }
 
-fn get_formatter(color: bool) -> PatchFormatter {
-    let mut f = PatchFormatter::new();
-    if color {
-        f = f.with_color();
+fn fund_groups(fund: &Value) -> Vec<String> {
+    fund["groups"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default()
+        .iter()
+        .map(|group| key_of(&group["group_id"]))
+        .collect()
+}
+
+/// Reviews of single proposal (grouped by ranking in rest response) as list ordered by id
+fn reviews(grouped: Value) -> Value {
+    let mut reviews: Vec<Value> = grouped
+        .as_object()
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|(_, reviews)| reviews.as_array().cloned().unwrap_or_default())
+        .collect();
+    reviews.sort_by_key(|review| key_of(&review["id"]));
+    Value::Array(reviews)
+}
+
+fn version(address: &str) -> Result<Value> {
+    let address = address.trim_end_matches('/').trim_end_matches("/api");
+    reqwest::blocking::get(format!("{}/api/vit-version", address))
+        .and_then(|response| response.json())
+        .map_err(|e| Error::Version(e).into())
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("cannot read block0: {0}")]
+    Block0Read(String),
+    #[error("cannot fetch version: {0}")]
+    Version(#[from] reqwest::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_matches_items_by_key() {
+        let local = keyed_by(
+            json!([{ "id": 1, "title": "a" }, { "id": 2, "title": "b" }]),
+            "id",
+        );
+        let remote = keyed_by(
+            json!([{ "id": 2, "title": "changed" }, { "id": 3, "title": "c" }]),
+            "id",
+        );
+
+        let mut report = DiffReport::new("local".to_string(), "remote".to_string());
+        report.compare("challenges", &local, &local);
+        assert!(!report.drift);
+
+        report.compare("challenges", &local, &remote);
+        assert!(report.drift);
+        assert_eq!(
+            report.sections["challenges"],
+            vec![
+                Change {
+                    path: "1".to_string(),
+                    local: Some(json!({ "id": 1, "title": "a" })),
+                    remote: None,
+                },
+                Change {
+                    path: "2.title".to_string(),
+                    local: Some(json!("b")),
+                    remote: Some(json!("changed")),
+                },
+                Change {
+                    path: "3".to_string(),
+                    local: None,
+                    remote: Some(json!({ "id": 3, "title": "c" })),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn colorize_styles_whole_values() {
+        let report = json!({ "changes": [{
+            "path": "1",
+            "local": { "id": 1 },
+            "remote": "c",
+        }]});
+        let content = serde_json::to_string_pretty(&report).unwrap();
+        let colored = colorize(&content);
+        let red = Style::new().red().force_styling(true);
+        let green = Style::new().green().force_styling(true);
+
+        assert!(colored.contains(&red.apply_to(r#"      "local": {"#).to_string()));
+        assert!(colored.contains(&red.apply_to(r#"        "id": 1"#).to_string()));
+        assert!(colored.contains(&red.apply_to(r#"      },"#).to_string()));
+        assert!(colored.contains(&green.apply_to(r#"      "remote": "c""#).to_string()));
+        assert!(colored.contains("\n      \"path\": \"1\",\n"));
     }
-    f
 }
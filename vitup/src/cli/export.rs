@@ -0,0 +1,103 @@
+use crate::builders::utils::BundleBuilder;
+use crate::config::{read_config_with_overrides, ConfigOverride};
+use crate::Result;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub enum ExportCommand {
+    /// pack deployment into single archive with manifest
+    Bundle(ExportBundleCommand),
+}
+
+impl ExportCommand {
+    pub fn exec(self) -> Result<()> {
+        match self {
+            Self::Bundle(bundle) => bundle.exec(),
+        }
+    }
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct ExportBundleCommand {
+    /// deployment folder
+    #[structopt(long = "deployment")]
+    pub deployment: PathBuf,
+
+    /// config used to generate deployment
+    #[structopt(long = "config")]
+    pub config: PathBuf,
+
+    /// override config value, e.g. --set data.current_fund.proposals=500
+    #[structopt(long = "set")]
+    pub overrides: Vec<ConfigOverride>,
+
+    /// proposals import json used to generate deployment
+    #[structopt(long = "proposals", requires_all = &["challenges", "funds", "reviews"])]
+    pub proposals: Option<PathBuf>,
+
+    /// challenges import json used to generate deployment
+    #[structopt(long = "challenges")]
+    pub challenges: Option<PathBuf>,
+
+    /// funds import json used to generate deployment
+    #[structopt(long = "funds")]
+    pub funds: Option<PathBuf>,
+
+    /// reviews import json used to generate deployment
+    #[structopt(long = "reviews")]
+    pub reviews: Option<PathBuf>,
+
+    /// path or name of the jormungandr, its version is stored in manifest
+    #[structopt(long = "jormungandr", default_value = "jormungandr")]
+    pub jormungandr: PathBuf,
+
+    /// output archive
+    #[structopt(long = "output", default_value = "./deployment.tar.gz")]
+    pub output: PathBuf,
+}
+
+impl ExportBundleCommand {
+    pub fn exec(self) -> Result<()> {
+        let config = read_config_with_overrides(&self.config, &self.overrides)?;
+        let mut builder = BundleBuilder::new(&self.deployment, config);
+
+        for (name, path) in [
+            ("proposals.json", &self.proposals),
+            ("challenges.json", &self.challenges),
+            ("funds.json", &self.funds),
+            ("reviews.json", &self.reviews),
+        ] {
+            if let Some(path) = path {
+                builder = builder.data_file(name, path);
+            }
+        }
+
+        if let Some(version) = tool_version(&self.jormungandr) {
+            builder = builder.tool("jormungandr".to_string(), version);
+        }
+
+        let manifest = builder.build(&self.output)?;
+        println!(
+            "Bundle with {} files saved to: {}",
+            manifest.files.len(),
+            self.output.display()
+        );
+        if manifest.seed.is_none() {
+            println!("Note: config has no seed, so fresh `vitup start` from this config (unlike start from bundle) will produce different artifacts");
+        }
+        Ok(())
+    }
+}
+
+fn tool_version(tool: &Path) -> Option<String> {
+    Command::new(tool)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -1,3 +1,4 @@
+use crate::builders::ElectionCommittee;
 use crate::Result;
 use chain_addr::AddressReadable;
 use chain_crypto::bech32::Bech32;
@@ -5,8 +6,11 @@ use chain_crypto::Ed25519;
 use chain_crypto::PublicKey;
 use chain_impl_mockchain::vote::CommitteeId;
 use jormungandr_lib::interfaces::CommitteeIdDef;
+use rand_chacha::ChaChaRng;
+use rand_core::{RngCore, SeedableRng};
 use std::io::stdout;
 use std::io::Write;
+use std::path::PathBuf;
 use structopt::StructOpt;
 
 #[derive(StructOpt, Debug)]
@@ -54,3 +58,86 @@ impl CommitteeIdCommandArgs {
         .map_err(Into::into)
     }
 }
+
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct ElectionCommitteeCommandArgs {
+    /// number of committee members
+    #[structopt(short = "m", long = "members", default_value = "3")]
+    pub members: usize,
+
+    /// how many members are required to decrypt tally
+    #[structopt(short = "t", long = "threshold", default_value = "2")]
+    pub threshold: usize,
+
+    /// common reference string. Random if not provided
+    #[structopt(long = "crs")]
+    pub crs: Option<String>,
+
+    /// seed for key generation, useful for reproducible test environments
+    #[structopt(long = "seed")]
+    pub seed: Option<u64>,
+
+    #[structopt(long = "alias-prefix", default_value = "committee_member")]
+    pub alias_prefix: String,
+
+    /// Careful! directory would be removed before export
+    #[structopt(short = "o", long = "output", default_value = "./election_committee")]
+    pub output_directory: PathBuf,
+}
+
+impl ElectionCommitteeCommandArgs {
+    pub fn exec(self) -> Result<()> {
+        std::env::set_var("RUST_BACKTRACE", "full");
+
+        let mut rng = match self.seed {
+            Some(seed) => ChaChaRng::seed_from_u64(seed),
+            None => ChaChaRng::from_entropy(),
+        };
+        let crs = match &self.crs {
+            Some(crs) => crs.as_bytes().to_vec(),
+            None => {
+                let mut crs = vec![0u8; 32];
+                rng.fill_bytes(&mut crs);
+                crs
+            }
+        };
+
+        let committee = ElectionCommittee::generate(
+            &self.alias_prefix,
+            self.members,
+            self.threshold,
+            &crs,
+            &mut rng,
+        )?;
+
+        if self.output_directory.exists() {
+            std::fs::remove_dir_all(&self.output_directory)?;
+        }
+        std::fs::create_dir_all(&self.output_directory)?;
+
+        let def = committee.def();
+        let committee_file = self.output_directory.join("committee.json");
+        std::fs::write(&committee_file, serde_json::to_string_pretty(&def)?)?;
+
+        for secrets in committee.secrets() {
+            let member_file = self
+                .output_directory
+                .join(format!("{}_secrets.json", secrets.alias));
+            std::fs::write(&member_file, serde_json::to_string_pretty(&secrets)?)?;
+            println!("{} keys dumped to {:?}", secrets.alias, member_file);
+        }
+
+        println!("Election committee dumped to {:?}", committee_file);
+        println!("election key: {}", def.election_key);
+        println!(
+            "blockchain.committees: {}",
+            serde_json::to_string(&def.committee_ids)?
+        );
+        println!(
+            "to use it for private vote plans set vote_plan.election_committee to {:?}",
+            committee_file
+        );
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use crate::builders::utils::{DeploymentTree, SessionSettingsExtension};
 use crate::builders::VitBackendSettingsBuilder;
-use crate::config::read_config;
+use crate::config::{read_config_with_overrides, ConfigOverride};
 use crate::mode::standard::generate_database;
 use crate::Result;
 use hersir::config::SessionSettings;
@@ -19,6 +19,10 @@ pub struct ExternalDataCommandArgs {
     #[structopt(long = "config")]
     pub config: PathBuf,
 
+    /// override config value, e.g. --set data.current_fund.proposals=500
+    #[structopt(long = "set")]
+    pub overrides: Vec<ConfigOverride>,
+
     /// proposals import json
     #[structopt(
         long = "proposals",
@@ -55,7 +59,7 @@ impl ExternalDataCommandArgs {
         let session_settings = SessionSettings::from_dir(&self.output_directory);
 
         let mut quick_setup = VitBackendSettingsBuilder::default();
-        let mut config = read_config(&self.config)?;
+        let mut config = read_config_with_overrides(&self.config, &self.overrides)?;
 
         if let Some(snapshot) = self.snapshot {
             config.extend_from_initials_file(snapshot, chain_addr::Discrimination::Production)?;
@@ -84,7 +88,13 @@ impl ExternalDataCommandArgs {
         )
         .unwrap();
 
-        generate_database(&deployment_tree, vit_parameters, template_generator)?;
+        generate_database(
+            &deployment_tree,
+            vit_parameters,
+            template_generator,
+            controller.vote_plan_layout(),
+            &config.service.version,
+        )?;
 
         println!(
             "voteplan ids: {:?}",
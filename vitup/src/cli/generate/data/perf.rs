@@ -1,7 +1,7 @@
 use crate::builders::utils::DeploymentTree;
 use crate::builders::utils::SessionSettingsExtension;
 use crate::builders::VitBackendSettingsBuilder;
-use crate::config::read_config;
+use crate::config::{read_config_with_overrides, ConfigOverride};
 use crate::mode::standard::generate_database;
 use crate::Result;
 use glob::glob;
@@ -21,6 +21,10 @@ pub struct PerfDataCommandArgs {
     #[structopt(long = "config")]
     pub config: PathBuf,
 
+    /// override config value, e.g. --set data.current_fund.proposals=500
+    #[structopt(long = "set")]
+    pub overrides: Vec<ConfigOverride>,
+
     /// proposals import json
     #[structopt(
         long = "proposals",
@@ -65,7 +69,7 @@ impl PerfDataCommandArgs {
 
         let session_settings = SessionSettings::from_dir(&self.output_directory);
 
-        let mut config = read_config(&self.config)?;
+        let mut config = read_config_with_overrides(&self.config, &self.overrides)?;
 
         if let Some(ref snapshot) = self.snapshot {
             config.extend_from_initials_file(snapshot, chain_addr::Discrimination::Production)?;
@@ -91,7 +95,13 @@ impl PerfDataCommandArgs {
         )
         .unwrap();
 
-        generate_database(&deployment_tree, vit_parameters, template_generator)?;
+        generate_database(
+            &deployment_tree,
+            vit_parameters,
+            template_generator,
+            controller.vote_plan_layout(),
+            &config.service.version,
+        )?;
 
         self.move_single_user_secrets(
             &deployment_tree,
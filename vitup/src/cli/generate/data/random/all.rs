@@ -1,7 +1,7 @@
 use crate::builders::utils::DeploymentTree;
 use crate::builders::utils::SessionSettingsExtension;
 use crate::builders::VitBackendSettingsBuilder;
-use crate::config::read_config;
+use crate::config::{read_config_with_overrides, ConfigOverride};
 use crate::mode::standard::generate_random_database;
 use crate::Result;
 use hersir::config::SessionSettings;
@@ -18,6 +18,10 @@ pub struct AllRandomDataCommandArgs {
     #[structopt(long = "config")]
     pub config: PathBuf,
 
+    /// override config value, e.g. --set data.current_fund.proposals=500
+    #[structopt(long = "set")]
+    pub overrides: Vec<ConfigOverride>,
+
     #[structopt(long = "snapshot")]
     pub snapshot: Option<PathBuf>,
 }
@@ -28,7 +32,7 @@ impl AllRandomDataCommandArgs {
 
         let session_settings = SessionSettings::from_dir(&self.output_directory);
 
-        let mut config = read_config(&self.config)?;
+        let mut config = read_config_with_overrides(&self.config, &self.overrides)?;
 
         if let Some(snapshot) = self.snapshot {
             config.extend_from_initials_file(snapshot, chain_addr::Discrimination::Production)?;
@@ -45,7 +49,12 @@ impl AllRandomDataCommandArgs {
             .session_settings(session_settings)
             .build()?;
 
-        generate_random_database(&deployment_tree, vit_parameters)?;
+        generate_random_database(
+            &deployment_tree,
+            vit_parameters,
+            controller.vote_plan_layout(),
+            &config,
+        )?;
 
         println!(
             "voteplan ids: {:?}",
@@ -1,4 +1,5 @@
 use crate::builders::ReviewGenerator;
+use crate::config::{read_config_with_overrides, Config, ConfigOverride, RandomStream, Range};
 use crate::Result;
 use std::io::Write;
 use std::path::PathBuf;
@@ -18,8 +19,22 @@ pub struct RandomReviewsDataCommandArgs {
     )]
     pub proposals: PathBuf,
 
-    #[structopt(long = "assessors-per-proposal-count", default_value = "3")]
-    pub assessors_per_proposal_count: u32,
+    /// config with `reviews` profile section. Default profile is used if not defined
+    #[structopt(long = "config")]
+    pub config: Option<PathBuf>,
+
+    /// override config value, e.g. --set reviews.ranking.filtered_out=1
+    #[structopt(long = "set")]
+    pub overrides: Vec<ConfigOverride>,
+
+    /// fixed number of reviews per proposal, overrides `reviews.count` from profile
+    #[structopt(long = "assessors-per-proposal-count")]
+    pub assessors_per_proposal_count: Option<usize>,
+
+    /// seed for random generator, so the same reviews are produced on each run.
+    /// Overrides `seed` from config
+    #[structopt(long = "seed")]
+    pub seed: Option<u64>,
 }
 
 impl RandomReviewsDataCommandArgs {
@@ -27,7 +42,19 @@ impl RandomReviewsDataCommandArgs {
         let proposals: Vec<ProposalTemplate> =
             serde_json::from_str(&std::fs::read_to_string(&self.proposals)?)?;
 
-        let mut generator = ReviewGenerator::new(self.assessors_per_proposal_count);
+        let mut config = match &self.config {
+            Some(config) => read_config_with_overrides(config, &self.overrides)?,
+            None => Config::default(),
+        };
+        if let Some(count) = self.assessors_per_proposal_count {
+            config.reviews.count = Range::fixed(count);
+        }
+        if self.seed.is_some() {
+            config.seed = self.seed;
+        }
+
+        let mut generator = ReviewGenerator::new(config.reviews.clone())
+            .with_rng(config.rng(RandomStream::Reviews));
         let reviews = generator.generate(proposals)?;
         let content = serde_json::to_string_pretty(&reviews)?;
         let mut file = std::fs::File::create(&self.output_file)?;
@@ -1,6 +1,6 @@
+use crate::config::rng_from_seed;
 use crate::Result;
 use rand::RngCore;
-use rand_core::OsRng;
 use std::io::Write;
 use std::path::PathBuf;
 use structopt::StructOpt;
@@ -23,6 +23,10 @@ pub struct RandomScoresDataCommandArgs {
 
     #[structopt(long = "max", default_value = "499")]
     pub maximal_score: u32,
+
+    /// seed for score generator. Random if not provided
+    #[structopt(long = "seed")]
+    pub seed: Option<u64>,
 }
 
 impl RandomScoresDataCommandArgs {
@@ -30,7 +34,7 @@ impl RandomScoresDataCommandArgs {
         let mut value: Vec<serde_json::Value> =
             serde_json::from_str(&std::fs::read_to_string(&self.proposals)?)?;
 
-        let mut generator = OsRng;
+        let mut generator = rng_from_seed(self.seed);
         for proposal in value.iter_mut() {
             *proposal
                 .as_object_mut()
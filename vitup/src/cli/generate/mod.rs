@@ -3,7 +3,7 @@ mod data;
 mod qr;
 mod snapshot;
 
-pub use committee::CommitteeIdCommandArgs;
+pub use committee::{CommitteeIdCommandArgs, ElectionCommitteeCommandArgs};
 pub use data::{DataCommandArgs, ExternalDataCommandArgs, RandomDataCommandArgs};
 pub use qr::QrCommandArgs;
 pub use snapshot::SnapshotCommandArgs;
@@ -1,6 +1,6 @@
 use crate::builders::utils::DeploymentTree;
 use crate::builders::utils::SessionSettingsExtension;
-use crate::builders::VitBackendSettingsBuilder;
+use crate::builders::{PinHint, QrExport, SecretFormat, SheetFormat, VitBackendSettingsBuilder};
 use crate::config::ConfigBuilder;
 use crate::config::Initials;
 use crate::Result;
@@ -25,6 +25,18 @@ pub struct QrCommandArgs {
 
     #[structopt(long = "global-pin", default_value = "1234")]
     pub global_pin: String,
+
+    /// printable sheets with qr codes, alias, pin hint and address: svg, pdf
+    #[structopt(long = "sheet")]
+    pub sheets: Vec<SheetFormat>,
+
+    /// how pin is printed on sheets: full, masked, hidden
+    #[structopt(long = "pin-hint", default_value = "masked")]
+    pub pin_hint: PinHint,
+
+    /// additionally export secret keys as: bech32, hex, csv (index of all wallets)
+    #[structopt(long = "secret")]
+    pub secrets: Vec<SecretFormat>,
 }
 
 impl QrCommandArgs {
@@ -57,6 +69,11 @@ impl QrCommandArgs {
         println!("{:?}", config.initials);
         let _ = VitBackendSettingsBuilder::default()
             .session_settings(session_settings)
+            .qr_export(QrExport {
+                sheets: self.sheets,
+                secrets: self.secrets,
+                pin_hint: self.pin_hint,
+            })
             .config(&config)
             .build()?;
 
@@ -1,8 +1,14 @@
+use super::mapping::{Error as MappingError, Mapping};
+use crate::cli::validate::ideascale::rules::{
+    IdeascaleData, Rule, RuleSettings, RulesConfig, Severity, Validator,
+};
 use serde::{Deserialize, Serialize};
 use std::io::Write;
 use std::{fs::File, path::PathBuf};
 use structopt::StructOpt;
 use thiserror::Error;
+use vit_servicing_station_tests::common::data::{parse_challenges, parse_proposals, parse_reviews};
+
 #[derive(StructOpt, Debug)]
 #[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
 pub enum ImportFromIdeascaleFormatCommand {
@@ -10,6 +16,8 @@ pub enum ImportFromIdeascaleFormatCommand {
     Proposals(ImportProposals),
     Challenges(ImportChallenges),
     Reviews(ImportReviews),
+    /// import raw IdeaScale csv exports using mapping file
+    Csv(ImportCsv),
 }
 
 impl ImportFromIdeascaleFormatCommand {
@@ -21,6 +29,7 @@ impl ImportFromIdeascaleFormatCommand {
             Self::Proposals(proposals) => proposals.exec()?,
             Self::Challenges(challenges) => challenges.exec()?,
             Self::Reviews(reviews) => reviews.exec()?,
+            Self::Csv(csv) => csv.exec()?,
         }
 
         Ok(())
@@ -34,7 +43,7 @@ pub struct ImportProposals {
 
     #[structopt(
         long = "output",
-        default_value = "../resources/external/challenges.json"
+        default_value = "../resources/external/proposals.json"
     )]
     pub output: PathBuf,
 }
@@ -58,7 +67,7 @@ pub struct ImportChallenges {
 
     #[structopt(
         long = "output",
-        default_value = "../resources/external/proposals.json"
+        default_value = "../resources/external/challenges.json"
     )]
     pub output: PathBuf,
 }
@@ -166,6 +175,14 @@ pub enum Error {
     CannotParseRatingToFloat(#[from] std::num::ParseFloatError),
     #[error(transparent)]
     Csv(#[from] csv::Error),
+    #[error(transparent)]
+    Mapping(#[from] MappingError),
+    #[error(transparent)]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("imported data does not match template: {0}")]
+    Template(#[from] vit_servicing_station_tests::common::data::TemplateLoad),
+    #[error("{0} validation errors found in imported data")]
+    ValidationFailed(usize),
 }
 
 #[derive(Serialize, Deserialize)]
@@ -215,3 +232,122 @@ impl ImportReviews {
         Ok(())
     }
 }
+
+#[derive(StructOpt, Debug)]
+pub struct ImportCsv {
+    /// raw proposals csv export
+    #[structopt(long = "proposals")]
+    pub proposals: Option<PathBuf>,
+
+    /// raw challenges csv export
+    #[structopt(long = "challenges")]
+    pub challenges: Option<PathBuf>,
+
+    /// raw reviews csv export
+    #[structopt(long = "reviews")]
+    pub reviews: Option<PathBuf>,
+
+    /// yaml or json mapping of csv columns to template fields. Columns are taken as is if not provided
+    #[structopt(long = "mapping")]
+    pub mapping: Option<PathBuf>,
+
+    /// funds json. If provided, imported data is also checked with ideascale validation rules
+    #[structopt(long = "funds")]
+    pub funds: Option<PathBuf>,
+
+    /// csv delimiter
+    #[structopt(long = "delimiter", default_value = ",")]
+    pub delimiter: char,
+
+    /// output folder
+    #[structopt(long = "output", default_value = "../resources/external")]
+    pub output: PathBuf,
+}
+
+impl ImportCsv {
+    pub fn exec(self) -> Result<(), Error> {
+        let mapping: Mapping = match &self.mapping {
+            Some(mapping) => serde_yaml::from_str(&jortestkit::file::read_file(mapping)?)?,
+            None => Default::default(),
+        };
+        std::fs::create_dir_all(&self.output)?;
+        let mut data = IdeascaleData::default();
+
+        if let Some(input) = &self.proposals {
+            data.proposals = mapping
+                .proposals
+                .map_csv(File::open(input)?, self.delimiter as u8)?;
+            let output = self.write("proposals.json", &data.proposals)?;
+            parse_proposals(output)?;
+        }
+        if let Some(input) = &self.challenges {
+            data.challenges = mapping
+                .challenges
+                .map_csv(File::open(input)?, self.delimiter as u8)?;
+            let output = self.write("challenges.json", &data.challenges)?;
+            parse_challenges(output)?;
+        }
+        if let Some(input) = &self.reviews {
+            data.reviews = mapping
+                .reviews
+                .map_csv(File::open(input)?, self.delimiter as u8)?;
+            let output = self.write("reviews.json", &data.reviews)?;
+            parse_reviews(output)?;
+        }
+
+        if let Some(funds) = &self.funds {
+            data.funds = match serde_json::from_str(&jortestkit::file::read_file(funds)?)? {
+                serde_json::Value::Array(funds) => funds,
+                fund => vec![fund],
+            };
+            self.check(&mut data)?;
+        }
+        Ok(())
+    }
+
+    fn write(&self, name: &str, entries: &[serde_json::Value]) -> Result<PathBuf, Error> {
+        let output = self.output.join(name);
+        std::fs::write(&output, serde_json::to_string_pretty(entries)?)?;
+        println!("{} entries imported to {:?}", entries.len(), output);
+        Ok(output)
+    }
+
+    fn check(&self, data: &mut IdeascaleData) -> Result<(), Error> {
+        // cross references can be checked only if referenced entities were imported
+        let mut rules = RulesConfig::default();
+        for (rule, imported) in [
+            (Rule::UnknownChallenge, !data.challenges.is_empty()),
+            (Rule::UnknownReviewProposal, !data.proposals.is_empty()),
+        ] {
+            if !imported {
+                rules.rules.insert(
+                    rule,
+                    RuleSettings {
+                        enabled: Some(false),
+                        severity: None,
+                    },
+                );
+            }
+        }
+        let violations = Validator::new(rules).validate(data);
+        for violation in &violations {
+            println!(
+                "[{:?}] {} {:?} {} ({}): {}",
+                violation.severity,
+                violation.rule,
+                violation.entity,
+                violation.entity_id,
+                violation.field,
+                violation.message
+            );
+        }
+        let errors = violations
+            .iter()
+            .filter(|violation| violation.severity == Severity::Error)
+            .count();
+        if errors > 0 {
+            return Err(Error::ValidationFailed(errors));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::io::Read;
+use thiserror::Error;
+
+/// Describes how columns of raw IdeaScale csv export map to template fields, e.g.:
+///
+/// ```yaml
+/// proposals:
+///   fields:
+///     proposal_id: "Idea ID"
+///     proposal_title: "Idea Title"
+///   defaults:
+///     chain_vote_type: public
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Mapping {
+    pub proposals: EntityMapping,
+    pub challenges: EntityMapping,
+    pub reviews: EntityMapping,
+}
+
+impl Default for Mapping {
+    fn default() -> Self {
+        Self {
+            proposals: EntityMapping::default(),
+            challenges: EntityMapping {
+                integers: vec!["internal_id".to_string()],
+                ..Default::default()
+            },
+            reviews: EntityMapping {
+                integers: vec![
+                    "impact_alignment_rating_given".to_string(),
+                    "feasibility_rating_given".to_string(),
+                    "auditability_rating_given".to_string(),
+                ],
+                ..Default::default()
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EntityMapping {
+    /// template field -> csv column
+    pub fields: BTreeMap<String, String>,
+    /// values for template fields which are not present in csv
+    pub defaults: BTreeMap<String, String>,
+    /// template fields which should be written as numbers
+    pub integers: Vec<String>,
+    /// copy columns which are not mentioned in `fields` under their original name
+    pub include_unmapped: bool,
+}
+
+impl Default for EntityMapping {
+    fn default() -> Self {
+        Self {
+            fields: BTreeMap::new(),
+            defaults: BTreeMap::new(),
+            integers: Vec::new(),
+            include_unmapped: true,
+        }
+    }
+}
+
+impl EntityMapping {
+    /// Converts csv rows into json objects ready to be parsed as templates
+    pub fn map_csv<R: Read>(&self, reader: R, delimiter: u8) -> Result<Vec<Value>, Error> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .delimiter(delimiter)
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+
+        // spreadsheet exports often start with byte order mark
+        let headers: Vec<String> = reader
+            .headers()?
+            .iter()
+            .map(|header| header.trim_start_matches('\u{feff}').to_string())
+            .collect();
+
+        for column in self.fields.values() {
+            if !headers.contains(column) {
+                return Err(Error::MissingColumn(column.clone()));
+            }
+        }
+
+        let mut entries = Vec::new();
+        for (row, record) in reader.records().enumerate() {
+            let record = record?;
+            let column = |name: &str| {
+                headers
+                    .iter()
+                    .position(|header| header == name)
+                    .and_then(|idx| record.get(idx))
+                    .unwrap_or_default()
+                    .to_string()
+            };
+
+            let mut entry = Map::new();
+            if self.include_unmapped {
+                for header in headers
+                    .iter()
+                    .filter(|h| !self.fields.values().any(|c| c == *h))
+                {
+                    entry.insert(header.clone(), Value::String(column(header)));
+                }
+            }
+            for (field, source) in &self.fields {
+                entry.insert(field.clone(), Value::String(column(source)));
+            }
+            for (field, value) in &self.defaults {
+                entry
+                    .entry(field.clone())
+                    .or_insert_with(|| Value::String(value.clone()));
+            }
+            for field in &self.integers {
+                if let Some(value) = entry.get_mut(field) {
+                    let text = value.as_str().unwrap_or_default().replace(',', "");
+                    *value = text
+                        .parse::<i64>()
+                        .map_err(|_| Error::NotAnInteger {
+                            row: row + 1,
+                            field: field.clone(),
+                            value: text.clone(),
+                        })?
+                        .into();
+                }
+            }
+            entries.push(Value::Object(entry));
+        }
+        Ok(entries)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("column '{0}' not found in csv headers")]
+    MissingColumn(String),
+    #[error("row {row}: field '{field}' should be an integer, got: '{value}'")]
+    NotAnInteger {
+        row: usize,
+        field: String,
+        value: String,
+    },
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn raw_columns_are_mapped_to_template_fields() {
+        let csv = "\u{feff}Idea ID,Idea Title,Rating,Campaign\n1, First ,\"1,000\",c1\n";
+        let mapping: EntityMapping = serde_yaml::from_str(
+            r#"
+fields:
+  proposal_id: Idea ID
+  proposal_title: Idea Title
+  rating: Rating
+defaults:
+  chain_vote_type: public
+integers: [rating]
+"#,
+        )
+        .unwrap();
+
+        let entries = mapping.map_csv(csv.as_bytes(), b',').unwrap();
+        assert_eq!(
+            entries,
+            vec![json!({
+                "proposal_id": "1",
+                "proposal_title": "First",
+                "rating": 1000,
+                "Campaign": "c1",
+                "chain_vote_type": "public",
+            })]
+        );
+
+        let missing: EntityMapping = serde_yaml::from_str("fields: { proposal_id: Id }").unwrap();
+        assert!(matches!(
+            missing.map_csv(csv.as_bytes(), b','),
+            Err(Error::MissingColumn(_))
+        ));
+    }
+}
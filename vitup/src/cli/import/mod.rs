@@ -1,6 +1,8 @@
 mod ideascale;
+mod mapping;
 
 pub use ideascale::{Error as ImportError, ImportFromIdeascaleFormatCommand};
+pub use mapping::{EntityMapping, Error as MappingError, Mapping};
 
 use structopt::StructOpt;
 
@@ -0,0 +1,82 @@
+use crate::config::{read_config_with_overrides, ConfigOverride, DbMigrator, ServiceVersion};
+use crate::Result;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Upgrades vit-servicing-station database from older deployment to schema
+/// of given vit-servicing-station release
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct MigrateCommand {
+    /// database file, e.g. vit_station/storage.db
+    #[structopt(long = "db")]
+    pub db: PathBuf,
+
+    /// target vit-servicing-station version. By default `service.version` from
+    /// configuration or latest supported one
+    #[structopt(long = "version")]
+    pub version: Option<String>,
+
+    /// configuration
+    #[structopt(long = "config", conflicts_with = "version")]
+    pub config: Option<PathBuf>,
+
+    /// override config value, e.g. --set service.version=3.4
+    #[structopt(long = "set")]
+    pub overrides: Vec<ConfigOverride>,
+
+    /// only print detected and target schema without changing database
+    #[structopt(long = "dry-run")]
+    pub dry_run: bool,
+
+    /// do not copy database to `{db}.bak` before migration
+    #[structopt(long = "no-backup")]
+    pub no_backup: bool,
+}
+
+impl MigrateCommand {
+    pub fn exec(self) -> Result<()> {
+        if !self.db.exists() {
+            return Err(crate::error::Error::CannotFindDatabase(self.db));
+        }
+
+        let version: ServiceVersion = match (&self.version, &self.config) {
+            (Some(version), _) => version.parse()?,
+            (None, Some(config)) => read_config_with_overrides(config, &self.overrides)?
+                .service
+                .version
+                .parse()?,
+            (None, None) => ServiceVersion::latest(),
+        };
+
+        let migrator = DbMigrator::open(&self.db)?;
+        match migrator.applied().last() {
+            Some(migration) => println!(
+                "current schema: {} (vit-servicing-station {})",
+                migration.name, migration.since
+            ),
+            None => println!("current schema: empty"),
+        }
+        println!("target vit-servicing-station: {}", version);
+
+        if self.dry_run {
+            return Ok(());
+        }
+
+        if !self.no_backup {
+            let mut backup = self.db.clone().into_os_string();
+            backup.push(".bak");
+            std::fs::copy(&self.db, &backup)?;
+            println!("database backed up to {:?}", backup);
+        }
+
+        let steps = migrator.migrate_to(&version)?;
+        if steps.is_empty() {
+            println!("database is up to date");
+        }
+        for step in steps {
+            println!("{}", step);
+        }
+        Ok(())
+    }
+}
@@ -1,18 +1,27 @@
+pub mod config;
 pub mod diff;
+pub mod export;
 pub mod generate;
 pub mod import;
+pub mod migrate;
 pub mod start;
 pub mod time;
 pub mod validate;
 
 use self::time::TimeCommand;
-use crate::cli::generate::{CommitteeIdCommandArgs, QrCommandArgs, SnapshotCommandArgs};
-use crate::cli::start::AdvancedStartCommandArgs;
+use crate::cli::generate::{
+    CommitteeIdCommandArgs, ElectionCommitteeCommandArgs, QrCommandArgs, SnapshotCommandArgs,
+};
+use crate::cli::start::{AdvancedStartCommandArgs, BundleStartCommandArgs};
 use crate::cli::start::{MockFarmCommand, MockStartCommandArgs};
 use crate::Result;
+use config::ConfigCommand;
 use diff::DiffCommand;
+pub use diff::Error as DiffError;
+use export::ExportCommand;
 use generate::DataCommandArgs;
 use import::ImportCommand;
+use migrate::MigrateCommand;
 use start::QuickStartCommandArgs;
 use structopt::StructOpt;
 pub use validate::Error as ValidateError;
@@ -24,7 +33,8 @@ pub enum VitCliCommand {
     Start(StartCommand),
     /// generate fund data
     Generate(GenerateCommand),
-    /// print differences between new deployment and target env
+    /// print structured differences between new deployment and target env as json.
+    /// Exits with code 2 when drift is found
     Diff(DiffCommand),
     /// validate static data
     Validate(ValidateCommand),
@@ -32,6 +42,12 @@ pub enum VitCliCommand {
     Import(ImportCommand),
     // convert time
     Time(TimeCommand),
+    /// inspect configuration
+    Config(ConfigCommand),
+    /// export deployment
+    Export(ExportCommand),
+    /// upgrade vit-servicing-station database to schema of given version
+    Migrate(MigrateCommand),
 }
 
 impl VitCliCommand {
@@ -39,10 +55,16 @@ impl VitCliCommand {
         match self {
             Self::Start(start_command) => start_command.exec(),
             Self::Generate(generate_command) => generate_command.exec(),
-            Self::Diff(diff_command) => diff_command.exec(),
+            Self::Diff(diff_command) => match diff_command.exec()? {
+                0 => Ok(()),
+                code => std::process::exit(code),
+            },
             Self::Validate(validate_command) => validate_command.exec().map_err(Into::into),
             Self::Import(import_command) => import_command.exec().map_err(Into::into),
             Self::Time(time_command) => time_command.exec(),
+            Self::Config(config_command) => config_command.exec(),
+            Self::Export(export_command) => export_command.exec(),
+            Self::Migrate(migrate_command) => migrate_command.exec(),
         }
     }
 }
@@ -57,6 +79,8 @@ pub enum StartCommand {
     Mock(MockStartCommandArgs),
     /// start multiple mock environments
     MockFarm(MockFarmCommand),
+    /// start backend from deployment bundle
+    Bundle(BundleStartCommandArgs),
 }
 
 impl StartCommand {
@@ -68,6 +92,7 @@ impl StartCommand {
             Self::MockFarm(mock_farm_start_command) => {
                 mock_farm_start_command.exec().map_err(Into::into)
             }
+            Self::Bundle(bundle_start_command) => bundle_start_command.exec(),
         }
     }
 }
@@ -82,6 +107,8 @@ pub enum GenerateCommand {
     Snapshot(SnapshotCommandArgs),
     /// Committee Id
     Committee(CommitteeIdCommandArgs),
+    /// multi-member committee with election key for private voting
+    ElectionCommittee(ElectionCommitteeCommandArgs),
 }
 
 impl GenerateCommand {
@@ -91,6 +118,9 @@ impl GenerateCommand {
             Self::Data(data_start_command) => data_start_command.exec(),
             Self::Snapshot(snapshot_start_command) => snapshot_start_command.exec(),
             Self::Committee(generate_committee_command) => generate_committee_command.exec(),
+            Self::ElectionCommittee(election_committee_command) => {
+                election_committee_command.exec()
+            }
         }
     }
 }
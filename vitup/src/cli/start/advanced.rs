@@ -1,6 +1,6 @@
 use crate::config::mode::{parse_mode_from_str, Mode};
-use crate::config::read_config;
 use crate::config::read_voter_hirs;
+use crate::config::{read_config_with_overrides, ConfigOverride};
 use crate::mode::spawn::{spawn_network, NetworkSpawnParams};
 use crate::{error::Error, Result};
 use chain_addr::Discrimination;
@@ -52,6 +52,10 @@ pub struct AdvancedStartCommandArgs {
     #[structopt(long = "config")]
     pub config: PathBuf,
 
+    /// override config value, e.g. --set data.current_fund.proposals=500
+    #[structopt(long = "set")]
+    pub overrides: Vec<ConfigOverride>,
+
     /// proposals import json
     #[structopt(
         long = "proposals",
@@ -110,7 +114,7 @@ impl AdvancedStartCommandArgs {
             title: title.to_owned(),
         };
 
-        let mut config = read_config(&self.config)?;
+        let mut config = read_config_with_overrides(&self.config, &self.overrides)?;
 
         if let Some(snapshot) = self.snapshot {
             config
@@ -136,6 +140,7 @@ impl AdvancedStartCommandArgs {
             session_settings,
             token,
             config.service.version.clone(),
+            config.topology.clone(),
             testing_directory,
         );
         spawn_network(mode, network_spawn_params, &mut template_generator, config)
@@ -0,0 +1,79 @@
+use crate::builders::utils::unpack_bundle;
+use crate::config::mode::Mode;
+use crate::mode::spawn::{spawn_bundle, BundledDeployment, NetworkSpawnParams};
+use crate::{error::Error, Result};
+use hersir::config::SessionSettings;
+use jormungandr_automation::jormungandr::LogLevel;
+use std::path::PathBuf;
+use std::str::FromStr;
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct BundleStartCommandArgs {
+    /// bundle created by `vitup export bundle`
+    #[structopt(long = "bundle")]
+    pub bundle: PathBuf,
+
+    /// path or name of the jormungandr node to test
+    #[structopt(long = "jormungandr", default_value = "jormungandr")]
+    pub jormungandr: PathBuf,
+
+    /// set a directory in which the tests will be run, allowing every details
+    /// to be save persistently. By default it will create temporary directories
+    /// and will delete the files and documents
+    #[structopt(long = "root-dir", default_value = "./catalyst")]
+    pub testing_directory: PathBuf,
+
+    /// level for all nodes
+    #[structopt(long = "log-level", default_value = "info")]
+    pub log_level: String,
+
+    /// endopint in format: 127.0.0.1:80
+    #[structopt(long = "endpoint", default_value = "0.0.0.0:80")]
+    pub endpoint: String,
+}
+
+impl BundleStartCommandArgs {
+    pub fn exec(self) -> Result<()> {
+        std::env::set_var("RUST_BACKTRACE", "full");
+
+        let testing_directory = self.testing_directory;
+        let log_level = self.log_level;
+        let title = "bundle";
+
+        if testing_directory.exists() {
+            std::fs::remove_dir_all(&testing_directory)?;
+        }
+
+        let source = testing_directory.join("source");
+        let manifest = unpack_bundle(&self.bundle, &source)?;
+        println!(
+            "Bundle verified: {} files, created at {}",
+            manifest.files.len(),
+            manifest.created
+        );
+        let session_settings = SessionSettings {
+            jormungandr: self.jormungandr,
+            root: testing_directory.join(title).into(),
+            generate_documentation: true,
+            mode: Mode::Standard.into(),
+            log: LogLevel::from_str(&log_level)
+                .map_err(|_| Error::UnknownLogLevel(log_level.clone()))?,
+            title: title.to_owned(),
+        };
+
+        let bundle = BundledDeployment::locate(&manifest, &source)?;
+        let config = manifest.config;
+        let network_spawn_params = NetworkSpawnParams::new(
+            self.endpoint,
+            config.protocol(&testing_directory)?,
+            session_settings,
+            None,
+            config.service.version.clone(),
+            config.topology.clone(),
+            testing_directory.join(title),
+        );
+        spawn_bundle(network_spawn_params, bundle, &config).map_err(Into::into)
+    }
+}
@@ -1,3 +1,4 @@
+use crate::config::{read_config_with_overrides, ConfigOverride};
 use crate::mode::mock::{farm, read_config, start_rest_server, Configuration, Context};
 use std::sync::Mutex;
 use std::{path::PathBuf, sync::Arc};
@@ -14,6 +15,10 @@ pub struct MockStartCommandArgs {
 
     #[structopt(long = "params")]
     pub params: Option<PathBuf>,
+
+    /// override params value, e.g. --set data.current_fund.proposals=500
+    #[structopt(long = "set")]
+    pub overrides: Vec<ConfigOverride>,
 }
 
 impl MockStartCommandArgs {
@@ -23,7 +28,9 @@ impl MockStartCommandArgs {
         let start_params = self
             .params
             .as_ref()
-            .map(|x| crate::config::read_config(x).unwrap());
+            .map(|params| read_config_with_overrides(params, &self.overrides))
+            .transpose()
+            .map_err(|error| Error::Params(Box::new(error)))?;
 
         if self.token.is_some() {
             configuration.token = self.token;
@@ -72,6 +79,8 @@ pub enum Error {
     CannotReadConfiguration(#[from] crate::mode::mock::MockConfigError),
     #[error(transparent)]
     CannotReadParameters(#[from] serde_yaml::Error),
+    #[error("cannot read start parameters: {0}")]
+    Params(Box<crate::error::Error>),
     #[error(transparent)]
     Join(#[from] tokio::task::JoinError),
     #[error(transparent)]
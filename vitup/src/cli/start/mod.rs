@@ -1,7 +1,9 @@
 mod advanced;
+mod bundle;
 mod mock;
 mod quick;
 
 pub use advanced::AdvancedStartCommandArgs;
+pub use bundle::BundleStartCommandArgs;
 pub use mock::{Error as MockError, MockFarmCommand, MockStartCommandArgs};
 pub use quick::QuickStartCommandArgs;
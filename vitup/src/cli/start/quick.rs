@@ -1,3 +1,4 @@
+use crate::builders::TemplatesGenerator;
 pub use crate::builders::{VitBackendSettingsBuilder, LEADER_1, LEADER_2, LEADER_3, WALLET_NODE};
 use crate::config::read_voter_hirs;
 use crate::config::ConfigBuilder;
@@ -15,7 +16,6 @@ use jortestkit::prelude::read_file;
 use std::path::PathBuf;
 use std::str::FromStr;
 use structopt::StructOpt;
-use vit_servicing_station_tests::common::data::ArbitraryValidVotingTemplateGenerator;
 
 #[derive(StructOpt, Debug)]
 #[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
@@ -216,7 +216,7 @@ impl QuickStartCommandArgs {
 
         print_intro(&session_settings, "VOTING BACKEND");
 
-        let mut template_generator = ArbitraryValidVotingTemplateGenerator::new();
+        let mut template_generator = TemplatesGenerator::from_config(&config).build()?;
 
         if testing_directory.exists() {
             std::fs::remove_dir_all(&testing_directory)?;
@@ -228,6 +228,7 @@ impl QuickStartCommandArgs {
             session_settings,
             token,
             config.service.version.clone(),
+            config.topology.clone(),
             testing_directory,
         );
         spawn_network(mode, network_spawn_params, &mut template_generator, config)
@@ -1,20 +1,142 @@
-use crate::config::read_config;
+use crate::config::{read_config_with_overrides, Config, ConfigOverride, PlanLimits, TimeTargets};
+use crate::error::Error;
 use crate::Result;
+use serde_json::json;
 use std::path::PathBuf;
 use structopt::StructOpt;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 
 #[derive(StructOpt, Debug)]
 #[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
 pub struct TimeCommand {
-    /// configuration
+    /// configuration. Optional for `plan`, which otherwise starts from default
+    /// configuration, so fund dates not derived from targets are relative to now
     #[structopt(long = "config")]
-    pub config: PathBuf,
+    pub config: Option<PathBuf>,
+
+    /// override config value, e.g. --set data.current_fund.proposals=500
+    #[structopt(long = "set")]
+    pub overrides: Vec<ConfigOverride>,
+
+    #[structopt(subcommand)]
+    pub plan: Option<TimeSubcommand>,
+}
+
+#[derive(StructOpt, Debug)]
+pub enum TimeSubcommand {
+    /// calculate blockchain settings and fund dates from target dates
+    Plan(PlanCommand),
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct PlanCommand {
+    /// block0 date (rfc3339). By default one epoch before vote start
+    #[structopt(long = "block0", parse(try_from_str = parse_date))]
+    pub block0: Option<OffsetDateTime>,
+
+    /// voting power snapshot date (rfc3339), e.g. 2022-06-01T10:00:00Z
+    #[structopt(long = "snapshot", parse(try_from_str = parse_date))]
+    pub snapshot: OffsetDateTime,
+
+    /// vote start date (rfc3339)
+    #[structopt(long = "vote-start", parse(try_from_str = parse_date))]
+    pub vote_start: OffsetDateTime,
+
+    /// tally start date (rfc3339)
+    #[structopt(long = "tally-start", parse(try_from_str = parse_date))]
+    pub tally_start: OffsetDateTime,
+
+    /// tally end date (rfc3339)
+    #[structopt(long = "tally-end", parse(try_from_str = parse_date))]
+    pub tally_end: OffsetDateTime,
+
+    /// next fund vote start date (rfc3339)
+    #[structopt(long = "next-fund", parse(try_from_str = parse_date))]
+    pub next_fund: OffsetDateTime,
+
+    #[structopt(long = "min-slot-duration", default_value = "1")]
+    pub min_slot_duration: u8,
+
+    #[structopt(long = "max-slot-duration", default_value = "20")]
+    pub max_slot_duration: u8,
+
+    #[structopt(long = "min-slots-per-epoch", default_value = "10")]
+    pub min_slots_per_epoch: u32,
+
+    #[structopt(long = "max-slots-per-epoch", default_value = "1000000")]
+    pub max_slots_per_epoch: u32,
+
+    /// accepted difference (in seconds) between target and planned dates
+    #[structopt(long = "tolerance", default_value = "0")]
+    pub tolerance: u64,
+
+    /// output file for config sections. If not provided sections are printed to stdout
+    #[structopt(long = "output")]
+    pub output: Option<PathBuf>,
 }
 
 impl TimeCommand {
     pub fn exec(self) -> Result<()> {
         std::env::set_var("RUST_BACKTRACE", "full");
-        read_config(&self.config)?.print_report();
+        let config = match &self.config {
+            Some(config) => read_config_with_overrides(config, &self.overrides)?,
+            None if self.plan.is_some() => Config::default(),
+            None => return Err(Error::ConfigRequired),
+        };
+
+        match self.plan {
+            None => config.print_report(),
+            Some(TimeSubcommand::Plan(plan)) => plan.exec(config)?,
+        }
         Ok(())
     }
 }
+
+impl PlanCommand {
+    pub fn exec(self, mut config: Config) -> Result<()> {
+        let targets = TimeTargets {
+            block0: self.block0,
+            snapshot: self.snapshot,
+            vote_start: self.vote_start,
+            tally_start: self.tally_start,
+            tally_end: self.tally_end,
+            next_fund: self.next_fund,
+        };
+        let limits = PlanLimits {
+            min_slot_duration: self.min_slot_duration,
+            max_slot_duration: self.max_slot_duration,
+            min_slots_per_epoch: self.min_slots_per_epoch,
+            max_slots_per_epoch: self.max_slots_per_epoch,
+            tolerance_secs: self.tolerance,
+        };
+
+        let plan = targets.plan(&limits)?;
+        plan.apply_to_blockchain(&mut config.blockchain);
+        config.vote_plan.vote_time = plan.vote_time();
+        config.data.current_fund.dates =
+            targets.fund_dates(&plan, config.data.current_fund.dates.clone());
+
+        let sections = json!({
+            "blockchain": config.blockchain,
+            "vote_plan": { "vote_time": config.vote_plan.vote_time },
+            "data": { "current_fund": { "dates": config.data.current_fund.dates } },
+        });
+        let content = serde_yaml::to_string(&sections)?;
+
+        match &self.output {
+            Some(output) => {
+                std::fs::write(output, content)?;
+                println!("Config sections saved to {:?}", output);
+                config.print_report();
+            }
+            None => println!("{}", content),
+        }
+        Ok(())
+    }
+}
+
+fn parse_date(date: &str) -> std::result::Result<OffsetDateTime, time::error::Parse> {
+    OffsetDateTime::parse(date, &Rfc3339)
+}
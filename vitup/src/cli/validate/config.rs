@@ -1,4 +1,4 @@
-use crate::config::read_config;
+use crate::config::{read_config_with_overrides, ConfigOverride};
 use std::path::PathBuf;
 use structopt::StructOpt;
 use thiserror::Error;
@@ -9,11 +9,15 @@ pub struct ConfigValidateCommand {
     /// target config
     #[structopt(name = "CONFIG")]
     pub config: PathBuf,
+
+    /// override config value, e.g. --set data.current_fund.proposals=500
+    #[structopt(long = "set")]
+    pub overrides: Vec<ConfigOverride>,
 }
 
 impl ConfigValidateCommand {
     pub fn exec(self) -> Result<(), Error> {
-        read_config(self.config)
+        read_config_with_overrides(self.config, &self.overrides)
             .map(|_| ())
             .map_err(|e| Error::ValidationError(e.to_string()))
     }
@@ -0,0 +1,580 @@
+mod report;
+mod tls;
+
+use crate::builders::pin_to_bytes;
+use catalyst_toolbox::kedqr::KeyQrCode;
+use chain_crypto::bech32::Bech32;
+use indicatif::{HumanDuration, MultiProgress, ProgressBar, ProgressStyle};
+use jormungandr_lib::interfaces::{BlockDate, SettingsDto};
+use rand::seq::SliceRandom;
+use report::{CheckResult, CheckStatus, Report, ReportFormat};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use structopt::StructOpt;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+use thiserror::Error;
+use valgrind::{Error as ValgrindError, ValgrindClient};
+use vit_servicing_station_lib::utils::datetime::unix_timestamp_to_datetime;
+
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct DeploymentValidateCommand {
+    /// target address
+    #[structopt(long = "address")]
+    pub address: String,
+
+    /// yaml or json file with check thresholds. Defaults are used if not provided
+    #[structopt(long = "thresholds")]
+    pub thresholds: Option<PathBuf>,
+
+    /// snapshot tags which should be available. If empty, all tags exposed by vit-ss are checked
+    #[structopt(long = "tag")]
+    pub tags: Vec<String>,
+
+    /// folder with qr codes. Sample of wallets is looked up on the node
+    #[structopt(long = "qr-codes")]
+    pub qr_codes: Option<PathBuf>,
+
+    /// global pin for qr codes. If not provided pin is read from file name (e.g. wallet_alice_1234.png)
+    #[structopt(long = "pin")]
+    pub pin: Option<String>,
+
+    /// additional CA certificate (pem) trusted when checking target certificate,
+    /// e.g. CA generated by vitup for deployment
+    #[structopt(long = "ca-cert")]
+    pub ca_cert: Option<PathBuf>,
+
+    /// report output file
+    #[structopt(long = "report")]
+    pub report: Option<PathBuf>,
+
+    /// report format: json or junit
+    #[structopt(long = "format", default_value = "json")]
+    pub format: ReportFormat,
+}
+
+/// Limits used by checks. Can be provided as yaml or json file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Thresholds {
+    /// maximum accepted duration of single check
+    pub max_response_time_ms: u64,
+    /// minimum number of days before target certificate expires
+    pub min_certificate_validity_days: u64,
+    /// maximum difference between vote plan dates calculated from node settings
+    /// and fund dates exposed by vit-ss
+    pub max_clock_skew_secs: u64,
+    /// how many qr codes are decoded and looked up on the node
+    pub qr_sample_size: usize,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            max_response_time_ms: 5_000,
+            min_certificate_validity_days: 14,
+            max_clock_skew_secs: 60,
+            qr_sample_size: 10,
+        }
+    }
+}
+
+#[derive(Debug, EnumIter, Copy, Clone)]
+enum Check {
+    Fund,
+    Proposals,
+    Settings,
+    VotePlan,
+    Reviews,
+    Challenges,
+    BadGateway,
+    Times,
+    SnapshotTags,
+    QrAccounts,
+    FragmentsV1,
+    Certificate,
+    ClockSkew,
+    VotePlanProposals,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Check::Fund => write!(f, "[vit-ss] Fund endpoint check"),
+            Check::Proposals => write!(f, "[vit-ss] Proposals endpoint check"),
+            Check::Settings => write!(f, "[jormungandr] Settings endpoint check"),
+            Check::VotePlan => write!(f, "[jormungandr] Vote plan endpoint"),
+            Check::Reviews => write!(f, "[vit-ss] Reviews endpoint"),
+            Check::Challenges => write!(f, "[vit-ss] Challenges endpoint"),
+            Check::BadGateway => write!(f, "[proxy] Bad gateway check"),
+            Check::Times => write!(f, "[vit-ss] Fund dates check"),
+            Check::SnapshotTags => write!(f, "[vit-ss] Voting power snapshot endpoints"),
+            Check::QrAccounts => write!(f, "[jormungandr] Qr wallets account lookup"),
+            Check::FragmentsV1 => write!(f, "[jormungandr] V1 fragments endpoints"),
+            Check::Certificate => write!(f, "[proxy] TLS certificate check"),
+            Check::ClockSkew => write!(f, "[jormungandr/vit-ss] Vote dates clock skew"),
+            Check::VotePlanProposals => write!(f, "[jormungandr/vit-ss] Vote plans and proposals"),
+        }
+    }
+}
+
+/// Everything needed by checks, shared between check threads
+struct Context {
+    address: String,
+    thresholds: Thresholds,
+    tags: Vec<String>,
+    qr_codes: Option<PathBuf>,
+    pin: Option<String>,
+    ca_cert: Option<PathBuf>,
+}
+
+impl Context {
+    fn client(&self) -> Result<ValgrindClient, CheckError> {
+        ValgrindClient::new(self.address.clone(), Default::default()).map_err(Into::into)
+    }
+
+    /// Address without trailing `/api` segment
+    fn base_address(&self) -> &str {
+        self.address.trim_end_matches('/').trim_end_matches("/api")
+    }
+
+    fn get(&self, path: &str) -> Result<reqwest::blocking::Response, CheckError> {
+        reqwest::blocking::get(&format!("{}/api/{}", self.base_address(), path))?
+            .error_for_status()
+            .map_err(Into::into)
+    }
+}
+
+enum Outcome {
+    Passed(Duration),
+    Skipped(String),
+}
+
+impl Check {
+    fn execute(&self, context: &Context) -> std::result::Result<Outcome, CheckError> {
+        let wallet_backend = context.client()?;
+        let mut started = Instant::now();
+
+        match self {
+            Self::Fund => wallet_backend
+                .funds()
+                .map(|_| Outcome::Passed(started.elapsed()))
+                .map_err(Into::into),
+            Self::Proposals => wallet_backend
+                .proposals("direct")
+                .map(|_| Outcome::Passed(started.elapsed()))
+                .map_err(Into::into),
+            Self::Settings => wallet_backend
+                .settings()
+                .map(|_| Outcome::Passed(started.elapsed()))
+                .map_err(Into::into),
+            Self::VotePlan => wallet_backend
+                .vote_plan_statuses()
+                .map(|_| Outcome::Passed(started.elapsed()))
+                .map_err(Into::into),
+            Self::Reviews => {
+                let proposals = wallet_backend.proposals("direct")?;
+                started = Instant::now();
+                wallet_backend
+                    .review(&proposals[0].proposal.proposal_id)
+                    .map(|_| Outcome::Passed(started.elapsed()))
+                    .map_err(Into::into)
+            }
+            Self::Challenges => wallet_backend
+                .challenges()
+                .map(|_| Outcome::Passed(started.elapsed()))
+                .map_err(Into::into),
+            Self::BadGateway => {
+                let left = wallet_backend.funds()?;
+                let right = wallet_backend.funds()?;
+
+                if left != right {
+                    Err(CheckError::Assert(
+                        "two calls to funds return different response".to_string(),
+                    ))
+                } else {
+                    Ok(Outcome::Passed(started.elapsed()))
+                }
+            }
+            Self::Times => {
+                let fund = wallet_backend.funds()?;
+
+                let registration_date = unix_timestamp_to_datetime(fund.registration_snapshot_time);
+                let fund_start_date = unix_timestamp_to_datetime(fund.fund_start_time);
+                let fund_end_date = unix_timestamp_to_datetime(fund.fund_end_time);
+                let next_fund_date = unix_timestamp_to_datetime(fund.next_fund_start_time);
+
+                if registration_date > fund_start_date {
+                    return Err(CheckError::Assert(
+                        "registration_date is further in the future than fund_start_date"
+                            .to_string(),
+                    ));
+                }
+                if fund_start_date > fund_end_date {
+                    return Err(CheckError::Assert(
+                        "fund_start_date is further in the future than fund_end_date".to_string(),
+                    ));
+                }
+
+                if fund_end_date > next_fund_date {
+                    return Err(CheckError::Assert(
+                        "fund_end_date is further in the future than next_fund_date".to_string(),
+                    ));
+                }
+                Ok(Outcome::Passed(started.elapsed()))
+            }
+            Self::SnapshotTags => {
+                let available: Vec<String> = context.get("v0/snapshot")?.json()?;
+                let tags = if context.tags.is_empty() {
+                    available.clone()
+                } else {
+                    context.tags.clone()
+                };
+                if tags.is_empty() {
+                    return Err(CheckError::Assert("no snapshot tags available".to_string()));
+                }
+                // any well formed voting key is valid, unknown key should result in empty list
+                let voting_key = "0".repeat(64);
+                for tag in &tags {
+                    if !available.contains(tag) {
+                        return Err(CheckError::Assert(format!(
+                            "snapshot tag '{}' not found, available: {:?}",
+                            tag, available
+                        )));
+                    }
+                    context.get(&format!("v0/snapshot/{}/{}", tag, voting_key))?;
+                }
+                Ok(Outcome::Passed(started.elapsed()))
+            }
+            Self::QrAccounts => {
+                let folder = match &context.qr_codes {
+                    Some(folder) => folder,
+                    None => return Ok(Outcome::Skipped("--qr-codes not provided".to_string())),
+                };
+                let qrs = sample_qr_codes(folder, context.thresholds.qr_sample_size)?;
+                if qrs.is_empty() {
+                    return Err(CheckError::Assert(format!(
+                        "no qr codes found in {:?}",
+                        folder
+                    )));
+                }
+                started = Instant::now();
+                for qr in qrs {
+                    let pin = match &context.pin {
+                        Some(pin) => pin.clone(),
+                        None => pin_from_file_name(&qr)?,
+                    };
+                    let secrets = KeyQrCode::decode(image::open(&qr)?, &pin_to_bytes(&pin))
+                        .map_err(|e| CheckError::Qr(qr.clone(), e.to_string()))?;
+                    let public_key = secrets
+                        .first()
+                        .ok_or_else(|| CheckError::Qr(qr.clone(), "empty qr code".to_string()))?
+                        .to_public();
+                    wallet_backend
+                        .node_client()
+                        .account_state_by_pk(public_key.to_bech32_str())
+                        .map_err(|e| {
+                            CheckError::Assert(format!("account from {:?} not found: {}", qr, e))
+                        })?;
+                }
+                Ok(Outcome::Passed(started.elapsed()))
+            }
+            Self::FragmentsV1 => {
+                context.get("v1/fragments/logs")?;
+                context.get(&format!(
+                    "v1/fragments/statuses?fragment_ids={}",
+                    "0".repeat(64)
+                ))?;
+                Ok(Outcome::Passed(started.elapsed()))
+            }
+            Self::Certificate => {
+                let url: reqwest::Url = context
+                    .address
+                    .parse()
+                    .map_err(|e| CheckError::Assert(format!("invalid address: {}", e)))?;
+                if url.scheme() != "https" {
+                    return Ok(Outcome::Skipped(format!(
+                        "target address uses '{}' scheme",
+                        url.scheme()
+                    )));
+                }
+                let days_left = tls::days_until_expiry(&url, context.ca_cert.as_deref())?;
+                let minimum = context.thresholds.min_certificate_validity_days as i64;
+                if days_left < minimum {
+                    return Err(CheckError::Assert(format!(
+                        "certificate expires in {} days, expected at least {}",
+                        days_left, minimum
+                    )));
+                }
+                Ok(Outcome::Passed(started.elapsed()))
+            }
+            Self::ClockSkew => {
+                let settings = wallet_backend.settings()?;
+                let vote_plans = wallet_backend.vote_plan_statuses()?;
+                let fund = wallet_backend.funds()?;
+
+                for vote_plan in &fund.chain_vote_plans {
+                    let status = vote_plans
+                        .iter()
+                        .find(|status| status.id.to_string() == vote_plan.chain_voteplan_id)
+                        .ok_or_else(|| {
+                            CheckError::Assert(format!(
+                                "vote plan {} from vit-ss not found on node",
+                                vote_plan.chain_voteplan_id
+                            ))
+                        })?;
+                    for (name, block_date, expected) in [
+                        (
+                            "vote start",
+                            status.vote_start,
+                            vote_plan.chain_vote_start_time,
+                        ),
+                        ("vote end", status.vote_end, vote_plan.chain_vote_end_time),
+                        (
+                            "committee end",
+                            status.committee_end,
+                            vote_plan.chain_committee_end_time,
+                        ),
+                    ] {
+                        let actual = block_date_to_timestamp(&settings, block_date);
+                        let skew = (actual - expected).unsigned_abs();
+                        if skew > context.thresholds.max_clock_skew_secs {
+                            return Err(CheckError::Assert(format!(
+                                "{} of vote plan {} differs by {} s between node ({}) and vit-ss ({})",
+                                name,
+                                vote_plan.chain_voteplan_id,
+                                skew,
+                                unix_timestamp_to_datetime(actual),
+                                unix_timestamp_to_datetime(expected)
+                            )));
+                        }
+                    }
+                }
+                Ok(Outcome::Passed(started.elapsed()))
+            }
+            Self::VotePlanProposals => {
+                let vote_plans = wallet_backend.vote_plan_statuses()?;
+                let fund = wallet_backend.funds()?;
+                let mut groups: Vec<String> = fund
+                    .groups
+                    .iter()
+                    .map(|group| group.group_id.clone())
+                    .collect();
+                if groups.is_empty() {
+                    groups = vec!["direct".to_string(), "dreps".to_string()];
+                }
+
+                for group in groups {
+                    for info in wallet_backend.proposals(&group)? {
+                        let proposal_id = &info.proposal.proposal_id;
+                        let vote_plan = vote_plans
+                            .iter()
+                            .find(|status| status.id.to_string() == info.voteplan.chain_voteplan_id)
+                            .ok_or_else(|| {
+                                CheckError::Assert(format!(
+                                    "proposal {} points to unknown vote plan {}",
+                                    proposal_id, info.voteplan.chain_voteplan_id
+                                ))
+                            })?;
+                        let on_chain = vote_plan
+                            .proposals
+                            .iter()
+                            .find(|proposal| {
+                                proposal.index as i64 == info.voteplan.chain_proposal_index
+                            })
+                            .ok_or_else(|| {
+                                CheckError::Assert(format!(
+                                    "proposal {} has index {} not present in vote plan {}",
+                                    proposal_id,
+                                    info.voteplan.chain_proposal_index,
+                                    info.voteplan.chain_voteplan_id
+                                ))
+                            })?;
+                        let chain_proposal_id =
+                            String::from_utf8_lossy(&info.proposal.chain_proposal_id).to_string();
+                        if on_chain.proposal_id.to_string() != chain_proposal_id {
+                            return Err(CheckError::Assert(format!(
+                                "proposal {} has chain id {}, but vote plan {} contains {} at index {}",
+                                proposal_id,
+                                chain_proposal_id,
+                                info.voteplan.chain_voteplan_id,
+                                on_chain.proposal_id,
+                                on_chain.index
+                            )));
+                        }
+                    }
+                }
+                Ok(Outcome::Passed(started.elapsed()))
+            }
+        }
+    }
+}
+
+fn block_date_to_timestamp(settings: &SettingsDto, block_date: BlockDate) -> i64 {
+    let block0_time = std::time::SystemTime::from(settings.block0_time)
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let slots =
+        block_date.epoch() as u64 * settings.slots_per_epoch as u64 + block_date.slot() as u64;
+    (block0_time + slots * settings.slot_duration) as i64
+}
+
+fn sample_qr_codes(folder: &Path, size: usize) -> Result<Vec<PathBuf>, CheckError> {
+    let mut qrs = Vec::new();
+    for entry in std::fs::read_dir(folder)? {
+        let path = entry?.path();
+        let file_name = path.file_name().unwrap().to_string_lossy();
+        // zero funds wallets are not part of block0
+        if path.extension().map(|ext| ext == "png").unwrap_or(false)
+            && !file_name.starts_with("zero_funds")
+        {
+            qrs.push(path);
+        }
+    }
+    Ok(qrs
+        .choose_multiple(&mut rand::thread_rng(), size)
+        .cloned()
+        .collect())
+}
+
+fn pin_from_file_name(qr: &Path) -> Result<String, CheckError> {
+    qr.file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.rsplit('_').next())
+        .filter(|pin| !pin.is_empty() && pin.chars().all(|c| c.is_ascii_digit()))
+        .map(ToString::to_string)
+        .ok_or_else(|| {
+            CheckError::Qr(
+                qr.to_path_buf(),
+                "cannot read pin from file name".to_string(),
+            )
+        })
+}
+
+impl DeploymentValidateCommand {
+    pub fn exec(self) -> Result<(), CheckError> {
+        let started = Instant::now();
+        let spinner_style = ProgressStyle::default_spinner()
+            .tick_chars("⠁⠂⠄⡀⢀⠠⠐⠈ ")
+            .template("{prefix:.bold.dim} {spinner} {wide_msg}");
+
+        let thresholds: Thresholds = match &self.thresholds {
+            Some(path) => serde_yaml::from_str(&std::fs::read_to_string(path)?)?,
+            None => Default::default(),
+        };
+        let context = Arc::new(Context {
+            address: self.address.clone(),
+            thresholds,
+            tags: self.tags,
+            qr_codes: self.qr_codes,
+            pin: self.pin,
+            ca_cert: self.ca_cert,
+        });
+
+        let commands: Vec<Check> = Check::iter().collect();
+        let len = commands.len();
+        let m = MultiProgress::new();
+
+        let handles: Vec<_> = commands
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, command)| {
+                let pb = m.add(ProgressBar::new(1));
+                pb.set_style(spinner_style.clone());
+                pb.set_prefix(&format!("[{}/{}]", i + 1, len));
+                pb.set_message(&format!("{}...In progress", command));
+
+                let context = context.clone();
+                thread::spawn(move || {
+                    let finish_style =
+                        ProgressStyle::default_spinner().template("{prefix:.bold.dim} {wide_msg}");
+
+                    let check_started = Instant::now();
+                    let max_response_time =
+                        Duration::from_millis(context.thresholds.max_response_time_ms);
+                    let (status, message) = match command.execute(&context) {
+                        Ok(Outcome::Passed(elapsed)) if elapsed > max_response_time => (
+                            CheckStatus::Failed,
+                            Some(format!(
+                                "took {} ms, threshold is {} ms",
+                                elapsed.as_millis(),
+                                max_response_time.as_millis()
+                            )),
+                        ),
+                        Ok(Outcome::Passed(_)) => (CheckStatus::Passed, None),
+                        Ok(Outcome::Skipped(reason)) => (CheckStatus::Skipped, Some(reason)),
+                        Err(error) => (CheckStatus::Failed, Some(error.to_string())),
+                    };
+                    let result = CheckResult {
+                        name: command.to_string(),
+                        status,
+                        duration_ms: check_started.elapsed().as_millis() as u64,
+                        message,
+                    };
+
+                    pb.set_style(finish_style);
+                    pb.finish_with_message(&result.to_string());
+                    result
+                })
+            })
+            .collect();
+
+        m.join().unwrap();
+
+        let report = Report::new(
+            self.address,
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect(),
+        );
+
+        println!();
+        println!("Done in {}", HumanDuration(started.elapsed()));
+
+        if let Some(output) = &self.report {
+            std::fs::write(output, self.format.render(&report)?)?;
+            println!("Report saved to {:?}", output);
+        }
+
+        let failed = report.failed();
+        if failed > 0 {
+            return Err(CheckError::Failed {
+                failed,
+                total: report.checks.len(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CheckError {
+    #[error(transparent)]
+    WalletBackend(#[from] ValgrindError),
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+    #[error(transparent)]
+    Yaml(#[from] serde_yaml::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Tls(#[from] tls::Error),
+    #[error("cannot read qr code {0:?}: {1}")]
+    Qr(PathBuf, String),
+    #[error("{0}")]
+    Assert(String),
+    #[error("{failed} of {total} checks failed")]
+    Failed { failed: usize, total: usize },
+}
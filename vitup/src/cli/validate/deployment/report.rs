@@ -0,0 +1,184 @@
+use serde::Serialize;
+use std::fmt;
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    pub duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl fmt::Display for CheckResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.status, &self.message) {
+            (CheckStatus::Passed, _) => write!(
+                f,
+                "[Passed] {}. Duration: {} ms",
+                self.name, self.duration_ms
+            ),
+            (CheckStatus::Failed, message) => write!(
+                f,
+                "[Failed] {}. Error: {}",
+                self.name,
+                message.as_deref().unwrap_or_default()
+            ),
+            (CheckStatus::Skipped, message) => write!(
+                f,
+                "[Skipped] {}. Reason: {}",
+                self.name,
+                message.as_deref().unwrap_or_default()
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub address: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created: OffsetDateTime,
+    pub checks: Vec<CheckResult>,
+}
+
+impl Report {
+    pub fn new(address: String, checks: Vec<CheckResult>) -> Self {
+        Self {
+            address,
+            created: OffsetDateTime::now_utc(),
+            checks,
+        }
+    }
+
+    pub fn failed(&self) -> usize {
+        self.count(CheckStatus::Failed)
+    }
+
+    fn count(&self, status: CheckStatus) -> usize {
+        self.checks
+            .iter()
+            .filter(|check| check.status == status)
+            .count()
+    }
+
+    pub fn to_junit(&self) -> String {
+        let total_ms: u64 = self.checks.iter().map(|check| check.duration_ms).sum();
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuite name=\"vitup validate deployment\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{}\">\n",
+            self.checks.len(),
+            self.failed(),
+            self.count(CheckStatus::Skipped),
+            seconds(total_ms)
+        ));
+        for check in &self.checks {
+            xml.push_str(&format!(
+                "  <testcase classname=\"{}\" name=\"{}\" time=\"{}\"",
+                escape(&self.address),
+                escape(&check.name),
+                seconds(check.duration_ms)
+            ));
+            let message = escape(check.message.as_deref().unwrap_or_default());
+            match check.status {
+                CheckStatus::Passed => xml.push_str("/>\n"),
+                CheckStatus::Failed => xml.push_str(&format!(
+                    ">\n    <failure message=\"{}\"/>\n  </testcase>\n",
+                    message
+                )),
+                CheckStatus::Skipped => xml.push_str(&format!(
+                    ">\n    <skipped message=\"{}\"/>\n  </testcase>\n",
+                    message
+                )),
+            }
+        }
+        xml.push_str("</testsuite>\n");
+        xml
+    }
+}
+
+fn seconds(millis: u64) -> String {
+    format!("{:.3}", millis as f64 / 1000.0)
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ReportFormat {
+    Json,
+    Junit,
+}
+
+impl ReportFormat {
+    pub fn render(&self, report: &Report) -> Result<String, serde_json::Error> {
+        match self {
+            Self::Json => serde_json::to_string_pretty(report),
+            Self::Junit => Ok(report.to_junit()),
+        }
+    }
+}
+
+impl std::str::FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &*s.trim().to_lowercase() {
+            "json" => Ok(Self::Json),
+            "junit" => Ok(Self::Junit),
+            other => Err(format!("unknown report format '{}'", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn junit_report_counts_and_escapes() {
+        let report = Report::new(
+            "https://localhost".to_string(),
+            vec![
+                CheckResult {
+                    name: "[vit-ss] Fund endpoint check".to_string(),
+                    status: CheckStatus::Passed,
+                    duration_ms: 1500,
+                    message: None,
+                },
+                CheckResult {
+                    name: "[proxy] TLS certificate check".to_string(),
+                    status: CheckStatus::Failed,
+                    duration_ms: 10,
+                    message: Some("expires in <5> days".to_string()),
+                },
+                CheckResult {
+                    name: "[jormungandr] Qr wallets account lookup".to_string(),
+                    status: CheckStatus::Skipped,
+                    duration_ms: 0,
+                    message: Some("--qr-codes not provided".to_string()),
+                },
+            ],
+        );
+
+        let junit = report.to_junit();
+        assert!(junit.contains("tests=\"3\" failures=\"1\" skipped=\"1\" time=\"1.510\""));
+        assert!(junit.contains("<failure message=\"expires in &lt;5&gt; days\"/>"));
+        assert!(junit.contains("time=\"1.500\"/>"));
+    }
+}
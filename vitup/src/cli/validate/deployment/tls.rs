@@ -0,0 +1,159 @@
+use reqwest::Url;
+use rustls::{ClientConfig, ClientConnection, OwnedTrustAnchor, RootCertStore, ServerName};
+use std::convert::TryFrom;
+use std::io::BufReader;
+use std::net::TcpStream;
+use std::path::Path;
+use std::sync::Arc;
+use thiserror::Error;
+use time::OffsetDateTime;
+
+/// Performs TLS handshake with target (which validates certificate chain, host name and
+/// validity period) and returns number of days until server certificate expires.
+pub fn days_until_expiry(url: &Url, extra_ca: Option<&Path>) -> Result<i64, Error> {
+    let host = url
+        .host_str()
+        .ok_or_else(|| Error::MissingHost(url.to_string()))?;
+    let port = url.port_or_known_default().unwrap_or(443);
+
+    let mut roots = RootCertStore::empty();
+    roots.add_server_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.0.iter().map(|anchor| {
+        OwnedTrustAnchor::from_subject_spki_name_constraints(
+            anchor.subject,
+            anchor.spki,
+            anchor.name_constraints,
+        )
+    }));
+    if let Some(ca) = extra_ca {
+        let certs = rustls_pemfile::certs(&mut BufReader::new(std::fs::File::open(ca)?))?;
+        roots.add_parsable_certificates(&certs);
+    }
+
+    let config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    let server_name =
+        ServerName::try_from(host).map_err(|_| Error::MissingHost(host.to_string()))?;
+    let mut connection = ClientConnection::new(Arc::new(config), server_name)?;
+    let mut socket = TcpStream::connect((host, port))?;
+    while connection.is_handshaking() {
+        connection.complete_io(&mut socket)?;
+    }
+
+    let certificate = connection
+        .peer_certificates()
+        .and_then(|certs| certs.first())
+        .ok_or(Error::NoCertificate)?;
+    let (_, certificate) = x509_parser::parse_x509_certificate(&certificate.0)
+        .map_err(|e| Error::Parse(e.to_string()))?;
+    let not_after = certificate.validity().not_after.timestamp();
+    Ok((not_after - OffsetDateTime::now_utc().unix_timestamp()) / (24 * 60 * 60))
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("address has no host: {0}")]
+    MissingHost(String),
+    #[error("server did not present any certificate")]
+    NoCertificate,
+    #[error("cannot parse server certificate: {0}")]
+    Parse(String),
+    #[error(transparent)]
+    Tls(#[from] rustls::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CertificatesBuilder, TlsSettings};
+    use assert_fs::TempDir;
+    use rustls::{ServerConfig, ServerConnection};
+    use std::fs::File;
+    use std::net::TcpListener;
+    use valgrind::Certs;
+
+    /// Serves single TLS handshake with given certificates on random local port
+    fn serve_once(certs: &Certs) -> u16 {
+        let chain =
+            rustls_pemfile::certs(&mut BufReader::new(File::open(&certs.cert_path).unwrap()))
+                .unwrap()
+                .into_iter()
+                .map(rustls::Certificate)
+                .collect();
+        let key = rustls_pemfile::pkcs8_private_keys(&mut BufReader::new(
+            File::open(&certs.key_path).unwrap(),
+        ))
+        .unwrap()
+        .remove(0);
+        let config = ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(chain, rustls::PrivateKey(key))
+            .unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            let (mut socket, _) = listener.accept().unwrap();
+            let mut connection = ServerConnection::new(Arc::new(config)).unwrap();
+            while connection.is_handshaking() {
+                // client closes connection when it rejects certificate
+                match connection.complete_io(&mut socket) {
+                    Ok((0, 0)) | Err(_) => break,
+                    Ok(_) => (),
+                }
+            }
+        });
+        port
+    }
+
+    fn check(settings: TlsSettings, trust_ca: bool) -> Result<i64, Error> {
+        let working_dir = TempDir::new().unwrap();
+        let certs = CertificatesBuilder::default()
+            .settings(settings)
+            .build(working_dir.path())
+            .unwrap();
+        let port = serve_once(&certs);
+        let url = Url::parse(&format!("https://localhost:{}", port)).unwrap();
+        let ca = certs.ca_path.as_deref().filter(|_| trust_ca);
+        days_until_expiry(&url, ca)
+    }
+
+    fn assert_rejected(result: Result<i64, Error>, reason: &str) {
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains(reason), "unexpected error: {}", error);
+    }
+
+    #[test]
+    fn valid_certificate_reports_days_until_expiry() {
+        let days = check(TlsSettings::default(), true).unwrap();
+        assert_eq!(days, 29);
+    }
+
+    #[test]
+    fn expired_certificate_is_rejected() {
+        let settings = TlsSettings {
+            validity_days: 0,
+            ..Default::default()
+        };
+        assert_rejected(check(settings, true), "CertExpired");
+    }
+
+    #[test]
+    fn certificate_for_other_host_is_rejected() {
+        let settings = TlsSettings {
+            hostnames: vec!["vitup.local".to_string()],
+            ips: Vec::new(),
+            ..Default::default()
+        };
+        assert_rejected(check(settings, true), "CertNotValidForName");
+    }
+
+    #[test]
+    fn certificate_from_untrusted_ca_is_rejected() {
+        assert_rejected(check(TlsSettings::default(), false), "UnknownIssuer");
+    }
+}
@@ -0,0 +1,547 @@
+use crate::builders::utils::{block0_vote_plans, DeploymentTree};
+use crate::builders::{ProposalHashFields, ReviewHashFields};
+use diesel::RunQueryDsl;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+use thiserror::Error;
+use valgrind::ValgrindClient;
+use vit_servicing_station_lib::db;
+use vit_servicing_station_lib::db::models::challenges::Challenge;
+use vit_servicing_station_lib::db::models::community_advisors_reviews::AdvisorReview;
+use vit_servicing_station_lib::db::models::proposals::FullProposalInfo;
+use vit_servicing_station_lib::db::schema::community_advisors_reviews;
+
+/// Compares external proposal ids stored in vit-servicing-station with proposals registered
+/// in block0 vote plans. Optionally recalculates ids from vit-servicing-station data, when
+/// deployment derives them from proposal content
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct HashesValidateCommand {
+    /// deployment folder containing block0.bin and database.sqlite3
+    #[structopt(long = "deployment", required_unless = "address")]
+    pub deployment: Option<PathBuf>,
+
+    /// address of running deployment
+    #[structopt(long = "address", conflicts_with = "deployment")]
+    pub address: Option<String>,
+
+    /// voting groups which proposals are checked
+    #[structopt(long = "groups", default_value = "direct,dreps", use_delimiter = true)]
+    pub groups: Vec<String>,
+
+    /// save report as json
+    #[structopt(long = "output")]
+    pub output: Option<PathBuf>,
+
+    /// external ids were derived from proposal content and reviews (e.g. imported data),
+    /// so recalculate them and report proposals changed after vote plan creation.
+    /// Deployments generated by vitup register random ids, which cannot be recalculated
+    #[structopt(long = "derived-ids")]
+    pub derived_ids: bool,
+}
+
+impl HashesValidateCommand {
+    pub fn exec(self) -> Result<(), Error> {
+        let data = match (&self.deployment, &self.address) {
+            (Some(deployment), _) => DeploymentData::from_deployment(deployment, &self.groups)?,
+            (None, Some(address)) => DeploymentData::from_address(address, &self.groups)?,
+            (None, None) => return Err(Error::NoSource),
+        };
+
+        let report = HashesReport::new(
+            &block0_proposals(&data.block0)?,
+            &data.entries(),
+            self.derived_ids,
+        );
+        report.print();
+
+        if let Some(output) = &self.output {
+            std::fs::write(output, serde_json::to_string_pretty(&report)?)?;
+        }
+
+        if report.is_ok() {
+            Ok(())
+        } else {
+            Err(Error::ValidationFailed(report.issues_count()))
+        }
+    }
+}
+
+struct DeploymentData {
+    block0: Vec<u8>,
+    proposals: Vec<FullProposalInfo>,
+    challenges: Vec<Challenge>,
+    reviews: HashMap<String, Vec<AdvisorReview>>,
+}
+
+impl DeploymentData {
+    fn from_address(address: &str, groups: &[String]) -> Result<Self, Error> {
+        let client = ValgrindClient::new(address.to_string(), Default::default())?;
+
+        let mut proposals = Vec::new();
+        for group in groups {
+            proposals.extend(client.proposals(group)?);
+        }
+
+        let mut reviews = HashMap::new();
+        for proposal in &proposals {
+            let proposal_id = proposal.proposal.proposal_id.clone();
+            if !reviews.contains_key(&proposal_id) {
+                let proposal_reviews = client.review(&proposal_id)?.into_values().flatten();
+                reviews.insert(proposal_id, proposal_reviews.collect());
+            }
+        }
+
+        Ok(Self {
+            block0: client.block0()?,
+            challenges: client.challenges()?,
+            proposals,
+            reviews,
+        })
+    }
+
+    fn from_deployment(deployment: &Path, groups: &[String]) -> Result<Self, Error> {
+        let tree = DeploymentTree::new(deployment);
+        let block0 = std::fs::read(tree.block0_path())?;
+        let pool = db::load_db_connection_pool(&tree.database_path().to_string_lossy())
+            .map_err(|e| Error::Database(e.to_string()))?;
+
+        tokio::runtime::Runtime::new()?.block_on(async {
+            let mut proposals = Vec::new();
+            for group in groups {
+                proposals.extend(
+                    db::queries::proposals::query_all_proposals(group.clone(), &pool)
+                        .await
+                        .map_err(|e| Error::Database(e.to_string()))?,
+                );
+            }
+
+            let challenges = db::queries::challenges::query_all_challenges(&pool)
+                .await
+                .map_err(|e| Error::Database(e.to_string()))?;
+
+            let connection = pool.get().map_err(|e| Error::Database(e.to_string()))?;
+            let mut reviews: HashMap<String, Vec<AdvisorReview>> = HashMap::new();
+            for review in community_advisors_reviews::table
+                .load::<AdvisorReview>(&connection)
+                .map_err(|e| Error::Database(e.to_string()))?
+            {
+                reviews
+                    .entry(review.proposal_id.to_string())
+                    .or_default()
+                    .push(review);
+            }
+
+            Ok::<_, Error>(Self {
+                block0,
+                proposals,
+                challenges,
+                reviews,
+            })
+        })
+    }
+
+    fn entries(&self) -> Vec<ProposalEntry> {
+        self.proposals
+            .iter()
+            .map(|info| {
+                let proposal = &info.proposal;
+                let mut reviews: Vec<ReviewHashFields> = self
+                    .reviews
+                    .get(&proposal.proposal_id)
+                    .cloned()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(Into::into)
+                    .collect();
+                reviews.sort_by(|a, b| a.id.cmp(&b.id));
+
+                ProposalEntry {
+                    vote_plan_id: info.voteplan.chain_voteplan_id.clone(),
+                    index: info.voteplan.chain_proposal_index,
+                    chain_proposal_id: String::from_utf8_lossy(&proposal.chain_proposal_id)
+                        .to_string(),
+                    fields: ProposalHashFields {
+                        proposal_id: proposal.proposal_id.clone(),
+                        proposal_url: proposal.proposal_url.clone(),
+                        proposal_summary: proposal.proposal_summary.clone(),
+                        category_name: proposal.proposal_category.category_name.clone(),
+                        challenge_type: self
+                            .challenges
+                            .iter()
+                            .find(|challenge| challenge.id == proposal.challenge_id)
+                            .map(|challenge| challenge.challenge_type.to_string())
+                            .unwrap_or_default(),
+                        chain_vote_options: proposal.chain_vote_options.as_csv_string(),
+                        chain_vote_type: proposal.chain_voteplan_payload.clone(),
+                        files_url: proposal.proposal_files_url.clone(),
+                        proposer_url: proposal.proposer.proposer_url.clone(),
+                        reviews,
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+/// Vote plan id with external ids of its proposals, in order of their index
+type Block0VotePlans = BTreeMap<String, Vec<String>>;
+
+fn block0_proposals(block0: &[u8]) -> Result<Block0VotePlans, Error> {
+    Ok(block0_vote_plans(block0)
+        .map_err(|e| Error::Block0(e.to_string()))?
+        .into_iter()
+        .map(|vote_plan| {
+            (
+                vote_plan.to_id().to_string(),
+                vote_plan
+                    .proposals()
+                    .iter()
+                    .map(|proposal| proposal.external_id().to_string())
+                    .collect(),
+            )
+        })
+        .collect())
+}
+
+struct ProposalEntry {
+    vote_plan_id: String,
+    index: i64,
+    chain_proposal_id: String,
+    fields: ProposalHashFields,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MismatchKind {
+    /// external id recalculated from vit-ss data differs from one stored in vit-ss
+    Hash,
+    /// proposal stored in vit-ss differs from block0 proposal under the same index
+    Block0,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Mismatch {
+    pub kind: MismatchKind,
+    pub proposal_id: String,
+    pub vote_plan_id: String,
+    pub index: i64,
+    pub expected: String,
+    pub actual: String,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OrphanSource {
+    /// vit-ss proposal which points to vote plan or index not existing in block0
+    VitStation,
+    /// block0 proposal which no vit-ss proposal points to
+    Block0,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrphanProposal {
+    pub source: OrphanSource,
+    pub proposal_id: Option<String>,
+    pub external_id: String,
+    pub vote_plan_id: String,
+    pub index: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IndexGap {
+    pub vote_plan_id: String,
+    pub missing: Vec<i64>,
+    pub duplicated: Vec<i64>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct HashesReport {
+    pub checked: usize,
+    pub mismatches: Vec<Mismatch>,
+    pub orphans: Vec<OrphanProposal>,
+    pub index_gaps: Vec<IndexGap>,
+}
+
+impl HashesReport {
+    /// `derived_ids` enables comparison of stored ids with ones recalculated from proposal data
+    fn new(vote_plans: &Block0VotePlans, entries: &[ProposalEntry], derived_ids: bool) -> Self {
+        let mut report = Self {
+            checked: entries.len(),
+            ..Default::default()
+        };
+        let mut used_indexes: BTreeMap<&str, Vec<i64>> = BTreeMap::new();
+
+        for entry in entries {
+            used_indexes
+                .entry(&entry.vote_plan_id)
+                .or_default()
+                .push(entry.index);
+
+            if derived_ids {
+                let recalculated = entry.fields.external_id().to_string();
+                if recalculated != entry.chain_proposal_id {
+                    report.mismatches.push(Mismatch {
+                        kind: MismatchKind::Hash,
+                        proposal_id: entry.fields.proposal_id.clone(),
+                        vote_plan_id: entry.vote_plan_id.clone(),
+                        index: entry.index,
+                        expected: recalculated,
+                        actual: entry.chain_proposal_id.clone(),
+                    });
+                }
+            }
+
+            let block0_id = vote_plans
+                .get(&entry.vote_plan_id)
+                .and_then(|proposals| proposals.get(entry.index as usize));
+            match block0_id {
+                None => report.orphans.push(OrphanProposal {
+                    source: OrphanSource::VitStation,
+                    proposal_id: Some(entry.fields.proposal_id.clone()),
+                    external_id: entry.chain_proposal_id.clone(),
+                    vote_plan_id: entry.vote_plan_id.clone(),
+                    index: entry.index,
+                }),
+                Some(block0_id) if *block0_id != entry.chain_proposal_id => {
+                    report.mismatches.push(Mismatch {
+                        kind: MismatchKind::Block0,
+                        proposal_id: entry.fields.proposal_id.clone(),
+                        vote_plan_id: entry.vote_plan_id.clone(),
+                        index: entry.index,
+                        expected: block0_id.clone(),
+                        actual: entry.chain_proposal_id.clone(),
+                    })
+                }
+                Some(_) => (),
+            }
+        }
+
+        for (vote_plan_id, proposals) in vote_plans {
+            let used: BTreeSet<i64> = used_indexes
+                .get(vote_plan_id.as_str())
+                .map(|indexes| indexes.iter().cloned().collect())
+                .unwrap_or_default();
+            for (index, external_id) in proposals.iter().enumerate() {
+                if !used.contains(&(index as i64)) {
+                    report.orphans.push(OrphanProposal {
+                        source: OrphanSource::Block0,
+                        proposal_id: None,
+                        external_id: external_id.clone(),
+                        vote_plan_id: vote_plan_id.clone(),
+                        index: index as i64,
+                    });
+                }
+            }
+        }
+
+        for (vote_plan_id, mut indexes) in used_indexes {
+            indexes.sort_unstable();
+            let mut duplicated: Vec<i64> = indexes
+                .windows(2)
+                .filter(|pair| pair[0] == pair[1])
+                .map(|pair| pair[0])
+                .collect();
+            duplicated.dedup();
+            let used: BTreeSet<i64> = indexes.iter().cloned().collect();
+            let max = indexes.last().cloned().unwrap_or(-1);
+            let missing: Vec<i64> = (0..=max).filter(|index| !used.contains(index)).collect();
+
+            if !missing.is_empty() || !duplicated.is_empty() {
+                report.index_gaps.push(IndexGap {
+                    vote_plan_id: vote_plan_id.to_string(),
+                    missing,
+                    duplicated,
+                });
+            }
+        }
+        report
+    }
+
+    pub fn issues_count(&self) -> usize {
+        self.mismatches.len() + self.orphans.len() + self.index_gaps.len()
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.issues_count() == 0
+    }
+
+    pub fn print(&self) {
+        println!("Checked proposals: {}", self.checked);
+        for mismatch in &self.mismatches {
+            println!(
+                "[Mismatch:{:?}] proposal {} ({}#{}): expected {}, got {}",
+                mismatch.kind,
+                mismatch.proposal_id,
+                mismatch.vote_plan_id,
+                mismatch.index,
+                mismatch.expected,
+                mismatch.actual
+            );
+        }
+        for orphan in &self.orphans {
+            println!(
+                "[Orphan:{:?}] proposal {} ({}#{}): {}",
+                orphan.source,
+                orphan.proposal_id.as_deref().unwrap_or("-"),
+                orphan.vote_plan_id,
+                orphan.index,
+                orphan.external_id
+            );
+        }
+        for gap in &self.index_gaps {
+            println!(
+                "[IndexGap] vote plan {}: missing {:?}, duplicated {:?}",
+                gap.vote_plan_id, gap.missing, gap.duplicated
+            );
+        }
+        if self.is_ok() {
+            println!("No issues found");
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("either --deployment or --address should be defined")]
+    NoSource,
+    #[error("cannot read block0: {0}")]
+    Block0(String),
+    #[error("cannot read vit-servicing-station database: {0}")]
+    Database(String),
+    #[error("found {0} issues with proposals hashes")]
+    ValidationFailed(usize),
+    #[error(transparent)]
+    Valgrind(#[from] valgrind::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigBuilder;
+    use crate::mode::standard::generate_random_database;
+    use crate::testing::vitup_setup;
+    use assert_fs::TempDir;
+
+    fn entry(vote_plan_id: &str, index: i64, proposal_id: &str) -> ProposalEntry {
+        let fields = ProposalHashFields {
+            proposal_id: proposal_id.to_string(),
+            proposal_url: String::new(),
+            proposal_summary: String::new(),
+            category_name: String::new(),
+            challenge_type: "simple".to_string(),
+            chain_vote_options: "yes,no".to_string(),
+            chain_vote_type: "public".to_string(),
+            files_url: String::new(),
+            proposer_url: String::new(),
+            reviews: Vec::new(),
+        };
+        ProposalEntry {
+            vote_plan_id: vote_plan_id.to_string(),
+            index,
+            chain_proposal_id: fields.external_id().to_string(),
+            fields,
+        }
+    }
+
+    #[test]
+    fn consistent_deployment_has_no_issues() {
+        let entries = vec![entry("a", 0, "1"), entry("a", 1, "2")];
+        let vote_plans: Block0VotePlans = vec![(
+            "a".to_string(),
+            entries
+                .iter()
+                .map(|e| e.chain_proposal_id.clone())
+                .collect(),
+        )]
+        .into_iter()
+        .collect();
+
+        assert!(HashesReport::new(&vote_plans, &entries, true).is_ok());
+    }
+
+    #[test]
+    fn registered_ids_are_not_recalculated_by_default() {
+        let mut entries = vec![entry("a", 0, "1"), entry("a", 1, "2")];
+        // vitup registers random ids, unrelated to proposal content
+        for (entry, id) in entries.iter_mut().zip(["random_1", "random_2"]) {
+            entry.chain_proposal_id = id.to_string();
+        }
+        let vote_plans: Block0VotePlans = vec![(
+            "a".to_string(),
+            vec!["random_1".to_string(), "random_2".to_string()],
+        )]
+        .into_iter()
+        .collect();
+
+        assert!(HashesReport::new(&vote_plans, &entries, false).is_ok());
+        assert_eq!(
+            HashesReport::new(&vote_plans, &entries, true)
+                .mismatches
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn generated_deployment_has_no_issues() {
+        let config = ConfigBuilder::default().proposals_count(300).build();
+        let testing_directory = TempDir::new().unwrap();
+        let root = testing_directory.path().join("vitup");
+        let (controller, vit_parameters, _) = vitup_setup(&config, root.clone()).unwrap();
+        generate_random_database(
+            &DeploymentTree::new(&root),
+            vit_parameters,
+            controller.vote_plan_layout(),
+            &config,
+        )
+        .unwrap();
+
+        let data =
+            DeploymentData::from_deployment(&root, &config.data.current_fund.fund_info.groups)
+                .unwrap();
+        assert!(data.reviews.values().flatten().count() > 0);
+        let report = HashesReport::new(
+            &block0_proposals(&data.block0).unwrap(),
+            &data.entries(),
+            false,
+        );
+
+        assert!(report.checked > 0);
+        assert!(report.is_ok());
+    }
+
+    #[test]
+    fn inconsistencies_are_reported() {
+        let mut changed = entry("a", 0, "1");
+        changed.fields.proposal_summary = "changed after vote plan creation".to_string();
+        let entries = vec![changed, entry("a", 2, "3"), entry("b", 0, "4")];
+        let vote_plans: Block0VotePlans = vec![(
+            "a".to_string(),
+            vec![
+                entry("a", 0, "1").chain_proposal_id,
+                entry("a", 1, "2").chain_proposal_id,
+                entry("a", 2, "3").chain_proposal_id,
+            ],
+        )]
+        .into_iter()
+        .collect();
+
+        let report = HashesReport::new(&vote_plans, &entries, true);
+        assert_eq!(report.mismatches.len(), 1);
+        let mismatch = &report.mismatches[0];
+        assert_eq!(mismatch.kind, MismatchKind::Hash);
+        assert_eq!(mismatch.proposal_id, "1");
+        assert_eq!(mismatch.actual, entry("a", 0, "1").chain_proposal_id);
+        assert_ne!(mismatch.expected, mismatch.actual);
+        assert_eq!(report.orphans.len(), 2);
+        assert_eq!(report.index_gaps.len(), 1);
+        assert_eq!(report.index_gaps[0].missing, vec![1]);
+    }
+}
@@ -0,0 +1,286 @@
+mod report;
+pub mod rules;
+
+use crate::builders::build_current_fund;
+use crate::builders::utils::DeploymentTree;
+use crate::config::Config;
+use crate::mode::standard::DbGenerator;
+use chain_impl_mockchain::testing::scenario::template::ProposalDefBuilder;
+use chain_impl_mockchain::testing::scenario::template::VotePlanDef;
+use chain_impl_mockchain::testing::scenario::template::VotePlanDefBuilder;
+pub use report::{RuleEntry, Summary, ValidationReport};
+use rules::{IdeascaleData, RulesConfig, Validator};
+use serde_json::Value;
+use std::path::Path;
+use std::path::PathBuf;
+use structopt::StructOpt;
+use thiserror::Error;
+use vit_servicing_station_tests::common::data::ExternalValidVotingTemplateGenerator;
+use vit_servicing_station_tests::common::data::ValidVotePlanParameters;
+
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct IdeascaleValidateCommand {
+    /// output folder for reports, fixed data and database
+    #[structopt(long = "output", default_value = "./validate/output")]
+    pub output: PathBuf,
+
+    /// input folder
+    #[structopt(name = "INPUT")]
+    pub input: PathBuf,
+
+    /// prefix
+    #[structopt(long = "prefix")]
+    pub prefix: Option<String>,
+
+    /// apply automatic fixes for fixable rules
+    #[structopt(long = "fix")]
+    pub fix: bool,
+
+    /// replacement for proposer email when fixing
+    #[structopt(long = "mail", default_value = "")]
+    pub mail_replacement: String,
+
+    /// yaml or json file which enables, disables or changes severity of rules (globally or per fund)
+    #[structopt(long = "rules")]
+    pub rules: Option<PathBuf>,
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{errors} validation errors found, see report: {report:?}")]
+    ValidationFailed { errors: usize, report: PathBuf },
+    #[error("expected json array or object in {0:?}")]
+    UnexpectedFormat(PathBuf),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Yaml(#[from] serde_yaml::Error),
+    #[error(transparent)]
+    Template(#[from] vit_servicing_station_tests::common::data::TemplateLoad),
+    #[error(transparent)]
+    Data(#[from] crate::mode::standard::DataError),
+}
+
+impl IdeascaleValidateCommand {
+    fn add_prefix(&self, file_name: &str) -> String {
+        if let Some(prefix) = self.prefix.as_ref() {
+            format!("{}{}", prefix, file_name)
+        } else {
+            file_name.to_string()
+        }
+    }
+
+    pub fn exec(&self) -> Result<(), Error> {
+        std::env::set_var("RUST_BACKTRACE", "full");
+        std::fs::create_dir_all(&self.output)?;
+
+        let proposals_path = self.input.join(&self.add_prefix("proposals.json"));
+        let funds_path = self.input.join(&self.add_prefix("funds.json"));
+        let challenges_path = self.input.join(&self.add_prefix("challenges.json"));
+        let reviews_path = self.input.join(&self.add_prefix("reviews.json"));
+
+        let rules: RulesConfig = match &self.rules {
+            Some(path) => serde_yaml::from_str(&jortestkit::file::read_file(path)?)?,
+            None => Default::default(),
+        };
+        let mut validator = Validator::new(rules);
+        if self.fix {
+            validator = validator.fix(self.mail_replacement.clone());
+        }
+
+        let mut data = IdeascaleData {
+            funds: read_entries(&funds_path)?,
+            challenges: read_entries(&challenges_path)?,
+            proposals: read_entries(&proposals_path)?,
+            reviews: read_entries(&reviews_path)?,
+        };
+        let violations = validator.validate(&mut data);
+        let fund_id = data.fund_id();
+        let report = ValidationReport::new(
+            self.input.display().to_string(),
+            fund_id.clone(),
+            validator.rules(fund_id.as_deref()),
+            violations,
+        );
+
+        let report_path = self.output.join(self.add_prefix("report.json"));
+        std::fs::write(&report_path, serde_json::to_string_pretty(&report)?)?;
+        std::fs::write(
+            self.output.join(self.add_prefix("report.html")),
+            report.to_html(),
+        )?;
+        println!(
+            "Validation finished. Errors: {}, warnings: {}, fixed: {}. Report: {:?}",
+            report.summary.errors, report.summary.warnings, report.summary.fixed, report_path
+        );
+
+        let proposals_path = if self.fix {
+            let fixed = self.output.join(self.add_prefix("proposals.json"));
+            println!("Corrected proposals: {:?}..", fixed);
+            std::fs::write(&fixed, serde_json::to_string_pretty(&data.proposals)?)?;
+            fixed
+        } else {
+            proposals_path
+        };
+
+        check(&report, report_path)?;
+
+        self.generate_vit_database(
+            proposals_path,
+            challenges_path,
+            funds_path,
+            reviews_path,
+            data.proposals.len(),
+            data.challenges.len(),
+            data.reviews.len(),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn generate_vit_database(
+        &self,
+        proposals_path: PathBuf,
+        challenges_path: PathBuf,
+        funds_path: PathBuf,
+        reviews_path: PathBuf,
+        proposals_count: usize,
+        challenges_count: usize,
+        reviews_count: usize,
+    ) -> Result<(), Error> {
+        std::fs::create_dir_all(&self.output)?;
+
+        let deployment_tree = DeploymentTree::new(&self.output);
+
+        let mut template_generator = ExternalValidVotingTemplateGenerator::new(
+            proposals_path,
+            challenges_path,
+            funds_path,
+            reviews_path,
+        )?;
+
+        let mut input_parameters = Config::default();
+
+        input_parameters.data.current_fund.challenges = challenges_count;
+        input_parameters.data.current_fund.reviews = reviews_count;
+        input_parameters.data.current_fund.proposals = proposals_count as u32;
+
+        let vote_plans = (0..proposals_count)
+            .collect::<Vec<usize>>()
+            .chunks(255)
+            .map(|chunk| self.vote_plan_def(chunk.len()))
+            .collect();
+
+        let parameters = ValidVotePlanParameters::from(build_current_fund(
+            &input_parameters,
+            vote_plans,
+            vec![],
+        ));
+
+        DbGenerator::new(parameters, self.output.clone())
+            .build(&deployment_tree.database_path(), &mut template_generator)?;
+
+        Ok(())
+    }
+
+    fn vote_plan_def(&self, proposals_len: usize) -> VotePlanDef {
+        let mut vote_plan_builder = VotePlanDefBuilder::new("fund_x");
+        vote_plan_builder.owner("validate");
+        vote_plan_builder.vote_phases(1, 2, 3);
+
+        for _ in 0..proposals_len {
+            let mut proposal_builder = ProposalDefBuilder::new(
+                chain_impl_mockchain::testing::VoteTestGen::external_proposal_id(),
+            );
+            proposal_builder.options(3);
+            proposal_builder.action_off_chain();
+            vote_plan_builder.with_proposal(&mut proposal_builder);
+        }
+
+        vote_plan_builder.build()
+    }
+}
+
+/// Fails only if report contains unfixed violations with error severity, warnings are
+/// reported but do not stop the command
+fn check(report: &ValidationReport, report_path: PathBuf) -> Result<(), Error> {
+    if report.summary.errors > 0 {
+        return Err(Error::ValidationFailed {
+            errors: report.summary.errors,
+            report: report_path,
+        });
+    }
+    Ok(())
+}
+
+/// Reads json file with list of entries. Single object is treated as one element list
+fn read_entries(path: &Path) -> Result<Vec<Value>, Error> {
+    match serde_json::from_str(&jortestkit::file::read_file(path)?)? {
+        Value::Array(entries) => Ok(entries),
+        object @ Value::Object(_) => Ok(vec![object]),
+        _ => Err(Error::UnexpectedFormat(path.to_path_buf())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rules::Severity;
+    use serde_json::json;
+
+    fn report(proposal: Value) -> ValidationReport {
+        let mut data = IdeascaleData {
+            funds: vec![json!({ "id": 9 })],
+            challenges: vec![
+                json!({ "id": "1", "fund_id": "9", "rewards_total": "100", "proposers_rewards": "100" }),
+            ],
+            proposals: vec![proposal],
+            reviews: vec![],
+        };
+        let validator = Validator::new(Default::default());
+        let violations = validator.validate(&mut data);
+        ValidationReport::new(
+            "input".to_string(),
+            data.fund_id(),
+            validator.rules(None),
+            violations,
+        )
+    }
+
+    #[test]
+    fn warning_only_report_does_not_fail() {
+        let report = report(json!({
+            "proposal_id": "10",
+            "challenge_id": "1",
+            "challenge_type": "simple",
+            "proposal_funds": "1,000",
+            "proposal_summary": "**bold**",
+        }));
+
+        assert_eq!(report.summary.errors, 0);
+        assert_eq!(report.summary.warnings, 2);
+        assert!(report
+            .violations
+            .iter()
+            .all(|v| v.severity == Severity::Warning));
+        assert!(check(&report, PathBuf::from("report.json")).is_ok());
+    }
+
+    #[test]
+    fn report_with_errors_fails() {
+        let report = report(json!({
+            "proposal_id": "10",
+            "challenge_id": "2",
+            "challenge_type": "simple",
+            "proposal_funds": "1,000",
+        }));
+
+        assert_eq!(report.summary.errors, 1);
+        assert!(matches!(
+            check(&report, PathBuf::from("report.json")),
+            Err(Error::ValidationFailed { errors: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,152 @@
+use super::rules::{Rule, Severity, Violation};
+use serde::Serialize;
+use std::fmt::Write;
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleEntry {
+    pub rule: Rule,
+    pub description: String,
+    pub fixable: bool,
+    /// `None` if rule was disabled for validated fund
+    pub severity: Option<Severity>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Summary {
+    pub errors: usize,
+    pub warnings: usize,
+    pub fixed: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReport {
+    pub input: String,
+    pub fund_id: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created: OffsetDateTime,
+    pub summary: Summary,
+    pub rules: Vec<RuleEntry>,
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    pub fn new(
+        input: String,
+        fund_id: Option<String>,
+        rules: Vec<(Rule, Option<Severity>)>,
+        violations: Vec<Violation>,
+    ) -> Self {
+        let unfixed = |severity| {
+            violations
+                .iter()
+                .filter(|v| !v.fixed && v.severity == severity)
+                .count()
+        };
+        Self {
+            input,
+            fund_id,
+            created: OffsetDateTime::now_utc(),
+            summary: Summary {
+                errors: unfixed(Severity::Error),
+                warnings: unfixed(Severity::Warning),
+                fixed: violations.iter().filter(|v| v.fixed).count(),
+            },
+            rules: rules
+                .into_iter()
+                .map(|(rule, severity)| RuleEntry {
+                    rule,
+                    description: rule.description().to_string(),
+                    fixable: rule.is_fixable(),
+                    severity,
+                })
+                .collect(),
+            violations,
+        }
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        writeln!(
+            html,
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">"
+        )
+        .unwrap();
+        writeln!(html, "<title>Ideascale validation report</title>").unwrap();
+        writeln!(
+            html,
+            "<style>body{{font-family:sans-serif}}table{{border-collapse:collapse}}td,th{{border:1px solid #ccc;padding:4px 8px;text-align:left}}.error{{background:#fdd}}.warning{{background:#ffd}}.fixed{{background:#dfd}}</style>"
+        )
+        .unwrap();
+        writeln!(html, "</head>\n<body>").unwrap();
+        writeln!(
+            html,
+            "<h1>Ideascale validation report</h1>\n<p>Input: {} | Fund: {} | Created: {}</p>",
+            escape(&self.input),
+            escape(self.fund_id.as_deref().unwrap_or("-")),
+            self.created
+        )
+        .unwrap();
+        writeln!(
+            html,
+            "<p>Errors: {} | Warnings: {} | Fixed: {}</p>",
+            self.summary.errors, self.summary.warnings, self.summary.fixed
+        )
+        .unwrap();
+
+        writeln!(html, "<h2>Violations</h2>\n<table>").unwrap();
+        writeln!(html, "<tr><th>Entity</th><th>Id</th><th>Field</th><th>Rule</th><th>Severity</th><th>Message</th><th>Fixed</th></tr>").unwrap();
+        for violation in &self.violations {
+            let class = match (violation.fixed, violation.severity) {
+                (true, _) => "fixed",
+                (false, Severity::Error) => "error",
+                (false, Severity::Warning) => "warning",
+            };
+            writeln!(
+                html,
+                "<tr class=\"{}\"><td>{:?}</td><td>{}</td><td>{}</td><td>{}</td><td>{:?}</td><td>{}</td><td>{}</td></tr>",
+                class,
+                violation.entity,
+                escape(&violation.entity_id),
+                escape(&violation.field),
+                violation.rule,
+                violation.severity,
+                escape(&violation.message),
+                violation.fixed
+            )
+            .unwrap();
+        }
+        writeln!(html, "</table>").unwrap();
+
+        writeln!(html, "<h2>Rules</h2>\n<table>").unwrap();
+        writeln!(
+            html,
+            "<tr><th>Rule</th><th>Description</th><th>Severity</th><th>Fixable</th></tr>"
+        )
+        .unwrap();
+        for entry in &self.rules {
+            writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                entry.rule,
+                escape(&entry.description),
+                entry
+                    .severity
+                    .map(|severity| format!("{:?}", severity))
+                    .unwrap_or_else(|| "disabled".to_string()),
+                entry.fixable
+            )
+            .unwrap();
+        }
+        writeln!(html, "</table>\n</body>\n</html>").unwrap();
+        html
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
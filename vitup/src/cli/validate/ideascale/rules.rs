@@ -0,0 +1,510 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+const MARKDOWN_FIELDS: [&str; 7] = [
+    "proposal_solution",
+    "proposal_summary",
+    "proposer_relevant_experience",
+    "proposal_brief",
+    "proposal_goal",
+    "proposal_importance",
+    "proposal_metrics",
+];
+const ILLEGAL_MARKDOWN: [&str; 5] = ["**", "\\\\*", "\\\\", "\\*\\*", "\\-"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Entity {
+    Challenge,
+    Proposal,
+    Review,
+}
+
+/// Named validation rule for ideascale data
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rule {
+    ChallengeFundId,
+    ChallengeRewardsEmpty,
+    ChallengeRewardsMismatch,
+    DuplicateId,
+    UnknownChallenge,
+    MissingChallengeType,
+    ProposerEmail,
+    ProposalFundsFormat,
+    ProposalFundsEmpty,
+    MarkdownSyntax,
+    UnknownReviewProposal,
+}
+
+impl Rule {
+    /// Default severity. Formatting heuristics are only advisory, while rules which
+    /// break import of data into servicing station are errors
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::ProposalFundsFormat | Self::MarkdownSyntax => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+
+    pub fn entity(&self) -> Entity {
+        match self {
+            Self::ChallengeFundId
+            | Self::ChallengeRewardsEmpty
+            | Self::ChallengeRewardsMismatch => Entity::Challenge,
+            Self::UnknownReviewProposal => Entity::Review,
+            _ => Entity::Proposal,
+        }
+    }
+
+    pub fn is_fixable(&self) -> bool {
+        matches!(
+            self,
+            Self::MissingChallengeType
+                | Self::ProposerEmail
+                | Self::ProposalFundsFormat
+                | Self::MarkdownSyntax
+        )
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::ChallengeFundId => "challenge belongs to different fund",
+            Self::ChallengeRewardsEmpty => "challenge has empty rewards total",
+            Self::ChallengeRewardsMismatch => {
+                "challenge rewards total differs from proposers rewards"
+            }
+            Self::DuplicateId => "id is used by more than one entity",
+            Self::UnknownChallenge => "proposal points to challenge which does not exist",
+            Self::MissingChallengeType => {
+                "proposal has no challenge type (fix: inferred from proposal fields)"
+            }
+            Self::ProposerEmail => "proposal contains private data (fix: replaced with --mail)",
+            Self::ProposalFundsFormat => "proposal funds contain ',' (fix: removed)",
+            Self::ProposalFundsEmpty => "proposal funds are empty",
+            Self::MarkdownSyntax => "proposal text contains illegal markdown (fix: removed)",
+            Self::UnknownReviewProposal => "review points to proposal which does not exist",
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = serde_json::to_value(self).unwrap();
+        write!(f, "{}", name.as_str().unwrap())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct RuleSettings {
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub severity: Option<Severity>,
+}
+
+/// Enables, disables or changes severity of rules. Settings defined for fund
+/// (by fund id) take precedence over global ones, e.g.:
+///
+/// ```yaml
+/// rules:
+///   markdown-syntax: { severity: warning }
+/// funds:
+///   "9":
+///     challenge-rewards-mismatch: { enabled: false }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RulesConfig {
+    #[serde(default)]
+    pub rules: BTreeMap<Rule, RuleSettings>,
+    #[serde(default)]
+    pub funds: BTreeMap<String, BTreeMap<Rule, RuleSettings>>,
+}
+
+impl RulesConfig {
+    /// Effective severity of rule for given fund or `None` if rule is disabled
+    pub fn severity(&self, fund: Option<&str>, rule: Rule) -> Option<Severity> {
+        let for_fund = fund
+            .and_then(|fund| self.funds.get(fund))
+            .and_then(|rules| rules.get(&rule));
+        let global = self.rules.get(&rule);
+
+        let enabled = for_fund
+            .and_then(|s| s.enabled)
+            .or_else(|| global.and_then(|s| s.enabled))
+            .unwrap_or(true);
+        enabled.then(|| {
+            for_fund
+                .and_then(|s| s.severity)
+                .or_else(|| global.and_then(|s| s.severity))
+                .unwrap_or_else(|| rule.severity())
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Violation {
+    pub rule: Rule,
+    pub severity: Severity,
+    pub entity: Entity,
+    pub entity_id: String,
+    pub field: String,
+    pub message: String,
+    pub fixed: bool,
+}
+
+/// Raw ideascale data. Kept as json values, so data which does not match
+/// servicing station templates can still be validated and fixed.
+#[derive(Debug, Clone, Default)]
+pub struct IdeascaleData {
+    pub funds: Vec<Value>,
+    pub challenges: Vec<Value>,
+    pub proposals: Vec<Value>,
+    pub reviews: Vec<Value>,
+}
+
+impl IdeascaleData {
+    pub fn fund_id(&self) -> Option<String> {
+        self.funds.first().map(|fund| text(&fund["id"]))
+    }
+}
+
+/// Runs all enabled rules in one pass and optionally applies fixes in place
+pub struct Validator {
+    config: RulesConfig,
+    fix: Option<String>,
+}
+
+impl Validator {
+    pub fn new(config: RulesConfig) -> Self {
+        Self { config, fix: None }
+    }
+
+    /// Applies fixes for fixable rules. Proposer email is replaced with `mail_replacement`
+    pub fn fix<S: Into<String>>(mut self, mail_replacement: S) -> Self {
+        self.fix = Some(mail_replacement.into());
+        self
+    }
+
+    pub fn rules(&self, fund: Option<&str>) -> Vec<(Rule, Option<Severity>)> {
+        Rule::iter()
+            .map(|rule| (rule, self.config.severity(fund, rule)))
+            .collect()
+    }
+
+    pub fn validate(&self, data: &mut IdeascaleData) -> Vec<Violation> {
+        let fund_id = data.fund_id();
+        let mut violations = Violations {
+            config: &self.config,
+            fund: fund_id.clone(),
+            fix: self.fix.is_some(),
+            list: Vec::new(),
+        };
+
+        for challenge in &data.challenges {
+            let id = text(&challenge["id"]);
+            if let Some(fund_id) = &fund_id {
+                if text(&challenge["fund_id"]) != *fund_id {
+                    violations.push(
+                        Rule::ChallengeFundId,
+                        &id,
+                        "fund_id",
+                        format!("expected {}, got {}", fund_id, text(&challenge["fund_id"])),
+                    );
+                }
+            }
+            let rewards_total = text(&challenge["rewards_total"]);
+            if rewards_total.is_empty() {
+                violations.push(Rule::ChallengeRewardsEmpty, &id, "rewards_total", "empty");
+            }
+            let proposers_rewards = text(&challenge["proposers_rewards"]);
+            if rewards_total != proposers_rewards {
+                violations.push(
+                    Rule::ChallengeRewardsMismatch,
+                    &id,
+                    "rewards_total",
+                    format!("{} != {}", rewards_total, proposers_rewards),
+                );
+            }
+        }
+
+        for (entity, field, items) in [
+            (Entity::Challenge, "id", &data.challenges),
+            (Entity::Proposal, "proposal_id", &data.proposals),
+            (Entity::Review, "id", &data.reviews),
+        ] {
+            let mut seen = HashSet::new();
+            for item in items.iter() {
+                let id = text(&item[field]);
+                if !seen.insert(id.clone()) {
+                    violations.push_for(entity, Rule::DuplicateId, &id, field, "duplicated id");
+                }
+            }
+        }
+
+        let challenge_ids: HashSet<String> = data
+            .challenges
+            .iter()
+            .map(|challenge| text(&challenge["id"]))
+            .collect();
+
+        for proposal in data.proposals.iter_mut() {
+            let id = text(&proposal["proposal_id"]);
+            self.validate_proposal(&id, proposal, &challenge_ids, &mut violations);
+        }
+
+        let proposal_ids: HashSet<String> = data
+            .proposals
+            .iter()
+            .map(|proposal| text(&proposal["proposal_id"]))
+            .collect();
+        for review in &data.reviews {
+            let proposal_id = text(&review["proposal_id"]);
+            if !proposal_ids.contains(&proposal_id) {
+                violations.push(
+                    Rule::UnknownReviewProposal,
+                    &text(&review["id"]),
+                    "proposal_id",
+                    format!("proposal '{}' not found", proposal_id),
+                );
+            }
+        }
+
+        violations.list
+    }
+
+    fn validate_proposal(
+        &self,
+        id: &str,
+        proposal: &mut Value,
+        challenge_ids: &HashSet<String>,
+        violations: &mut Violations,
+    ) {
+        let challenge_id = text(&proposal["challenge_id"]);
+        if !challenge_ids.contains(&challenge_id) {
+            violations.push(
+                Rule::UnknownChallenge,
+                id,
+                "challenge_id",
+                format!("challenge '{}' not found", challenge_id),
+            );
+        }
+
+        if let Some(proposal) = proposal.as_object_mut() {
+            if !proposal.contains_key("challenge_type") {
+                let inferred = if proposal.contains_key("proposal_brief") {
+                    "community-choice"
+                } else {
+                    "simple"
+                };
+                if violations.fixing(Rule::MissingChallengeType) {
+                    proposal.insert("challenge_type".to_string(), inferred.into());
+                }
+                violations.push(
+                    Rule::MissingChallengeType,
+                    id,
+                    "challenge_type",
+                    format!("missing, inferred: {}", inferred),
+                );
+            }
+        }
+
+        if let Some(proposer_email) = proposal.get_mut("proposer_email") {
+            if violations.fixing(Rule::ProposerEmail) {
+                *proposer_email = Value::String(self.fix.clone().unwrap_or_default());
+            }
+            violations.push(Rule::ProposerEmail, id, "proposer_email", "not sanitized");
+        }
+
+        if let Some(proposal_funds) = proposal.get_mut("proposal_funds") {
+            let before = text(proposal_funds);
+            if before.contains(',') {
+                let after = before.replace(',', "");
+                if violations.fixing(Rule::ProposalFundsFormat) {
+                    *proposal_funds = Value::String(after.clone());
+                }
+                violations.push(
+                    Rule::ProposalFundsFormat,
+                    id,
+                    "proposal_funds",
+                    format!("'{}' -> '{}'", before, after),
+                );
+            }
+            if before.is_empty() {
+                violations.push(Rule::ProposalFundsEmpty, id, "proposal_funds", "empty");
+            }
+        }
+
+        for field in MARKDOWN_FIELDS {
+            if let Some(Value::String(value)) = proposal.get_mut(field) {
+                let illegal: Vec<&str> = ILLEGAL_MARKDOWN
+                    .iter()
+                    .copied()
+                    .filter(|chars| value.contains(chars))
+                    .collect();
+                if illegal.is_empty() {
+                    continue;
+                }
+                if violations.fixing(Rule::MarkdownSyntax) {
+                    for chars in &illegal {
+                        *value = value.replace(chars, "");
+                    }
+                }
+                violations.push(
+                    Rule::MarkdownSyntax,
+                    id,
+                    field,
+                    format!("illegal chars: {}", illegal.join(" ")),
+                );
+            }
+        }
+    }
+}
+
+struct Violations<'a> {
+    config: &'a RulesConfig,
+    fund: Option<String>,
+    fix: bool,
+    list: Vec<Violation>,
+}
+
+impl Violations<'_> {
+    fn severity(&self, rule: Rule) -> Option<Severity> {
+        self.config.severity(self.fund.as_deref(), rule)
+    }
+
+    fn fixing(&self, rule: Rule) -> bool {
+        self.fix && rule.is_fixable() && self.severity(rule).is_some()
+    }
+
+    fn push<S: Into<String>>(&mut self, rule: Rule, entity_id: &str, field: &str, message: S) {
+        self.push_for(rule.entity(), rule, entity_id, field, message)
+    }
+
+    fn push_for<S: Into<String>>(
+        &mut self,
+        entity: Entity,
+        rule: Rule,
+        entity_id: &str,
+        field: &str,
+        message: S,
+    ) {
+        if let Some(severity) = self.severity(rule) {
+            self.list.push(Violation {
+                rule,
+                severity,
+                entity,
+                entity_id: entity_id.to_string(),
+                field: field.to_string(),
+                message: message.into(),
+                fixed: self.fixing(rule),
+            });
+        }
+    }
+}
+
+/// Ideascale exports mix strings and numbers for the same fields
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn data() -> IdeascaleData {
+        IdeascaleData {
+            funds: vec![json!({ "id": 9 })],
+            challenges: vec![
+                json!({ "id": "1", "fund_id": "9", "rewards_total": "100", "proposers_rewards": "100" }),
+                json!({ "id": "2", "fund_id": "8", "rewards_total": "100", "proposers_rewards": "90" }),
+            ],
+            proposals: vec![
+                json!({
+                    "proposal_id": "10",
+                    "challenge_id": "1",
+                    "challenge_type": "simple",
+                    "proposal_funds": "1,000",
+                    "proposal_summary": "**bold**",
+                    "proposer_email": "mail@mail.com",
+                }),
+                json!({ "proposal_id": "11", "challenge_id": "3", "proposal_funds": "" }),
+            ],
+            reviews: vec![json!({ "id": "1", "proposal_id": "12" })],
+        }
+    }
+
+    #[test]
+    fn all_violations_are_reported_in_one_pass() {
+        let mut data = data();
+        let violations = Validator::new(Default::default()).validate(&mut data);
+        let rules: Vec<(Rule, &str)> = violations
+            .iter()
+            .map(|v| (v.rule, v.entity_id.as_str()))
+            .collect();
+
+        assert_eq!(
+            rules,
+            vec![
+                (Rule::ChallengeFundId, "2"),
+                (Rule::ChallengeRewardsMismatch, "2"),
+                (Rule::ProposerEmail, "10"),
+                (Rule::ProposalFundsFormat, "10"),
+                (Rule::MarkdownSyntax, "10"),
+                (Rule::UnknownChallenge, "11"),
+                (Rule::MissingChallengeType, "11"),
+                (Rule::ProposalFundsEmpty, "11"),
+                (Rule::UnknownReviewProposal, "1"),
+            ]
+        );
+        assert!(violations.iter().all(|v| !v.fixed));
+        assert_eq!(data.proposals, self::data().proposals);
+    }
+
+    #[test]
+    fn fixes_are_applied_only_for_enabled_rules() {
+        let config: RulesConfig = serde_yaml::from_str(
+            r#"
+rules:
+  challenge-rewards-mismatch: { severity: warning }
+funds:
+  "9":
+    markdown-syntax: { enabled: false }
+"#,
+        )
+        .unwrap();
+        let mut data = data();
+        let violations = Validator::new(config).fix("hidden").validate(&mut data);
+
+        let mismatch = violations
+            .iter()
+            .find(|v| v.rule == Rule::ChallengeRewardsMismatch)
+            .unwrap();
+        assert_eq!(mismatch.severity, Severity::Warning);
+        assert!(!violations.iter().any(|v| v.rule == Rule::MarkdownSyntax));
+
+        let proposal = &data.proposals[0];
+        assert_eq!(proposal["proposal_funds"], "1000");
+        assert_eq!(proposal["proposer_email"], "hidden");
+        assert_eq!(proposal["proposal_summary"], "**bold**");
+        assert_eq!(data.proposals[1]["challenge_type"], "simple");
+    }
+}
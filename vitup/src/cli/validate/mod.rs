@@ -1,10 +1,12 @@
 mod config;
 mod deployment;
-mod ideascale;
+mod hashes;
+pub mod ideascale;
 
 use config::ConfigValidateCommand;
 use deployment::CheckError;
 use deployment::DeploymentValidateCommand;
+use hashes::HashesValidateCommand;
 pub use ideascale::Error as IdeascaleError;
 use ideascale::IdeascaleValidateCommand;
 use structopt::StructOpt;
@@ -16,6 +18,7 @@ pub enum ValidateCommand {
     Ideascale(IdeascaleValidateCommand),
     Deployment(DeploymentValidateCommand),
     Config(ConfigValidateCommand),
+    Hashes(HashesValidateCommand),
 }
 
 impl ValidateCommand {
@@ -24,6 +27,7 @@ impl ValidateCommand {
             Self::Ideascale(ideascale) => ideascale.exec().map_err(Into::into),
             Self::Deployment(deployment) => deployment.exec().map_err(Into::into),
             Self::Config(config) => config.exec().map_err(Into::into),
+            Self::Hashes(hashes) => hashes.exec().map_err(Into::into),
         }
     }
 }
@@ -36,4 +40,6 @@ pub enum Error {
     Deployment(#[from] CheckError),
     #[error(transparent)]
     Ideascale(#[from] ideascale::Error),
+    #[error(transparent)]
+    Hashes(#[from] hashes::Error),
 }
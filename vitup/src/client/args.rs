@@ -1,11 +1,14 @@
 use crate::client::rest::VitupAdminRestClient;
 use crate::client::rest::VitupDisruptionRestClient;
 use crate::client::rest::VitupRest;
-use crate::config::Config;
+use crate::config::{Config, SnapshotInitials};
+use crate::mode::mock::{FragmentRecieveStrategy, NetworkCongestionMode};
 use crate::Result;
-use std::path::PathBuf;
+use snapshot_lib::VoterHIR;
+use std::path::{Path, PathBuf};
 use structopt::StructOpt;
 use thor::PersistentLogViewer;
+use vit_servicing_station_lib::db::models::funds::Fund;
 
 #[derive(StructOpt, Debug)]
 pub struct VitupClientCommand {
@@ -91,6 +94,15 @@ impl LogsCommand {
 #[derive(StructOpt, Debug)]
 pub enum FilesCommand {
     List,
+    /// downloads file from mock working directory
+    Get {
+        /// path relative to mock working directory
+        #[structopt(long = "path")]
+        path: String,
+        /// prints content to stdout if not provided
+        #[structopt(long = "output")]
+        output: Option<PathBuf>,
+    },
 }
 
 impl FilesCommand {
@@ -100,25 +112,46 @@ impl FilesCommand {
                 println!("{}", serde_json::to_string_pretty(&rest.list_files()?)?);
                 Ok(())
             }
+            Self::Get { path, output } => {
+                let content = rest.get_file(path)?;
+                match output {
+                    Some(output) => std::fs::write(output, content)?,
+                    None => println!("{}", String::from_utf8_lossy(&content)),
+                }
+                Ok(())
+            }
         }
     }
 }
 
+fn read_json<T: serde::de::DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T> {
+    let content = jortestkit::prelude::read_file(path)?;
+    serde_json::from_str(&content).map_err(Into::into)
+}
+
 #[derive(StructOpt, Debug)]
 pub enum ControlCommand {
-    Reset,
+    Reset(ResetCommand),
     SetUnavailable,
     SetErrorCode(SetErrorCodeCommand),
     SetAvailable,
     SetFundId(SetFundIdCommand),
+    /// replaces fund with the same id or adds new one
+    UpdateFund(UpdateFundCommand),
+    /// overrides version reported by mock
+    SetVersion(SetVersionCommand),
     Fragments(FragmentsCommand),
+    /// rejects calls to account endpoint
+    BlockAccount(BlockAccountCommand),
+    Congestion(CongestionCommand),
+    Snapshot(SnapshotCommand),
     Health,
 }
 
 impl ControlCommand {
     pub fn exec(self, rest: VitupDisruptionRestClient) -> Result<()> {
         match self {
-            Self::Reset => rest.reset().map_err(Into::into),
+            Self::Reset(reset) => reset.exec(rest),
             Self::SetUnavailable => rest.make_unavailable().map_err(Into::into),
             Self::SetErrorCode(set_error_code) => {
                 rest.set_error_code(set_error_code.code).map_err(Into::into)
@@ -127,7 +160,14 @@ impl ControlCommand {
             Self::SetFundId(set_fund_id) => {
                 rest.set_fund_id(set_fund_id.fund_id).map_err(Into::into)
             }
+            Self::UpdateFund(update_fund) => update_fund.exec(rest),
+            Self::SetVersion(set_version) => {
+                rest.set_version(set_version.version).map_err(Into::into)
+            }
             Self::Fragments(fragments_command) => fragments_command.exec(rest).map_err(Into::into),
+            Self::BlockAccount(block_account_command) => block_account_command.exec(rest),
+            Self::Congestion(congestion_command) => congestion_command.exec(rest),
+            Self::Snapshot(snapshot_command) => snapshot_command.exec(rest),
             Self::Health => {
                 match rest.is_up() {
                     true => {
@@ -143,6 +183,43 @@ impl ControlCommand {
     }
 }
 
+#[derive(StructOpt, Debug)]
+pub struct ResetCommand {
+    /// json file with mock configuration, default one is used if not provided
+    #[structopt(long = "config")]
+    config: Option<PathBuf>,
+}
+
+impl ResetCommand {
+    pub fn exec(self, rest: VitupDisruptionRestClient) -> Result<()> {
+        let config: Config = match self.config {
+            Some(config) => read_json(config)?,
+            None => Default::default(),
+        };
+        rest.reset(&config).map_err(Into::into)
+    }
+}
+
+#[derive(StructOpt, Debug)]
+pub struct UpdateFundCommand {
+    /// json file with fund definition
+    #[structopt(long = "fund")]
+    fund: PathBuf,
+}
+
+impl UpdateFundCommand {
+    pub fn exec(self, rest: VitupDisruptionRestClient) -> Result<()> {
+        let fund: Fund = read_json(self.fund)?;
+        rest.update_fund(&fund).map_err(Into::into)
+    }
+}
+
+#[derive(StructOpt, Debug)]
+pub struct SetVersionCommand {
+    #[structopt(long = "version")]
+    version: String,
+}
+
 #[derive(StructOpt, Debug)]
 pub struct SetFundIdCommand {
     #[structopt(long = "fund-id")]
@@ -160,7 +237,11 @@ pub enum FragmentsCommand {
     Reject,
     Hold,
     Accept,
+    /// drops incoming fragments without any trace
+    Forget,
     Reset,
+    /// changes status of already received fragment
+    Update(UpdateFragmentCommand),
 }
 
 impl FragmentsCommand {
@@ -169,7 +250,115 @@ impl FragmentsCommand {
             Self::Reject => rest.reject_all_fragments().map_err(Into::into),
             Self::Hold => rest.hold_all_fragments().map_err(Into::into),
             Self::Accept => rest.accept_all_fragments().map_err(Into::into),
+            Self::Forget => rest.forget_all_fragments().map_err(Into::into),
             Self::Reset => rest.reset_fragments_behavior().map_err(Into::into),
+            Self::Update(update_command) => update_command.exec(rest),
+        }
+    }
+}
+
+#[derive(StructOpt, Debug)]
+pub struct UpdateFragmentCommand {
+    /// fragment id or `last` for most recently received fragment
+    #[structopt(long = "id", default_value = "last")]
+    id: String,
+
+    #[structopt(subcommand)]
+    status: FragmentStatusCommand,
+}
+
+impl UpdateFragmentCommand {
+    pub fn exec(self, rest: VitupDisruptionRestClient) -> Result<()> {
+        let strategy = match self.status {
+            FragmentStatusCommand::Reject => FragmentRecieveStrategy::Reject,
+            FragmentStatusCommand::Hold => FragmentRecieveStrategy::Pending,
+            FragmentStatusCommand::Accept => FragmentRecieveStrategy::Accept,
+            FragmentStatusCommand::Forget => FragmentRecieveStrategy::Forget,
+        };
+        rest.update_fragment(self.id, strategy).map_err(Into::into)
+    }
+}
+
+#[derive(StructOpt, Debug)]
+pub enum FragmentStatusCommand {
+    Reject,
+    Hold,
+    Accept,
+    Forget,
+}
+
+#[derive(StructOpt, Debug)]
+pub enum BlockAccountCommand {
+    /// rejects given number of next calls to account endpoint
+    Set {
+        #[structopt(long = "calls")]
+        calls: u32,
+    },
+    /// makes account endpoint available again
+    Reset,
+}
+
+impl BlockAccountCommand {
+    pub fn exec(self, rest: VitupDisruptionRestClient) -> Result<()> {
+        match self {
+            Self::Set { calls } => rest.block_account_endpoint(calls).map_err(Into::into),
+            Self::Reset => rest.reset_block_account_endpoint().map_err(Into::into),
+        }
+    }
+}
+
+#[derive(StructOpt, Debug)]
+pub enum CongestionCommand {
+    Normal,
+    Jammed,
+    Moderate,
+    /// disables congestion simulation
+    Reset,
+}
+
+impl CongestionCommand {
+    pub fn exec(self, rest: VitupDisruptionRestClient) -> Result<()> {
+        let mode = match self {
+            Self::Normal => NetworkCongestionMode::Normal,
+            Self::Jammed => NetworkCongestionMode::Jammed,
+            Self::Moderate => NetworkCongestionMode::Moderate,
+            Self::Reset => NetworkCongestionMode::Disabled,
+        };
+        rest.set_congestion(mode).map_err(Into::into)
+    }
+}
+
+#[derive(StructOpt, Debug)]
+pub enum SnapshotCommand {
+    /// adds or overwrites voters snapshot for given tag
+    Add {
+        #[structopt(long = "tag")]
+        tag: String,
+        /// json file with voters, e.g. output of `snapshot create`
+        #[structopt(long = "snapshot")]
+        snapshot: PathBuf,
+    },
+    /// generates voters snapshot from initials definition
+    Create {
+        /// json file with snapshot initials
+        #[structopt(long = "initials")]
+        initials: PathBuf,
+    },
+}
+
+impl SnapshotCommand {
+    pub fn exec(self, rest: VitupDisruptionRestClient) -> Result<()> {
+        match self {
+            Self::Add { tag, snapshot } => {
+                let snapshot: Vec<VoterHIR> = read_json(snapshot)?;
+                rest.add_snapshot(tag, &snapshot).map_err(Into::into)
+            }
+            Self::Create { initials } => {
+                let initials: SnapshotInitials = read_json(initials)?;
+                let snapshot = rest.create_snapshot(&initials)?;
+                println!("{}", serde_json::to_string_pretty(&snapshot)?);
+                Ok(())
+            }
         }
     }
 }
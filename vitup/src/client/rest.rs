@@ -1,7 +1,15 @@
-use crate::config::Config;
-use crate::mode::service::manager::{file_lister::FolderDump, State};
-use reqwest::blocking::Response;
+use crate::config::{Config, SnapshotInitials};
+use crate::mode::mock::{FragmentRecieveStrategy, NetworkCongestionMode};
+use crate::mode::service::manager::{file_lister::FolderDump, Job, JobStatus, State};
+use crate::mode::standard::{DataUpdate, Disruption, DisruptionStatus};
+use jortestkit::web::api_token::API_TOKEN_HEADER;
+use reqwest::blocking::{RequestBuilder, Response};
+use reqwest::Method;
+use serde::Serialize;
+use snapshot_lib::VoterHIR;
 use thiserror::Error;
+use uuid::Uuid;
+use vit_servicing_station_lib::db::models::funds::Fund;
 
 pub struct VitupRest {
     token: Option<String>,
@@ -43,23 +51,133 @@ impl VitupRest {
     }
 
     pub fn post_skip_response<S: Into<String>>(&self, local_path: S) -> Result<(), Error> {
-        self.post(local_path).map(|_| ())
+        self.post(local_path)?.error_for_status()?;
+        Ok(())
     }
 
     pub fn post<S: Into<String>>(&self, local_path: S) -> Result<Response, Error> {
-        let path = self.path(local_path);
-        println!("Calling: {}", path);
-        let client = reqwest::blocking::Client::new();
-        client.post(&path).send().map_err(Into::into)
+        self.request(Method::POST, local_path)
+            .send()
+            .map_err(Into::into)
+    }
+
+    pub fn post_json<S: Into<String>, T: Serialize + ?Sized>(
+        &self,
+        local_path: S,
+        body: &T,
+    ) -> Result<Response, Error> {
+        Ok(self
+            .request(Method::POST, local_path)
+            .json(body)
+            .send()?
+            .error_for_status()?)
+    }
+
+    pub fn put_json<S: Into<String>, T: Serialize + ?Sized>(
+        &self,
+        local_path: S,
+        body: &T,
+    ) -> Result<Response, Error> {
+        Ok(self
+            .request(Method::PUT, local_path)
+            .json(body)
+            .send()?
+            .error_for_status()?)
     }
 
     pub fn get<S: Into<String>>(&self, local_path: S) -> Result<String, Error> {
+        Ok(self.request(Method::GET, local_path).send()?.text()?)
+    }
+
+    /// Request with api token header attached, if token was provided
+    fn request<S: Into<String>>(&self, method: Method, local_path: S) -> RequestBuilder {
         let path = self.path(local_path);
         println!("Calling: {}", path);
-        Ok(reqwest::blocking::get(&path)?.text()?)
+        let request = reqwest::blocking::Client::new().request(method, &path);
+        match &self.token {
+            Some(token) => request.header(API_TOKEN_HEADER, token),
+            None => request,
+        }
     }
 }
 
+const LOGS_GET: &str = "api/control/logs/get";
+const LOGS_CLEAR: &str = "api/control/logs/clear";
+const FILES_LIST: &str = "api/control/files/list";
+/// followed by path of file, relative to mock working directory
+const FILES_GET: &str = "api/control/files/get";
+const RESET: &str = "api/control/command/reset";
+const AVAILABLE: &str = "api/control/command/available/{bool}";
+const ERROR_CODE: &str = "api/control/command/error-code/{u16}";
+const FUND_ID: &str = "api/control/command/fund/id/{i32}";
+const FUND_UPDATE: &str = "api/control/command/fund/update";
+const VERSION: &str = "api/control/command/version/{String}";
+const BLOCK_ACCOUNT: &str = "api/control/command/block-account/{u32}";
+const BLOCK_ACCOUNT_RESET: &str = "api/control/command/block-account/reset";
+const FRAGMENTS_REJECT: &str = "api/control/command/fragments/reject";
+const FRAGMENTS_ACCEPT: &str = "api/control/command/fragments/accept";
+const FRAGMENTS_PENDING: &str = "api/control/command/fragments/pending";
+const FRAGMENTS_RESET: &str = "api/control/command/fragments/reset";
+const FRAGMENTS_FORGET: &str = "api/control/command/fragments/forget";
+const FRAGMENT_REJECT: &str = "api/control/command/fragments/update/{String}/reject";
+const FRAGMENT_ACCEPT: &str = "api/control/command/fragments/update/{String}/accept";
+const FRAGMENT_PENDING: &str = "api/control/command/fragments/update/{String}/pending";
+const FRAGMENT_FORGET: &str = "api/control/command/fragments/update/{String}/forget";
+const CONGESTION_NORMAL: &str = "api/control/command/congestion/normal";
+const CONGESTION_JAMMED: &str = "api/control/command/congestion/jammed";
+const CONGESTION_MODERATE: &str = "api/control/command/congestion/moderate";
+const CONGESTION_RESET: &str = "api/control/command/congestion/reset";
+const SNAPSHOT_ADD: &str = "api/control/command/snapshot/add/{String}";
+const SNAPSHOT_CREATE: &str = "api/control/command/snapshot/create";
+
+/// Control endpoints of mock covered by [`VitupDisruptionRestClient`]. Path parameters
+/// are written as `{type}`, the same way they are declared in mock routes
+pub const CONTROL_ENDPOINTS: &[&str] = &[
+    LOGS_GET,
+    LOGS_CLEAR,
+    FILES_LIST,
+    FILES_GET,
+    RESET,
+    AVAILABLE,
+    ERROR_CODE,
+    FUND_ID,
+    FUND_UPDATE,
+    VERSION,
+    BLOCK_ACCOUNT,
+    BLOCK_ACCOUNT_RESET,
+    FRAGMENTS_REJECT,
+    FRAGMENTS_ACCEPT,
+    FRAGMENTS_PENDING,
+    FRAGMENTS_RESET,
+    FRAGMENTS_FORGET,
+    FRAGMENT_REJECT,
+    FRAGMENT_ACCEPT,
+    FRAGMENT_PENDING,
+    FRAGMENT_FORGET,
+    CONGESTION_NORMAL,
+    CONGESTION_JAMMED,
+    CONGESTION_MODERATE,
+    CONGESTION_RESET,
+    SNAPSHOT_ADD,
+    SNAPSHOT_CREATE,
+];
+
+/// Replaces `{type}` placeholders of endpoint with arguments, in order
+fn endpoint(template: &str, args: &[&str]) -> String {
+    let mut args = args.iter().copied();
+    template
+        .split('/')
+        .map(|segment| {
+            if segment.starts_with('{') {
+                args.next().expect("missing endpoint argument")
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 pub struct VitupDisruptionRestClient {
     inner: VitupRest,
 }
@@ -72,66 +190,149 @@ impl From<VitupRest> for VitupDisruptionRestClient {
 
 impl VitupDisruptionRestClient {
     pub fn get_logs(&self) -> Result<Vec<String>, Error> {
-        serde_json::from_str(&self.inner.get("api/control/logs/get")?).map_err(Into::into)
+        serde_json::from_str(&self.inner.get(LOGS_GET)?).map_err(Into::into)
     }
 
     pub fn clear_logs(&self) -> Result<(), Error> {
-        self.inner.post_skip_response("api/control/logs/clear")
+        self.inner.post_skip_response(LOGS_CLEAR)
     }
 
     pub fn list_files(&self) -> Result<FolderDump, Error> {
-        serde_json::from_str(&self.inner.get("api/control/files/list")?).map_err(Into::into)
+        serde_json::from_str(&self.inner.get(FILES_LIST)?).map_err(Into::into)
+    }
+
+    /// Content of file from mock working directory, path is relative to it
+    pub fn get_file<S: Into<String>>(&self, path: S) -> Result<Vec<u8>, Error> {
+        let response = self
+            .inner
+            .request(Method::GET, format!("{}/{}", FILES_GET, path.into()))
+            .send()?
+            .error_for_status()?;
+        Ok(response.bytes()?.to_vec())
     }
 
-    pub fn reset(&self) -> Result<(), Error> {
-        self.inner.post_skip_response("api/control/command/reset")
+    /// Recreates mock environment from scratch with given configuration
+    pub fn reset(&self, config: &Config) -> Result<(), Error> {
+        self.inner.post_json(RESET, config).map(|_| ())
     }
 
     pub fn make_unavailable(&self) -> Result<(), Error> {
-        self.inner.post_skip_response("api/control/available/false")
+        self.inner
+            .post_skip_response(endpoint(AVAILABLE, &["false"]))
     }
 
     pub fn set_error_code(&self, error_code: u16) -> Result<(), Error> {
         self.inner
-            .post_skip_response(format!("api/control/availabl/error-code/{}", error_code))
+            .post_skip_response(endpoint(ERROR_CODE, &[&error_code.to_string()]))
     }
 
     pub fn make_available(&self) -> Result<(), Error> {
-        self.inner.post_skip_response("api/control/available/true")
+        self.inner
+            .post_skip_response(endpoint(AVAILABLE, &["true"]))
     }
 
     pub fn set_fund_id(&self, fund_id: u32) -> Result<(), Error> {
         self.inner
-            .post_skip_response(format!("api/control/command/fund/id/{}", fund_id))
+            .post_skip_response(endpoint(FUND_ID, &[&fund_id.to_string()]))
     }
 
-    pub fn reject_all_fragments(&self) -> Result<(), Error> {
+    /// Replaces fund with the same id or adds new one
+    pub fn update_fund(&self, fund: &Fund) -> Result<(), Error> {
+        self.inner.put_json(FUND_UPDATE, fund).map(|_| ())
+    }
+
+    /// Overrides version reported by mock
+    pub fn set_version<S: Into<String>>(&self, version: S) -> Result<(), Error> {
+        let version: String = version.into();
         self.inner
-            .post_skip_response("api/control/command/fragments/reject")
+            .post_skip_response(endpoint(VERSION, &[&version]))
     }
 
-    pub fn hold_all_fragments(&self) -> Result<(), Error> {
+    /// Rejects next `calls` requests to account endpoint
+    pub fn block_account_endpoint(&self, calls: u32) -> Result<(), Error> {
         self.inner
-            .post_skip_response("api/control/command/fragments/pending")
+            .post_skip_response(endpoint(BLOCK_ACCOUNT, &[&calls.to_string()]))
+    }
+
+    pub fn reset_block_account_endpoint(&self) -> Result<(), Error> {
+        self.inner.post_skip_response(BLOCK_ACCOUNT_RESET)
+    }
+
+    pub fn reject_all_fragments(&self) -> Result<(), Error> {
+        self.inner.post_skip_response(FRAGMENTS_REJECT)
+    }
+
+    pub fn hold_all_fragments(&self) -> Result<(), Error> {
+        self.inner.post_skip_response(FRAGMENTS_PENDING)
     }
 
     pub fn accept_all_fragments(&self) -> Result<(), Error> {
-        self.inner
-            .post_skip_response("api/control/command/fragments/accept")
+        self.inner.post_skip_response(FRAGMENTS_ACCEPT)
+    }
+
+    pub fn forget_all_fragments(&self) -> Result<(), Error> {
+        self.inner.post_skip_response(FRAGMENTS_FORGET)
     }
 
     pub fn reset_fragments_behavior(&self) -> Result<(), Error> {
+        self.inner.post_skip_response(FRAGMENTS_RESET)
+    }
+
+    /// Changes status of already received fragment. `fragment_id` can be `last`,
+    /// which stands for most recently received fragment
+    pub fn update_fragment<S: Into<String>>(
+        &self,
+        fragment_id: S,
+        strategy: FragmentRecieveStrategy,
+    ) -> Result<(), Error> {
+        let template = match strategy {
+            FragmentRecieveStrategy::Reject => FRAGMENT_REJECT,
+            FragmentRecieveStrategy::Accept => FRAGMENT_ACCEPT,
+            FragmentRecieveStrategy::Pending => FRAGMENT_PENDING,
+            FragmentRecieveStrategy::Forget => FRAGMENT_FORGET,
+            FragmentRecieveStrategy::None => {
+                return Err(Error::UnsupportedFragmentStrategy(strategy))
+            }
+        };
+        let fragment_id: String = fragment_id.into();
         self.inner
-            .post_skip_response("api/control/command/fragments/reset")
+            .post_skip_response(endpoint(template, &[&fragment_id]))
+    }
+
+    pub fn set_congestion(&self, mode: NetworkCongestionMode) -> Result<(), Error> {
+        let path = match mode {
+            NetworkCongestionMode::Normal => CONGESTION_NORMAL,
+            NetworkCongestionMode::Jammed => CONGESTION_JAMMED,
+            NetworkCongestionMode::Moderate => CONGESTION_MODERATE,
+            NetworkCongestionMode::Disabled => CONGESTION_RESET,
+        };
+        self.inner.post_skip_response(path)
+    }
+
+    /// Adds or overwrites voters snapshot for given tag
+    pub fn add_snapshot<S: Into<String>>(
+        &self,
+        tag: S,
+        snapshot: &[VoterHIR],
+    ) -> Result<(), Error> {
+        let tag: String = tag.into();
+        self.inner
+            .post_json(endpoint(SNAPSHOT_ADD, &[&tag]), snapshot)
+            .map(|_| ())
+    }
+
+    /// Generates voters snapshot, which can be uploaded with [`Self::add_snapshot`]
+    pub fn create_snapshot(&self, initials: &SnapshotInitials) -> Result<Vec<VoterHIR>, Error> {
+        let response = self.inner.post_json(SNAPSHOT_CREATE, initials)?;
+        serde_json::from_str(&response.text()?).map_err(Into::into)
     }
 
     pub fn is_up(&self) -> bool {
-        if let Ok(path) = self.inner.get("api/health") {
-            if let Ok(response) = reqwest::blocking::get(&path) {
-                return response.status() == reqwest::StatusCode::OK;
-            }
-        }
-        false
+        self.inner
+            .request(Method::GET, "api/health")
+            .send()
+            .map(|response| response.status() == reqwest::StatusCode::OK)
+            .unwrap_or(false)
     }
 }
 
@@ -178,6 +379,65 @@ impl VitupAdminRestClient {
         let text = self.inner.get("status")?;
         serde_json::from_str(&text).map_err(Into::into)
     }
+
+    /// Changes static data of running deployment, e.g. fund dates or proposal texts
+    pub fn update_data(&self, update: &DataUpdate) -> Result<String, Error> {
+        let path = self.inner.path("api/control/command/data/update");
+        println!("Calling: {}", path);
+        let client = reqwest::blocking::Client::new();
+        let response = client.put(&path).json(update).send()?;
+        Ok(response.text()?)
+    }
+
+    /// Schedules kill, restart, pause or isolation of node, vit station or wallet proxy
+    pub fn disrupt(&self, disruption: &Disruption) -> Result<String, Error> {
+        let path = self.inner.path("api/control/command/disruption");
+        println!("Calling: {}", path);
+        let client = reqwest::blocking::Client::new();
+        let response = client.post(&path).json(disruption).send()?;
+        Ok(response.text()?)
+    }
+
+    pub fn disruptions(&self) -> Result<DisruptionStatus, Error> {
+        serde_json::from_str(&self.inner.get("api/control/command/disruption")?).map_err(Into::into)
+    }
+
+    pub fn jobs(&self) -> Result<Vec<Job>, Error> {
+        serde_json::from_str(&self.inner.get("api/control/jobs")?).map_err(Into::into)
+    }
+
+    pub fn job(&self, id: Uuid) -> Result<Job, Error> {
+        serde_json::from_str(&self.inner.get(format!("api/control/jobs/{}", id))?)
+            .map_err(Into::into)
+    }
+
+    pub fn cancel_job(&self, id: Uuid) -> Result<JobStatus, Error> {
+        let text = self
+            .inner
+            .post(format!("api/control/jobs/{}/cancel", id))?
+            .text()?;
+        serde_json::from_str(&text).map_err(Into::into)
+    }
+
+    pub fn job_files(&self, id: Uuid) -> Result<FolderDump, Error> {
+        serde_json::from_str(
+            &self
+                .inner
+                .get(format!("api/control/jobs/{}/files/list", id))?,
+        )
+        .map_err(Into::into)
+    }
+
+    /// tar.gz archive with all logs of given job
+    pub fn job_logs(&self, id: Uuid) -> Result<Vec<u8>, Error> {
+        Ok(self
+            .inner
+            .request(Method::GET, format!("api/control/jobs/{}/logs", id))
+            .send()?
+            .error_for_status()?
+            .bytes()?
+            .to_vec())
+    }
 }
 
 #[derive(Error, Debug)]
@@ -186,4 +446,301 @@ pub enum Error {
     Reqwest(#[from] reqwest::Error),
     #[error(transparent)]
     Serde(#[from] serde_json::Error),
+    #[error("fragment cannot be updated with {0:?} strategy")]
+    UnsupportedFragmentStrategy(FragmentRecieveStrategy),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::args::VitupClientCommand;
+    use assert_fs::fixture::PathChild;
+    use assert_fs::prelude::FileWriteStr;
+    use assert_fs::TempDir;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use structopt::StructOpt;
+    use vit_servicing_station_tests::common::data::ArbitrarySnapshotGenerator;
+
+    /// Full paths of control routes declared in mock rest server, read from its
+    /// source. Nested `let root = warp::path!(.. / ..)` prefixes are scoped by braces
+    fn mock_control_routes() -> Vec<String> {
+        let source = include_str!("../mode/mock/rest/mod.rs");
+        let start = source.find("let control = {").unwrap();
+        let end = start + source[start..].find("let health =").unwrap();
+
+        let mut depth = 0usize;
+        let mut prefixes: Vec<(usize, Vec<String>)> = vec![(0, vec!["api".to_string()])];
+        let mut routes = Vec::new();
+
+        for line in source[start..end].lines() {
+            let args = line
+                .split("warp::path!(")
+                .nth(1)
+                .or_else(|| line.split("warp::path(").nth(1));
+            if let Some(args) = args {
+                let args = &args[..args.find(')').unwrap()];
+                let mut segments: Vec<String> = args
+                    .split('/')
+                    .map(str::trim)
+                    .map(|segment| match segment.strip_prefix('"') {
+                        Some(literal) => literal.trim_end_matches('"').to_string(),
+                        None => format!("{{{}}}", segment),
+                    })
+                    .collect();
+                let is_prefix = segments.last().map(String::as_str) == Some("{..}");
+                if is_prefix {
+                    segments.pop();
+                    prefixes.push((depth, segments));
+                } else {
+                    let mut route: Vec<String> = prefixes
+                        .iter()
+                        .flat_map(|(_, prefix)| prefix.clone())
+                        .collect();
+                    route.extend(segments);
+                    routes.push(route.join("/"));
+                }
+            }
+            depth += line.matches('{').count();
+            depth -= line.matches('}').count();
+            prefixes.retain(|(prefix_depth, _)| *prefix_depth <= depth);
+        }
+        routes
+    }
+
+    /// Local server answering every request with `200 OK` and a body accepted by client
+    /// method, which records paths of received requests
+    fn recording_server() -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let path = request_line
+                    .split(' ')
+                    .nth(1)
+                    .unwrap()
+                    .trim_start_matches('/');
+
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut request_body = vec![0; content_length];
+                reader.read_exact(&mut request_body).unwrap();
+
+                let body = if path == FILES_LIST {
+                    serde_json::to_string(&FolderDump::new(".")).unwrap()
+                } else if path == LOGS_GET || path == SNAPSHOT_CREATE {
+                    "[]".to_string()
+                } else {
+                    String::new()
+                };
+                recorded.lock().unwrap().push(path.to_string());
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        (address, requests)
+    }
+
+    /// Whether path was produced from endpoint template, `{type}` segments match anything
+    fn matches_endpoint(template: &str, path: &str) -> bool {
+        if template == FILES_GET {
+            return path.starts_with(&format!("{}/", FILES_GET));
+        }
+        let segments: Vec<&str> = path.split('/').collect();
+        let template: Vec<&str> = template.split('/').collect();
+        segments.len() == template.len()
+            && template
+                .iter()
+                .zip(segments)
+                .all(|(expected, segment)| expected.starts_with('{') || *expected == segment)
+    }
+
+    #[test]
+    fn vcli_commands_call_control_endpoints() {
+        let temp_dir = TempDir::new().unwrap();
+        let fund = temp_dir.child("fund.json");
+        fund.write_str(
+            &serde_json::to_string(&ArbitrarySnapshotGenerator::default().funds()[0]).unwrap(),
+        )
+        .unwrap();
+        let snapshot = temp_dir.child("snapshot.json");
+        snapshot.write_str("[]").unwrap();
+        let initials = temp_dir.child("initials.json");
+        initials
+            .write_str(&serde_json::to_string(&SnapshotInitials::default()).unwrap())
+            .unwrap();
+        let (fund, snapshot, initials) = (
+            fund.path().to_str().unwrap(),
+            snapshot.path().to_str().unwrap(),
+            initials.path().to_str().unwrap(),
+        );
+
+        // endpoint and arguments of `vcli disruption` command which calls it
+        let commands: Vec<(&str, Vec<&str>)> = vec![
+            (LOGS_GET, vec!["logs", "get"]),
+            (LOGS_CLEAR, vec!["logs", "clear"]),
+            (FILES_LIST, vec!["files", "list"]),
+            (FILES_GET, vec!["files", "get", "--path", "qr-codes/a.png"]),
+            (RESET, vec!["control", "reset"]),
+            (AVAILABLE, vec!["control", "set-unavailable"]),
+            (AVAILABLE, vec!["control", "set-available"]),
+            (
+                ERROR_CODE,
+                vec!["control", "set-error-code", "--code", "500"],
+            ),
+            (FUND_ID, vec!["control", "set-fund-id", "--fund-id", "9"]),
+            (FUND_UPDATE, vec!["control", "update-fund", "--fund", fund]),
+            (VERSION, vec!["control", "set-version", "--version", "3.6"]),
+            (
+                BLOCK_ACCOUNT,
+                vec!["control", "block-account", "set", "--calls", "2"],
+            ),
+            (
+                BLOCK_ACCOUNT_RESET,
+                vec!["control", "block-account", "reset"],
+            ),
+            (FRAGMENTS_REJECT, vec!["control", "fragments", "reject"]),
+            (FRAGMENTS_PENDING, vec!["control", "fragments", "hold"]),
+            (FRAGMENTS_ACCEPT, vec!["control", "fragments", "accept"]),
+            (FRAGMENTS_FORGET, vec!["control", "fragments", "forget"]),
+            (FRAGMENTS_RESET, vec!["control", "fragments", "reset"]),
+            (
+                FRAGMENT_REJECT,
+                vec!["control", "fragments", "update", "reject"],
+            ),
+            (
+                FRAGMENT_PENDING,
+                vec!["control", "fragments", "update", "hold"],
+            ),
+            (
+                FRAGMENT_ACCEPT,
+                vec!["control", "fragments", "update", "--id", "abc", "accept"],
+            ),
+            (
+                FRAGMENT_FORGET,
+                vec!["control", "fragments", "update", "forget"],
+            ),
+            (CONGESTION_NORMAL, vec!["control", "congestion", "normal"]),
+            (CONGESTION_JAMMED, vec!["control", "congestion", "jammed"]),
+            (
+                CONGESTION_MODERATE,
+                vec!["control", "congestion", "moderate"],
+            ),
+            (CONGESTION_RESET, vec!["control", "congestion", "reset"]),
+            (
+                SNAPSHOT_ADD,
+                vec![
+                    "control",
+                    "snapshot",
+                    "add",
+                    "--tag",
+                    "daily",
+                    "--snapshot",
+                    snapshot,
+                ],
+            ),
+            (
+                SNAPSHOT_CREATE,
+                vec!["control", "snapshot", "create", "--initials", initials],
+            ),
+        ];
+
+        let (address, requests) = recording_server();
+        for (expected, args) in &commands {
+            requests.lock().unwrap().clear();
+            VitupClientCommand::from_iter_safe(
+                ["vcli", "--endpoint", address.as_str(), "disruption"]
+                    .iter()
+                    .chain(args.iter()),
+            )
+            .unwrap()
+            .exec()
+            .unwrap();
+
+            let requests = requests.lock().unwrap();
+            assert_eq!(requests.len(), 1, "{:?}", args);
+            assert!(
+                matches_endpoint(expected, &requests[0]),
+                "`vcli disruption {}` called {} instead of {}",
+                args.join(" "),
+                requests[0],
+                expected
+            );
+        }
+
+        let not_exercised: Vec<&&str> = CONTROL_ENDPOINTS
+            .iter()
+            .filter(|endpoint| !commands.iter().any(|(command, _)| command == *endpoint))
+            .collect();
+        assert!(
+            not_exercised.is_empty(),
+            "control endpoints without vcli command: {:?}",
+            not_exercised
+        );
+    }
+
+    #[test]
+    fn client_covers_all_mock_control_endpoints() {
+        let routes = mock_control_routes();
+        assert!(
+            routes.contains(&"api/control/command/fragments/update/{String}/reject".to_string())
+        );
+
+        let uncovered: Vec<&String> = routes
+            .iter()
+            .filter(|route| !CONTROL_ENDPOINTS.contains(&route.as_str()))
+            .collect();
+        assert!(
+            uncovered.is_empty(),
+            "mock control endpoints not covered by client: {:?}",
+            uncovered
+        );
+
+        let unknown: Vec<&&str> = CONTROL_ENDPOINTS
+            .iter()
+            .filter(|endpoint| !routes.contains(&endpoint.to_string()))
+            .collect();
+        assert!(
+            unknown.is_empty(),
+            "client endpoints not served by mock: {:?}",
+            unknown
+        );
+    }
+
+    #[test]
+    fn endpoint_arguments_replace_placeholders_in_order() {
+        assert_eq!(
+            endpoint(FRAGMENT_REJECT, &["last"]),
+            "api/control/command/fragments/update/last/reject"
+        );
+        assert_eq!(
+            endpoint(ERROR_CODE, &["500"]),
+            "api/control/command/error-code/500"
+        );
+        assert_eq!(endpoint(FRAGMENTS_RESET, &[]), FRAGMENTS_RESET);
+    }
 }
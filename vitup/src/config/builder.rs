@@ -6,7 +6,7 @@ pub use crate::builders::{
 use crate::config::date_format;
 use crate::config::Block0Initials;
 use crate::config::SnapshotInitials;
-use crate::config::{Config, Initials, VoteTime};
+use crate::config::{Config, Initials, ReviewsProfile, Topology, VotePlanDefinition, VoteTime};
 use chain_addr::Discrimination;
 use chain_impl_mockchain::fee::LinearFee;
 use jormungandr_lib::interfaces::CommitteeIdDef;
@@ -155,6 +155,26 @@ impl ConfigBuilder {
         self
     }
 
+    pub fn vote_plan_definition(mut self, definition: VotePlanDefinition) -> Self {
+        self.config.vote_plan.definitions.push(definition);
+        self
+    }
+
+    pub fn topology(mut self, topology: Topology) -> Self {
+        self.config.topology = topology;
+        self
+    }
+
+    pub fn reviews(mut self, reviews: ReviewsProfile) -> Self {
+        self.config.reviews = reviews;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
     pub fn use_https(mut self) -> Self {
         self.config.service.https = true;
         self
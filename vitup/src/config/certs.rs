@@ -1,51 +1,176 @@
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, DistinguishedName, DnType, IsCa,
+    KeyUsagePurpose, SanType,
+};
+use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::Write;
+use std::net::{IpAddr, Ipv4Addr};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
+use time::{ext::NumericalDuration, OffsetDateTime};
 use valgrind::Certs;
 
+const CA_CRT: &str = "ca.crt";
 const SERVER_KEY: &str = "server.key";
 const SERVER_CRT: &str = "server.crt";
 
-pub struct CertificatesBuilder {
-    server_crt_content: String,
-    server_key_content: String,
+/// Tls settings for https deployments. Unless own certificate paths are provided,
+/// fresh CA and server certificate signed by it are generated for each deployment.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TlsSettings {
+    #[serde(default = "default_hostnames")]
+    pub hostnames: Vec<String>,
+    #[serde(default = "default_ips")]
+    pub ips: Vec<IpAddr>,
+    #[serde(default = "default_validity_days")]
+    pub validity_days: u32,
+    #[serde(default)]
+    pub cert_path: Option<PathBuf>,
+    #[serde(default)]
+    pub key_path: Option<PathBuf>,
+    #[serde(default)]
+    pub ca_path: Option<PathBuf>,
 }
 
-impl Default for CertificatesBuilder {
+impl Default for TlsSettings {
     fn default() -> Self {
         Self {
-            server_crt_content: include_str!("../../resources/tls/server.crt").to_string(),
-            server_key_content: include_str!("../../resources/tls/server.key").to_string(),
+            hostnames: default_hostnames(),
+            ips: default_ips(),
+            validity_days: default_validity_days(),
+            cert_path: None,
+            key_path: None,
+            ca_path: None,
         }
     }
 }
 
+fn default_hostnames() -> Vec<String> {
+    vec!["localhost".to_string()]
+}
+
+fn default_ips() -> Vec<IpAddr> {
+    vec![IpAddr::V4(Ipv4Addr::LOCALHOST)]
+}
+
+fn default_validity_days() -> u32 {
+    30
+}
+
+#[derive(Default)]
+pub struct CertificatesBuilder {
+    settings: TlsSettings,
+}
+
 impl CertificatesBuilder {
+    pub fn settings(mut self, settings: TlsSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
     pub fn build<P: AsRef<Path>>(self, working_dir: P) -> Result<Certs, Error> {
-        let mut working_dir = working_dir.as_ref().to_path_buf();
-        working_dir = working_dir.join("tls");
+        match (&self.settings.cert_path, &self.settings.key_path) {
+            (Some(cert_path), Some(key_path)) => self.existing(cert_path, key_path),
+            (None, None) => self.generate(working_dir),
+            _ => Err(Error::IncompleteCertsConfiguration),
+        }
+    }
+
+    fn existing(&self, cert_path: &Path, key_path: &Path) -> Result<Certs, Error> {
+        for path in [
+            Some(cert_path),
+            Some(key_path),
+            self.settings.ca_path.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if !path.exists() {
+                return Err(Error::CannotFindFile(path.to_path_buf()));
+            }
+        }
+
+        Ok(Certs {
+            key_path: key_path.to_path_buf(),
+            cert_path: cert_path.to_path_buf(),
+            ca_path: self.settings.ca_path.clone(),
+        })
+    }
+
+    fn generate<P: AsRef<Path>>(&self, working_dir: P) -> Result<Certs, Error> {
+        let working_dir = working_dir.as_ref().join("tls");
         std::fs::create_dir_all(&working_dir)?;
 
+        let not_before = OffsetDateTime::now_utc() - 1.hours();
+        let not_after = not_before + (self.settings.validity_days as i64).days();
+
+        let mut ca_params = CertificateParams::new(Vec::new());
+        ca_params.distinguished_name = distinguished_name("vitup CA");
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+        ca_params.not_before = not_before;
+        ca_params.not_after = not_after;
+        let ca = Certificate::from_params(ca_params)?;
+
+        let mut server_params = CertificateParams::new(self.settings.hostnames.clone());
+        server_params.distinguished_name = distinguished_name(
+            self.settings
+                .hostnames
+                .first()
+                .map(String::as_str)
+                .unwrap_or("vitup"),
+        );
+        server_params
+            .subject_alt_names
+            .extend(self.settings.ips.iter().cloned().map(SanType::IpAddress));
+        server_params.not_before = not_before;
+        server_params.not_after = not_after;
+        let server = Certificate::from_params(server_params)?;
+
+        let ca_path = working_dir.join(CA_CRT);
+        write_file(&ca_path, &ca.serialize_pem()?)?;
+
         let key_path = working_dir.join(SERVER_KEY);
-        let mut key_file =
-            File::create(&key_path).map_err(|_| Error::CannotCreateFile(key_path.clone()))?;
-        key_file
-            .write_all(self.server_key_content.as_bytes())
-            .map_err(|_| Error::CannotWriteToFile(key_path.clone()))?;
+        write_file(&key_path, &server.serialize_private_key_pem())?;
 
         let cert_path = working_dir.join(SERVER_CRT);
-        let mut server_file =
-            File::create(&cert_path).map_err(|_| Error::CannotWriteToFile(cert_path.clone()))?;
-        server_file.write_all(self.server_crt_content.as_bytes())?;
+        write_file(&cert_path, &server.serialize_pem_with_signer(&ca)?)?;
 
         Ok(Certs {
             key_path,
             cert_path,
+            ca_path: Some(ca_path),
         })
     }
 }
 
+/// Copies CA certificate (if any) to given folder, so it can be installed
+/// on devices which connect to deployment
+pub fn export_ca<P: AsRef<Path>>(certs: &Certs, folder: P) -> Result<Option<PathBuf>, Error> {
+    let ca_path = match &certs.ca_path {
+        Some(ca_path) => ca_path,
+        None => return Ok(None),
+    };
+    std::fs::create_dir_all(folder.as_ref())?;
+    let target = folder.as_ref().join(CA_CRT);
+    std::fs::copy(ca_path, &target).map_err(|_| Error::CannotWriteToFile(target.clone()))?;
+    Ok(Some(target))
+}
+
+fn distinguished_name(common_name: &str) -> DistinguishedName {
+    let mut distinguished_name = DistinguishedName::new();
+    distinguished_name.push(DnType::OrganizationName, "vitup");
+    distinguished_name.push(DnType::CommonName, common_name);
+    distinguished_name
+}
+
+fn write_file(path: &Path, content: &str) -> Result<(), Error> {
+    let mut file = File::create(path).map_err(|_| Error::CannotCreateFile(path.to_path_buf()))?;
+    file.write_all(content.as_bytes())
+        .map_err(|_| Error::CannotWriteToFile(path.to_path_buf()))
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Error)]
 pub enum Error {
@@ -53,6 +178,130 @@ pub enum Error {
     CannotCreateFile(PathBuf),
     #[error("cannot write file: {0}")]
     CannotWriteToFile(PathBuf),
+    #[error("cannot find file: {0}")]
+    CannotFindFile(PathBuf),
+    #[error("both cert_path and key_path need to be defined in order to use own certificates")]
+    IncompleteCertsConfiguration,
+    #[error(transparent)]
+    Generation(#[from] rcgen::RcgenError),
     #[error(transparent)]
     Io(#[from] std::io::Error),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+    use rustls::{
+        ClientConfig, ClientConnection, Connection, RootCertStore, ServerConfig, ServerConnection,
+        ServerName,
+    };
+    use std::convert::TryFrom;
+    use std::io::BufReader;
+    use std::sync::Arc;
+
+    fn read_pem(path: &Path) -> Vec<Vec<u8>> {
+        rustls_pemfile::certs(&mut BufReader::new(File::open(path).unwrap())).unwrap()
+    }
+
+    fn transfer(from: &mut Connection, to: &mut Connection) -> Result<(), rustls::Error> {
+        let mut buffer = Vec::new();
+        while from.wants_write() {
+            from.write_tls(&mut buffer).unwrap();
+        }
+        let mut pending = buffer.as_slice();
+        while !pending.is_empty() {
+            to.read_tls(&mut pending).unwrap();
+            to.process_new_packets()?;
+        }
+        Ok(())
+    }
+
+    /// In-memory handshake of client trusting only generated CA with server
+    /// presenting generated certificate
+    fn handshake(certs: &Certs, server_name: &str) -> Result<(), rustls::Error> {
+        let chain = read_pem(&certs.cert_path)
+            .into_iter()
+            .map(rustls::Certificate)
+            .collect();
+        let key = rustls_pemfile::pkcs8_private_keys(&mut BufReader::new(
+            File::open(&certs.key_path).unwrap(),
+        ))
+        .unwrap()
+        .remove(0);
+        let server_config = ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(chain, rustls::PrivateKey(key))?;
+
+        let mut roots = RootCertStore::empty();
+        roots.add_parsable_certificates(&read_pem(certs.ca_path.as_ref().unwrap()));
+        let client_config = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+
+        let mut client: Connection = ClientConnection::new(
+            Arc::new(client_config),
+            ServerName::try_from(server_name).unwrap(),
+        )?
+        .into();
+        let mut server: Connection = ServerConnection::new(Arc::new(server_config))?.into();
+        while client.is_handshaking() || server.is_handshaking() {
+            transfer(&mut client, &mut server)?;
+            transfer(&mut server, &mut client)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn generated_certificate_is_signed_by_generated_ca() {
+        let working_dir = TempDir::new().unwrap();
+        let settings = TlsSettings {
+            hostnames: vec!["localhost".to_string(), "vitup.local".to_string()],
+            ips: vec![IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3))],
+            ..Default::default()
+        };
+        let certs = CertificatesBuilder::default()
+            .settings(settings)
+            .build(working_dir.path())
+            .unwrap();
+
+        assert!(handshake(&certs, "localhost").is_ok());
+        assert!(handshake(&certs, "vitup.local").is_ok());
+        assert!(handshake(&certs, "other.local").is_err());
+
+        // iPAddress general name: context tag [7], 4 bytes of 10.1.2.3
+        let leaf = read_pem(&certs.cert_path).remove(0);
+        assert!(leaf
+            .windows(6)
+            .any(|window| window == [0x87, 0x04, 10, 1, 2, 3]));
+    }
+
+    #[test]
+    fn certificate_from_other_ca_is_rejected() {
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+        let mut certs = CertificatesBuilder::default().build(first.path()).unwrap();
+        certs.ca_path = CertificatesBuilder::default()
+            .build(second.path())
+            .unwrap()
+            .ca_path;
+
+        assert!(handshake(&certs, "localhost").is_err());
+    }
+
+    #[test]
+    fn own_certificates_require_both_cert_and_key() {
+        let settings = TlsSettings {
+            cert_path: Some(PathBuf::from("server.crt")),
+            ..Default::default()
+        };
+        assert!(matches!(
+            CertificatesBuilder::default()
+                .settings(settings)
+                .build(TempDir::new().unwrap().path()),
+            Err(Error::IncompleteCertsConfiguration)
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::config::initials::Role;
+use crate::config::initials::{Distribution, DistributionError, Role};
 use chain_addr::AddressReadable;
 use chain_addr::Discrimination;
 use chain_impl_mockchain::value::Value;
@@ -7,7 +7,7 @@ use fake::Fake;
 use hersir::config::{WalletTemplate, WalletTemplateBuilder};
 use jormungandr_lib::interfaces::InitialUTxO;
 use jormungandr_lib::interfaces::TokenIdentifier;
-use rand::Rng;
+use rand::{Rng, RngCore};
 use serde::{Deserialize, Serialize};
 use snapshot_lib::VoterHIR;
 use std::collections::HashMap;
@@ -38,6 +38,12 @@ pub enum Initial {
         #[serde(default)]
         role: Role,
     },
+    Distributed {
+        distribution: Distribution,
+        pin: String,
+        #[serde(default)]
+        role: Role,
+    },
     ZeroFunds {
         zero_funds: usize,
         pin: String,
@@ -60,9 +66,9 @@ pub enum Initial {
 }
 
 impl Initial {
-    pub fn new_random_wallet(funds: u64) -> Self {
+    pub fn new_random_wallet<R: RngCore>(funds: u64, rng: &mut R) -> Self {
         Self::Wallet {
-            name: Name().fake::<String>(),
+            name: Name().fake_with_rng::<String, _>(rng),
             funds,
             pin: "1234".to_string(),
             role: Default::default(),
@@ -150,6 +156,7 @@ impl Initials {
                     pin: _,
                     role: _,
                 } => sum += count,
+                Initial::Distributed { distribution, .. } => sum += distribution.count,
                 _ => {}
             }
         }
@@ -226,16 +233,17 @@ impl Initials {
         templates
     }
 
-    pub fn templates(
+    pub fn templates<R: RngCore>(
         &self,
         threshold: u64,
         discrimination: Discrimination,
         roles: impl Fn(&Role) -> TokenIdentifier,
-    ) -> HashMap<WalletTemplate, String> {
-        let mut rand = rand::thread_rng();
+        rand: &mut R,
+    ) -> Result<HashMap<WalletTemplate, String>, DistributionError> {
         let mut above_threshold_index = 0;
         let mut below_threshold_index = 0;
         let mut around_level_index = 0;
+        let mut distributed_index = 0;
         let mut templates = HashMap::new();
 
         for initial in self.0.iter() {
@@ -319,11 +327,74 @@ impl Initials {
                         );
                     }
                 }
+                Initial::Distributed {
+                    distribution,
+                    pin,
+                    role,
+                } => {
+                    for value in distribution.sample(rand)? {
+                        distributed_index += 1;
+                        let wallet_alias = format!("wallet_{}_distributed", distributed_index);
+                        templates.insert(
+                            WalletTemplateBuilder::new(&wallet_alias)
+                                .with(value)
+                                .discrimination(discrimination)
+                                .with_token(roles(role), value)
+                                .build(),
+                            pin.to_string(),
+                        );
+                    }
+                }
                 _ => {
                     //skip
                 }
             }
         }
-        templates
+        Ok(templates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::initials::DistributionShape;
+    use chain_impl_mockchain::tokens::identifier::TokenIdentifier as TokenIdentifierLib;
+    use chain_impl_mockchain::tokens::minting_policy::MintingPolicy;
+    use chain_impl_mockchain::tokens::name::TokenName;
+
+    #[test]
+    fn distributed_wallets_have_funds_within_bounds() {
+        let initials = Initials(vec![Initial::Distributed {
+            distribution: Distribution {
+                count: 50,
+                min: 1_000,
+                max: 10_000,
+                seed: Some(1),
+                shape: DistributionShape::LogNormal {
+                    mu: 8.0,
+                    sigma: 2.0,
+                },
+            },
+            pin: "1234".to_string(),
+            role: Role::Voter,
+        }]);
+        let token: TokenIdentifier = TokenIdentifierLib {
+            policy_hash: MintingPolicy::new().hash(),
+            token_name: TokenName::try_from(vec![0u8; 4]).unwrap(),
+        }
+        .into();
+
+        let templates = initials
+            .templates(
+                8_000,
+                Discrimination::Production,
+                |_| token.clone(),
+                &mut rand::thread_rng(),
+            )
+            .unwrap();
+        assert_eq!(templates.len(), 50);
+        assert!(templates
+            .keys()
+            .all(|template| (1_000..=10_000).contains(&template.value().0)));
     }
 }
@@ -0,0 +1,275 @@
+use crate::config::initials::Role;
+use chain_crypto::{Ed25519, SecretKey};
+use chain_impl_mockchain::account::Identifier as AccountIdentifier;
+use hersir::builder::Wallet as WalletSettings;
+use jormungandr_lib::crypto::account::Identifier;
+use rand_chacha::ChaChaRng;
+use rand_core::SeedableRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use snapshot_lib::registration::{Delegations, VotingRegistration};
+use snapshot_lib::VoterHIR;
+use std::collections::{BTreeMap, HashSet};
+use thiserror::Error;
+use thor::WalletAlias;
+
+/// Voters delegating their voting power to representatives (CIP-36), e.g.:
+///
+/// ```yaml
+/// delegation:
+///   representatives:
+///     - name: alice
+///       funds: 100
+///     - name: clarice
+///   delegators:
+///     - name: bob
+///       funds: 10000
+///       direct: 1000
+///       delegations:
+///         - { representative: alice, weight: 1 }
+///         - { representative: clarice, weight: 3 }
+/// ```
+///
+/// Participants named after block0 wallets (`wallet_{name}`) use wallet keys,
+/// others get keys derived from their names.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Delegation {
+    pub representatives: Vec<Representative>,
+    #[serde(default)]
+    pub delegators: Vec<Delegator>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Representative {
+    pub name: String,
+    /// representative own stake registered directly
+    #[serde(default)]
+    pub funds: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Delegator {
+    pub name: String,
+    pub funds: u64,
+    /// part of funds which is not delegated and votes directly
+    #[serde(default)]
+    pub direct: u64,
+    #[serde(default)]
+    pub delegations: Vec<DelegationWeight>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegationWeight {
+    pub representative: String,
+    pub weight: u32,
+}
+
+impl Delegation {
+    /// Voter registrations as seen on mainnet, in format accepted by mainnet-tools mocks
+    pub fn registrations(
+        &self,
+        defined_wallets: &[(WalletAlias, &WalletSettings)],
+    ) -> Result<Vec<VotingRegistration>, Error> {
+        let mut registrations = Vec::new();
+
+        for representative in &self.representatives {
+            if representative.funds > 0 {
+                registrations.push(registration(
+                    &representative.name,
+                    "direct",
+                    representative.funds,
+                    Delegations::Legacy(voting_key(&representative.name, defined_wallets)),
+                ));
+            }
+        }
+
+        for delegator in &self.delegators {
+            if delegator.direct > delegator.funds {
+                return Err(Error::DirectExceedsFunds(delegator.name.clone()));
+            }
+            let delegated = delegator.funds - delegator.direct;
+
+            if delegated > 0 {
+                if delegator.delegations.iter().all(|d| d.weight == 0) {
+                    return Err(Error::NoDelegations(delegator.name.clone()));
+                }
+                let mut weights = Vec::new();
+                for delegation in &delegator.delegations {
+                    if !self
+                        .representatives
+                        .iter()
+                        .any(|r| r.name == delegation.representative)
+                    {
+                        return Err(Error::UnknownRepresentative {
+                            delegator: delegator.name.clone(),
+                            representative: delegation.representative.clone(),
+                        });
+                    }
+                    weights.push((
+                        voting_key(&delegation.representative, defined_wallets),
+                        delegation.weight,
+                    ));
+                }
+                registrations.push(registration(
+                    &delegator.name,
+                    "delegated",
+                    delegated,
+                    Delegations::New(weights),
+                ));
+            }
+
+            if delegator.direct > 0 {
+                registrations.push(registration(
+                    &delegator.name,
+                    "direct",
+                    delegator.direct,
+                    Delegations::Legacy(voting_key(&delegator.name, defined_wallets)),
+                ));
+            }
+        }
+        Ok(registrations)
+    }
+
+    /// Voting power per voting key and group, calculated from registrations the same way
+    /// as snapshot does: delegated power is split by weights and remainder goes to last one
+    pub fn voter_hirs(
+        &self,
+        defined_wallets: &[(WalletAlias, &WalletSettings)],
+    ) -> Result<Vec<VoterHIR>, Error> {
+        let representatives: HashSet<Identifier> = self
+            .representatives
+            .iter()
+            .map(|r| voting_key(&r.name, defined_wallets))
+            .collect();
+
+        let mut powers: BTreeMap<String, (Identifier, u64)> = BTreeMap::new();
+        let mut add = |key: &Identifier, power: u64| {
+            powers
+                .entry(key.to_hex())
+                .or_insert_with(|| (key.clone(), 0))
+                .1 += power;
+        };
+
+        for registration in self.registrations(defined_wallets)? {
+            let power: u64 = registration.voting_power.into();
+            match &registration.delegations {
+                Delegations::Legacy(key) => add(key, power),
+                Delegations::New(weights) => {
+                    let total: u64 = weights.iter().map(|(_, weight)| *weight as u64).sum();
+                    let mut remaining = power;
+                    for (idx, (key, weight)) in weights.iter().enumerate() {
+                        let share = if idx == weights.len() - 1 {
+                            remaining
+                        } else {
+                            power * *weight as u64 / total
+                        };
+                        remaining -= share;
+                        add(key, share);
+                    }
+                }
+            }
+        }
+
+        Ok(powers
+            .into_values()
+            .filter(|(_, power)| *power > 0)
+            .map(|(key, power)| VoterHIR {
+                voting_group: if representatives.contains(&key) {
+                    Role::Representative.to_string()
+                } else {
+                    Role::Voter.to_string()
+                },
+                voting_key: key,
+                voting_power: power.into(),
+            })
+            .collect())
+    }
+}
+
+fn registration(
+    name: &str,
+    purpose: &str,
+    power: u64,
+    delegations: Delegations,
+) -> VotingRegistration {
+    VotingRegistration {
+        stake_public_key: format!(
+            "0x{}",
+            derived_key(name, &format!("stake_{}", purpose)).to_hex()
+        ),
+        reward_address: format!("0x{}", derived_key(name, "reward").to_hex()),
+        voting_power: power.into(),
+        delegations,
+        voting_purpose: 0,
+    }
+}
+
+fn voting_key(name: &str, defined_wallets: &[(WalletAlias, &WalletSettings)]) -> Identifier {
+    defined_wallets
+        .iter()
+        .find(|(alias, _)| alias.to_lowercase() == format!("wallet_{}", name).to_lowercase())
+        .map(|(_, wallet)| Identifier::from(wallet.identifier()))
+        .unwrap_or_else(|| derived_key(name, "voting"))
+}
+
+/// Key which is stable across runs, so registrations and voting power always match
+fn derived_key(name: &str, purpose: &str) -> Identifier {
+    let mut seed = [0u8; 32];
+    seed.copy_from_slice(&Sha256::digest(format!("{}:{}", purpose, name).as_bytes()));
+    let mut rng = ChaChaRng::from_seed(seed);
+    AccountIdentifier::from(SecretKey::<Ed25519>::generate(&mut rng).to_public()).into()
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("delegator '{delegator}' delegates to undefined representative '{representative}'")]
+    UnknownRepresentative {
+        delegator: String,
+        representative: String,
+    },
+    #[error("delegator '{0}' has funds to delegate, but no delegation with positive weight")]
+    NoDelegations(String),
+    #[error("delegator '{0}' direct voting power exceeds its funds")]
+    DirectExceedsFunds(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delegated_power_is_split_by_weights() {
+        let delegation: Delegation = serde_yaml::from_str(
+            r#"
+representatives:
+  - name: alice
+    funds: 100
+  - name: clarice
+delegators:
+  - name: bob
+    funds: 1000
+    direct: 1
+    delegations:
+      - { representative: alice, weight: 1 }
+      - { representative: clarice, weight: 2 }
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(delegation.registrations(&[]).unwrap().len(), 3);
+
+        let power = |name: &str| {
+            let key = voting_key(name, &[]);
+            delegation
+                .voter_hirs(&[])
+                .unwrap()
+                .into_iter()
+                .find(|hir| hir.voting_key == key)
+                .map(|hir| (hir.voting_group, u64::from(hir.voting_power)))
+                .unwrap()
+        };
+        assert_eq!(power("alice"), ("dreps".to_string(), 100 + 333));
+        assert_eq!(power("clarice"), ("dreps".to_string(), 666));
+        assert_eq!(power("bob"), ("direct".to_string(), 1));
+    }
+}
@@ -0,0 +1,317 @@
+use rand::Rng;
+use rand_chacha::ChaChaRng;
+use rand_core::{RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+use snapshot_lib::VoterHIR;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Voting power distribution of `count` wallets or voters, e.g.:
+///
+/// ```yaml
+/// distribution:
+///   count: 1000
+///   min: 500
+///   max: 50000000
+///   seed: 42
+///   shape:
+///     log_normal: { mu: 8.0, sigma: 2.5 }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Distribution {
+    pub count: usize,
+    pub min: u64,
+    pub max: u64,
+    /// if not provided, values are drawn from configuration random stream
+    #[serde(default)]
+    pub seed: Option<u64>,
+    pub shape: Shape,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Shape {
+    /// natural logarithm of value is normally distributed with given mean and deviation
+    LogNormal { mu: f64, sigma: f64 },
+    /// heavy tail with given index, scaled so that smallest value is `min`
+    Pareto { alpha: f64 },
+    /// rank-size law, k-th largest holder gets about `max / k^exponent`, so values follow
+    /// power law with index `1 / exponent`
+    Zipf { exponent: f64 },
+    /// log-scale histogram of voting power from real snapshot file (json list of voters)
+    Histogram {
+        file: PathBuf,
+        #[serde(default = "default_buckets")]
+        buckets: usize,
+    },
+}
+
+/// draws per value before giving up on distribution which barely overlaps bounds
+const MAX_ATTEMPTS: usize = 10_000;
+
+fn default_buckets() -> usize {
+    20
+}
+
+impl Distribution {
+    /// Voting powers within `[min, max]` bounds, one for each of `count` entries
+    pub fn sample<R: RngCore>(&self, rng: &mut R) -> Result<Vec<u64>, Error> {
+        if self.min == 0 || self.min > self.max {
+            return Err(Error::InvalidBounds {
+                min: self.min,
+                max: self.max,
+            });
+        }
+
+        let mut rng = match self.seed {
+            Some(seed) => ChaChaRng::seed_from_u64(seed),
+            None => ChaChaRng::from_rng(rng).unwrap(),
+        };
+
+        match &self.shape {
+            Shape::LogNormal { mu, sigma } => {
+                if *sigma < 0.0 {
+                    return Err(Error::InvalidParameter("sigma should not be negative"));
+                }
+                self.sample_within(&mut rng, |rng| (mu + sigma * standard_normal(rng)).exp())
+            }
+            Shape::Pareto { alpha } => {
+                if *alpha <= 0.0 {
+                    return Err(Error::InvalidParameter("alpha should be positive"));
+                }
+                self.sample_within(&mut rng, |rng| pareto(self.min, *alpha, rng))
+            }
+            Shape::Zipf { exponent } => {
+                if *exponent <= 0.0 {
+                    return Err(Error::InvalidParameter("exponent should be positive"));
+                }
+                // rank-size law `max / k^exponent` gives power law tail of index `1 / exponent`
+                // starting at lowest allowed value
+                self.sample_within(&mut rng, |rng| pareto(self.min, 1.0 / exponent, rng))
+            }
+            Shape::Histogram { file, buckets } => {
+                let histogram = Histogram::from_snapshot(file, *buckets)?;
+                self.sample_within(&mut rng, |rng| histogram.sample(rng))
+            }
+        }
+    }
+
+    /// Rejection sampling: values outside of `[min, max]` are drawn again, so distribution
+    /// is truncated instead of piling up clamped values on bounds
+    fn sample_within<R: Rng, F: FnMut(&mut R) -> f64>(
+        &self,
+        rng: &mut R,
+        mut draw: F,
+    ) -> Result<Vec<u64>, Error> {
+        (0..self.count)
+            .map(|_| {
+                (0..MAX_ATTEMPTS)
+                    .map(|_| draw(rng).round())
+                    .find(|value| *value >= self.min as f64 && *value <= self.max as f64)
+                    .map(|value| value as u64)
+                    .ok_or(Error::OutOfBounds {
+                        min: self.min,
+                        max: self.max,
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Value from (0, 1], so it is safe to take logarithm or divide by it
+fn open_unit<R: Rng>(rng: &mut R) -> f64 {
+    1.0 - rng.gen::<f64>()
+}
+
+/// Pareto distributed value not lower than `scale`
+fn pareto<R: Rng>(scale: u64, alpha: f64, rng: &mut R) -> f64 {
+    scale as f64 / open_unit(rng).powf(1.0 / alpha)
+}
+
+/// Box-Muller transform
+fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    let radius = (-2.0 * open_unit(rng).ln()).sqrt();
+    radius * (2.0 * std::f64::consts::PI * rng.gen::<f64>()).cos()
+}
+
+struct Histogram {
+    /// bucket edges in log scale
+    min: f64,
+    width: f64,
+    counts: Vec<usize>,
+}
+
+impl Histogram {
+    fn from_snapshot(file: &Path, buckets: usize) -> Result<Self, Error> {
+        if buckets == 0 {
+            return Err(Error::InvalidParameter("buckets should be positive"));
+        }
+        let content = std::fs::read_to_string(file)?;
+        let voters: Vec<VoterHIR> = serde_json::from_str(&content)?;
+        let powers: Vec<f64> = voters
+            .into_iter()
+            .map(|voter| u64::from(voter.voting_power))
+            .filter(|power| *power > 0)
+            .map(|power| (power as f64).ln())
+            .collect();
+
+        if powers.is_empty() {
+            return Err(Error::EmptyHistogram(file.to_path_buf()));
+        }
+
+        let min = powers.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = powers.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let width = (max - min) / buckets as f64;
+        let mut counts = vec![0; buckets];
+        for power in powers {
+            let bucket = if width > 0.0 {
+                (((power - min) / width) as usize).min(buckets - 1)
+            } else {
+                0
+            };
+            counts[bucket] += 1;
+        }
+        Ok(Self { min, width, counts })
+    }
+
+    fn sample<R: Rng>(&self, rng: &mut R) -> f64 {
+        let total: usize = self.counts.iter().sum();
+        let mut pick = rng.gen_range(0..total);
+        let bucket = self
+            .counts
+            .iter()
+            .position(|count| {
+                if pick < *count {
+                    true
+                } else {
+                    pick -= count;
+                    false
+                }
+            })
+            .unwrap();
+        (self.min + self.width * (bucket as f64 + rng.gen::<f64>())).exp()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("invalid distribution bounds: min ({min}) should be positive and not greater than max ({max})")]
+    InvalidBounds { min: u64, max: u64 },
+    #[error("distribution shape has too little mass between min ({min}) and max ({max})")]
+    OutOfBounds { min: u64, max: u64 },
+    #[error("invalid distribution parameter: {0}")]
+    InvalidParameter(&'static str),
+    #[error("snapshot file {0:?} does not contain any voting power")]
+    EmptyHistogram(PathBuf),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+    use chain_crypto::{Ed25519, SecretKey};
+    use chain_impl_mockchain::account::Identifier as AccountIdentifier;
+
+    fn distribution(shape: Shape) -> Distribution {
+        Distribution {
+            count: 1000,
+            min: 100,
+            max: 1_000_000,
+            seed: Some(7),
+            shape,
+        }
+    }
+
+    #[test]
+    fn distributions_are_skewed_and_bounded() {
+        let mut rng = rand::thread_rng();
+        for shape in [
+            Shape::LogNormal {
+                mu: 8.0,
+                sigma: 2.0,
+            },
+            Shape::Pareto { alpha: 1.2 },
+            Shape::Zipf { exponent: 1.0 },
+        ] {
+            let distribution = distribution(shape);
+            let mut values = distribution.sample(&mut rng).unwrap();
+            // seed makes distribution reproducible regardless of given generator
+            assert_eq!(values, distribution.sample(&mut rng).unwrap());
+            assert_eq!(values.len(), 1000);
+            assert!(values.iter().all(|v| (100..=1_000_000).contains(v)));
+
+            values.sort_unstable();
+            let mean = values.iter().sum::<u64>() / values.len() as u64;
+            assert!(mean > values[values.len() / 2], "{:?}", distribution.shape);
+        }
+
+        let mut invalid = distribution(Shape::Zipf { exponent: 1.0 });
+        invalid.min = 0;
+        assert!(matches!(
+            invalid.sample(&mut rng),
+            Err(Error::InvalidBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn values_outside_bounds_are_drawn_again() {
+        let mut distribution = distribution(Shape::LogNormal {
+            mu: 8.0,
+            sigma: 2.0,
+        });
+        distribution.min = 1_000;
+        distribution.max = 10_000;
+
+        let values = distribution.sample(&mut rand::thread_rng()).unwrap();
+        assert!(values.iter().all(|v| (1_000..=10_000).contains(v)));
+        // clamping would put more than half of values on bounds
+        let on_bounds = values
+            .iter()
+            .filter(|v| **v == distribution.min || **v == distribution.max)
+            .count();
+        assert!(on_bounds < 10, "{} values on bounds", on_bounds);
+    }
+
+    fn voter(voting_power: u64) -> VoterHIR {
+        VoterHIR {
+            voting_key: AccountIdentifier::from(
+                SecretKey::<Ed25519>::generate(rand::thread_rng()).to_public(),
+            )
+            .into(),
+            voting_group: "direct".to_string(),
+            voting_power: voting_power.into(),
+        }
+    }
+
+    #[test]
+    fn histogram_replays_bucket_proportions() {
+        let directory = TempDir::new().unwrap();
+        let snapshot = directory.path().join("snapshot.json");
+        let voters: Vec<VoterHIR> = (0..100)
+            .map(|index| voter(if index < 90 { 1_000 } else { 1_000_000 }))
+            .collect();
+        std::fs::write(&snapshot, serde_json::to_string(&voters).unwrap()).unwrap();
+
+        let mut distribution = distribution(Shape::Histogram {
+            file: snapshot,
+            buckets: 2,
+        });
+        distribution.count = 10_000;
+        distribution.min = 1;
+        distribution.max = 10_000_000;
+
+        let values = distribution.sample(&mut rand::thread_rng()).unwrap();
+        assert!(values.iter().all(|v| (1_000..=1_000_000).contains(v)));
+        // two log-scale buckets are split at geometric mean of 1_000 and 1_000_000
+        let lower_bucket = values.iter().filter(|v| **v < 31_623).count();
+        assert!(
+            (8_800..=9_200).contains(&lower_bucket),
+            "{} values in lower bucket",
+            lower_bucket
+        );
+    }
+}
@@ -1,7 +1,13 @@
 mod block0;
+mod delegation;
+mod distribution;
 mod snapshot;
 
 pub use block0::{Initial as Block0Initial, Initials as Block0Initials};
+pub use delegation::{
+    Delegation, DelegationWeight, Delegator, Error as DelegationError, Representative,
+};
+pub use distribution::{Distribution, Error as DistributionError, Shape as DistributionShape};
 use serde::{Deserialize, Serialize};
 pub use snapshot::{
     Error as SnapshotError, Initial as SnapshotInitial, Initials as SnapshotInitials,
@@ -1,8 +1,12 @@
-use crate::config::initials::Role;
-use chain_crypto::PublicKeyFromStrError;
+use crate::config::initials::delegation::{Delegation, Error as DelegationError};
+use crate::config::initials::{Distribution, DistributionError, Role};
+use chain_crypto::{Ed25519, PublicKeyFromStrError, SecretKey};
+use chain_impl_mockchain::account::Identifier as AccountIdentifier;
 use hersir::builder::Wallet as WalletSettings;
 use jormungandr_lib::crypto::account::Identifier;
+use rand::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
+use snapshot_lib::registration::VotingRegistration;
 use snapshot_lib::VoterHIR;
 use std::str::FromStr;
 use thor::{Wallet, WalletAlias};
@@ -11,6 +15,9 @@ use thor::{Wallet, WalletAlias};
 pub struct Initials {
     pub tag: String,
     pub content: Vec<Initial>,
+    /// representatives and voters delegating to them
+    #[serde(default)]
+    pub delegation: Option<Delegation>,
 }
 
 impl Default for Initials {
@@ -18,6 +25,7 @@ impl Default for Initials {
         Self {
             tag: "".to_string(),
             content: Vec::new(),
+            delegation: None,
         }
     }
 }
@@ -34,12 +42,25 @@ impl Initials {
                     role: Role::from_str(&hir.voting_group).unwrap(),
                 })
                 .collect(),
+            delegation: None,
         }
     }
 
-    pub fn as_voters_hirs(
+    /// Registrations of delegation participants, empty if no delegation is defined
+    pub fn registrations(
+        &self,
+        defined_wallets: &[(WalletAlias, &WalletSettings)],
+    ) -> Result<Vec<VotingRegistration>, Error> {
+        match &self.delegation {
+            Some(delegation) => Ok(delegation.registrations(defined_wallets)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub fn as_voters_hirs<R: RngCore + CryptoRng>(
         &self,
         defined_wallets: Vec<(WalletAlias, &WalletSettings)>,
+        rng: &mut R,
     ) -> Result<Vec<VoterHIR>, Error> {
         let mut voter_hirs = Vec::new();
 
@@ -48,12 +69,27 @@ impl Initials {
                 Initial::Random { count, level, role } => {
                     for _ in 0..*count {
                         voter_hirs.push(VoterHIR {
-                            voting_key: Wallet::default().account_id(),
+                            voting_key: AccountIdentifier::from(
+                                SecretKey::<Ed25519>::generate(&mut *rng).to_public(),
+                            )
+                            .into(),
                             voting_group: role.to_string(),
                             voting_power: (*level).into(),
                         });
                     }
                 }
+                Initial::Distributed { distribution, role } => {
+                    for value in distribution.sample(&mut *rng)? {
+                        voter_hirs.push(VoterHIR {
+                            voting_key: AccountIdentifier::from(
+                                SecretKey::<Ed25519>::generate(&mut *rng).to_public(),
+                            )
+                            .into(),
+                            voting_group: role.to_string(),
+                            voting_power: value.into(),
+                        });
+                    }
+                }
                 Initial::Wallet { name, funds, role } => {
                     let wallet = defined_wallets
                         .iter()
@@ -93,6 +129,9 @@ impl Initials {
                 }
             }
         }
+        if let Some(delegation) = &self.delegation {
+            voter_hirs.extend(delegation.voter_hirs(&defined_wallets)?);
+        }
         Ok(voter_hirs)
     }
 }
@@ -106,6 +145,11 @@ pub enum Initial {
         #[serde(default)]
         role: Role,
     },
+    Distributed {
+        distribution: Distribution,
+        #[serde(default)]
+        role: Role,
+    },
     WalletAutoFunds {
         name: String,
         #[serde(default)]
@@ -131,4 +175,40 @@ pub enum Error {
     CannotFindAlias(String),
     #[error(transparent)]
     PublicKey(#[from] PublicKeyFromStrError),
+    #[error(transparent)]
+    Distribution(#[from] DistributionError),
+    #[error(transparent)]
+    Delegation(#[from] DelegationError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::initials::DistributionShape;
+
+    #[test]
+    fn distributed_voters_have_voting_power_within_bounds() {
+        let initials = Initials {
+            content: vec![Initial::Distributed {
+                distribution: Distribution {
+                    count: 50,
+                    min: 500,
+                    max: 5_000,
+                    seed: Some(1),
+                    shape: DistributionShape::Pareto { alpha: 1.5 },
+                },
+                role: Role::Representative,
+            }],
+            ..Default::default()
+        };
+
+        let voters = initials
+            .as_voters_hirs(Vec::new(), &mut rand::thread_rng())
+            .unwrap();
+        assert_eq!(voters.len(), 50);
+        assert!(voters.iter().all(|voter| {
+            voter.voting_group == Role::Representative.to_string()
+                && (500..=5_000).contains(&u64::from(voter.voting_power))
+        }));
+    }
 }
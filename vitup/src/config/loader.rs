@@ -0,0 +1,381 @@
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+
+const EXTENDS: &str = "extends";
+
+/// Format of configuration file, recognized by file extension.
+/// Files without known extension are treated as json for backward compatibility.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl ConfigFormat {
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        match path
+            .as_ref()
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .as_deref()
+        {
+            Some("yaml") | Some("yml") => Self::Yaml,
+            Some("toml") => Self::Toml,
+            _ => Self::Json,
+        }
+    }
+
+    pub fn parse(&self, contents: &str) -> Result<Value, Error> {
+        match self {
+            Self::Json => serde_json::from_str(contents).map_err(Into::into),
+            Self::Yaml => serde_yaml::from_str(contents).map_err(Into::into),
+            Self::Toml => toml::from_str(contents).map_err(Into::into),
+        }
+    }
+
+    pub fn format<T: serde::Serialize>(&self, value: &T) -> Result<String, Error> {
+        match self {
+            Self::Json => serde_json::to_string_pretty(value).map_err(Into::into),
+            Self::Yaml => serde_yaml::to_string(value).map_err(Into::into),
+            Self::Toml => {
+                toml::to_string_pretty(&toml::Value::try_from(value)?).map_err(Into::into)
+            }
+        }
+    }
+}
+
+impl FromStr for ConfigFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            "toml" => Ok(Self::Toml),
+            other => Err(Error::UnknownFormat(other.to_string())),
+        }
+    }
+}
+
+/// Single `--set key.path=value` override applied on top of loaded configuration.
+/// Value takes type of the field it replaces (e.g. `data.current_fund.proposals=500`,
+/// `vote_plan.private=true`). Fields missing in configuration get value as plain string,
+/// unless it is quoted, list or map (`[1, 2]`), or typed with yaml tag (`!!int 500`,
+/// `!!float 0.5`, `!!bool true`, `!!str 3.6`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigOverride {
+    path: Vec<String>,
+    raw: String,
+    value: Value,
+    /// value type given by user, not inferred from replaced field
+    explicit: bool,
+}
+
+impl ConfigOverride {
+    pub fn new<S: Into<String>>(path: S, value: Value) -> Self {
+        Self {
+            path: path.into().split('.').map(ToString::to_string).collect(),
+            raw: value
+                .as_str()
+                .map(ToString::to_string)
+                .unwrap_or_else(|| value.to_string()),
+            value,
+            explicit: true,
+        }
+    }
+
+    pub fn path(&self) -> String {
+        self.path.join(".")
+    }
+
+    pub fn apply(&self, root: &mut Value) -> Result<(), Error> {
+        let mut current = root;
+        for segment in &self.path {
+            if current.is_null() {
+                *current = Value::Object(Map::new());
+            }
+            current = match current {
+                Value::Array(items) => segment
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(move |index| items.get_mut(index))
+                    .ok_or_else(|| Error::InvalidOverridePath(self.path()))?,
+                Value::Object(map) => map.entry(segment.clone()).or_insert(Value::Null),
+                _ => return Err(Error::InvalidOverridePath(self.path())),
+            };
+        }
+        // keep string and missing fields as typed by the user, e.g. `service.version=3.6`
+        *current = if self.explicit || !(current.is_string() || current.is_null()) {
+            self.value.clone()
+        } else {
+            Value::String(self.raw.clone())
+        };
+        Ok(())
+    }
+}
+
+/// Value with type stated by the user: quoted string, flow list or map, or yaml tagged scalar
+fn explicit_value(raw: &str) -> Option<Result<Value, serde_yaml::Error>> {
+    let raw = raw.trim();
+    if let Some((tag, scalar)) = raw.strip_prefix("!!").and_then(|raw| raw.split_once(' ')) {
+        let scalar = scalar.trim();
+        if tag == "str" {
+            return Some(Ok(Value::String(scalar.to_string())));
+        }
+        return Some(
+            serde_yaml::from_str(scalar).map(|value: Value| match (tag, &value) {
+                ("int", Value::Number(number)) if !number.is_f64() => value,
+                ("float", Value::Number(_)) | ("bool", Value::Bool(_)) => value,
+                _ => Value::Null,
+            }),
+        );
+    }
+    if raw.starts_with(&['"', '\'', '[', '{'][..]) {
+        return Some(serde_yaml::from_str(raw));
+    }
+    None
+}
+
+impl FromStr for ConfigOverride {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (path, raw_value) = s
+            .split_once('=')
+            .ok_or_else(|| Error::InvalidOverride(s.to_string()))?;
+        let path = path.trim();
+        if path.is_empty() || path.split('.').any(str::is_empty) {
+            return Err(Error::InvalidOverride(s.to_string()));
+        }
+        if let Some(value) = explicit_value(raw_value) {
+            return match value {
+                Ok(Value::Null) | Err(_) => Err(Error::InvalidOverride(s.to_string())),
+                Ok(value) => Ok(Self::new(path, value)),
+            };
+        }
+        let value = if raw_value.trim().is_empty() {
+            Value::String(raw_value.to_string())
+        } else {
+            serde_yaml::from_str(raw_value).unwrap_or_else(|_| Value::String(raw_value.to_string()))
+        };
+        Ok(Self {
+            raw: raw_value.to_string(),
+            explicit: false,
+            ..Self::new(path, value)
+        })
+    }
+}
+
+/// Loads configuration file with all its `extends:` bases merged in.
+/// Base paths are resolved relative to the file which declares them and
+/// latter entries override former ones, with the extending file applied last.
+pub fn load_layered<P: AsRef<Path>>(config: P) -> Result<Value, Error> {
+    load_layered_inner(config.as_ref(), &mut Vec::new())
+}
+
+fn load_layered_inner(config: &Path, chain: &mut Vec<PathBuf>) -> Result<Value, Error> {
+    if !config.exists() {
+        return Err(Error::CannotFindConfig(config.to_path_buf()));
+    }
+    let canonical = config.canonicalize()?;
+    if chain.contains(&canonical) {
+        return Err(Error::CyclicExtends(canonical));
+    }
+    chain.push(canonical);
+
+    let contents = std::fs::read_to_string(config)?;
+    let mut value = ConfigFormat::from_path(config).parse(&contents)?;
+
+    let bases = match value.as_object_mut().and_then(|map| map.remove(EXTENDS)) {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::String(base)) => vec![base],
+        Some(Value::Array(bases)) => bases
+            .into_iter()
+            .map(|base| match base {
+                Value::String(base) => Ok(base),
+                other => Err(Error::InvalidExtends(other.to_string())),
+            })
+            .collect::<Result<_, _>>()?,
+        Some(other) => return Err(Error::InvalidExtends(other.to_string())),
+    };
+
+    let parent = config.parent().unwrap_or_else(|| Path::new("."));
+    let mut merged = Value::Object(Map::new());
+    for base in bases {
+        merge(&mut merged, load_layered_inner(&parent.join(base), chain)?);
+    }
+    merge(&mut merged, value);
+
+    chain.pop();
+    Ok(merged)
+}
+
+/// Deep merges `overlay` into `base`. Objects are merged key by key,
+/// any other value (including arrays) is replaced as a whole.
+pub fn merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("cannot find config in: {0}")]
+    CannotFindConfig(PathBuf),
+    #[error("cyclic 'extends' detected at: {0}")]
+    CyclicExtends(PathBuf),
+    #[error("'extends' should be a path or list of paths, got: {0}")]
+    InvalidExtends(String),
+    #[error("override should be in format 'key.path=value', got: '{0}'")]
+    InvalidOverride(String),
+    #[error("cannot apply override for path: '{0}'")]
+    InvalidOverridePath(String),
+    #[error("unknown config format: '{0}', expected one of: json, yaml, toml")]
+    UnknownFormat(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Yaml(#[from] serde_yaml::Error),
+    #[error(transparent)]
+    TomlDeserialize(#[from] toml::de::Error),
+    #[error(transparent)]
+    TomlSerialize(#[from] toml::ser::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::fixture::PathChild;
+    use assert_fs::prelude::FileWriteStr;
+    use assert_fs::TempDir;
+    use serde_json::json;
+
+    #[test]
+    fn override_parses_typed_values() {
+        let number: ConfigOverride = "data.current_fund.proposals=500".parse().unwrap();
+        assert_eq!(number.path(), "data.current_fund.proposals");
+        assert_eq!(number.value, json!(500));
+
+        let boolean: ConfigOverride = "vote_plan.private=true".parse().unwrap();
+        assert_eq!(boolean.value, json!(true));
+
+        let mut value = json!({ "service": { "version": "1.0" } });
+        "service.version=2.0"
+            .parse::<ConfigOverride>()
+            .unwrap()
+            .apply(&mut value)
+            .unwrap();
+        assert_eq!(value, json!({ "service": { "version": "2.0" } }));
+
+        assert!("no_equal_sign".parse::<ConfigOverride>().is_err());
+        assert!("seed=!!int 4.2".parse::<ConfigOverride>().is_err());
+        assert!("a..b=1".parse::<ConfigOverride>().is_err());
+    }
+
+    #[test]
+    fn override_of_missing_field_is_kept_as_typed() {
+        let mut value = json!({});
+        for config_override in [
+            "service.version=3.6",
+            "data.current_fund.proposals=!!int 500",
+            "vote_plan.private=!!bool true",
+            "blockchain.block0_time=\"3.6\"",
+            "data.current_fund.fund_info.groups=[direct, rep]",
+        ] {
+            config_override
+                .parse::<ConfigOverride>()
+                .unwrap()
+                .apply(&mut value)
+                .unwrap();
+        }
+        assert_eq!(
+            value,
+            json!({
+                "service": { "version": "3.6" },
+                "data": { "current_fund": {
+                    "proposals": 500,
+                    "fund_info": { "groups": ["direct", "rep"] }
+                } },
+                "vote_plan": { "private": true },
+                "blockchain": { "block0_time": "3.6" }
+            })
+        );
+    }
+
+    #[test]
+    fn override_creates_missing_sections() {
+        let mut value = json!({ "data": { "current_fund": { "proposals": 10 } } });
+        ConfigOverride::new("data.current_fund.proposals", json!(500))
+            .apply(&mut value)
+            .unwrap();
+        ConfigOverride::new("service.https", json!(true))
+            .apply(&mut value)
+            .unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "data": { "current_fund": { "proposals": 500 } },
+                "service": { "https": true }
+            })
+        );
+    }
+
+    #[test]
+    fn extends_merges_base_configs() {
+        let temp_dir = TempDir::new().unwrap();
+        temp_dir
+            .child("base.json")
+            .write_str(r#"{ "vote_plan": { "private": false }, "data": { "current_fund": { "proposals": 10, "challenges": 3 } } }"#)
+            .unwrap();
+        temp_dir
+            .child("private.yaml")
+            .write_str("extends: base.json\nvote_plan:\n  private: true\n")
+            .unwrap();
+        temp_dir
+            .child("large.toml")
+            .write_str("extends = \"private.yaml\"\n[data.current_fund]\nproposals = 500\n")
+            .unwrap();
+
+        let value = load_layered(temp_dir.child("large.toml").path()).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "vote_plan": { "private": true },
+                "data": { "current_fund": { "proposals": 500, "challenges": 3 } }
+            })
+        );
+    }
+
+    #[test]
+    fn cyclic_extends_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        temp_dir
+            .child("a.yaml")
+            .write_str("extends: b.yaml\n")
+            .unwrap();
+        temp_dir
+            .child("b.yaml")
+            .write_str("extends: a.yaml\n")
+            .unwrap();
+        assert!(matches!(
+            load_layered(temp_dir.child("a.yaml").path()),
+            Err(Error::CyclicExtends(_))
+        ));
+    }
+}
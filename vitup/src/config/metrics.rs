@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Periodic export of deployment status in monitor and service mode, e.g.:
+///
+/// ```yaml
+/// metrics:
+///   interval: 30
+///   file: metrics.jsonl
+///   prometheus: 0.0.0.0:9100
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Metrics {
+    /// seconds between snapshots
+    #[serde(default = "default_interval")]
+    pub interval: u64,
+    /// JSON-lines file, relative to working directory, which every snapshot is appended to
+    #[serde(default)]
+    pub file: Option<PathBuf>,
+    /// address of Prometheus endpoint (`/metrics`) exposing latest snapshot
+    #[serde(default)]
+    pub prometheus: Option<SocketAddr>,
+}
+
+fn default_interval() -> u64 {
+    30
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            interval: default_interval(),
+            file: None,
+            prometheus: None,
+        }
+    }
+}
+
+impl Metrics {
+    pub fn is_enabled(&self) -> bool {
+        self.file.is_some() || self.prometheus.is_some()
+    }
+
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval.max(1))
+    }
+}
@@ -1,48 +1,247 @@
+use diesel::connection::SimpleConnection;
+use diesel::{Connection, SqliteConnection};
 use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use thiserror::Error;
 
 const UP: &str = "up.sql";
 const DOWN: &str = "down.sql";
-const VERSION: &str = "2020-05-22-112032_setup_db";
 
-pub struct MigrationFilesBuilder {
-    up_script_content: String,
-    down_script_content: String,
+/// The only diesel migration shipped by vit-servicing-station. Its schema was changed
+/// in place between releases, so [`MIGRATIONS`] after it are not diesel migrations
+/// and only this one is recorded in `__diesel_schema_migrations`
+pub const SETUP_DB: &str = "2020-05-22-112032_setup_db";
+/// Version under which diesel records [`SETUP_DB`]
+const SETUP_DB_VERSION: &str = "20200522112032";
+
+/// Single vit-servicing-station schema change
+pub struct Migration {
+    pub name: &'static str,
+    /// first vit-servicing-station release which requires this change
+    pub since: ServiceVersion,
+    up: &'static str,
+    down: &'static str,
+    /// statement which succeeds only if change is applied
+    probe: &'static str,
 }
 
-impl Default for MigrationFilesBuilder {
-    fn default() -> Self {
-        Self {
-            up_script_content: include_str!("../../resources/vit_station/up.sql").to_string(),
-            down_script_content: include_str!("../../resources/vit_station/down.sql").to_string(),
+macro_rules! migration {
+    ($name:expr, $dir:literal, ($major:literal, $minor:literal), $probe:literal) => {
+        Migration {
+            name: $name,
+            since: ServiceVersion {
+                major: $major,
+                minor: $minor,
+            },
+            up: include_str!(concat!("../../resources/vit_station/", $dir, "/up.sql")),
+            down: include_str!(concat!("../../resources/vit_station/", $dir, "/down.sql")),
+            probe: $probe,
         }
+    };
+}
+
+/// All known schema changes, in order of application. First one is the initial
+/// [`SETUP_DB`] schema, others are changes made to it by later releases
+pub const MIGRATIONS: &[Migration] = &[
+    migration!(
+        SETUP_DB,
+        "migrations/2020-05-22-112032_setup_db",
+        (2, 0),
+        "SELECT id FROM funds LIMIT 0"
+    ),
+    migration!(
+        "goals",
+        "schema_changes/goals",
+        (3, 4),
+        "SELECT id FROM goals LIMIT 0"
+    ),
+    migration!(
+        "voting_groups",
+        "schema_changes/voting_groups",
+        (3, 6),
+        "SELECT group_id FROM groups LIMIT 0"
+    ),
+];
+
+/// Schema changes which make up database schema expected by given vit-servicing-station release
+pub fn migrations_for(version: &ServiceVersion) -> Result<&'static [Migration], Error> {
+    let count = MIGRATIONS
+        .iter()
+        .take_while(|migration| migration.since <= *version)
+        .count();
+    if count == 0 {
+        return Err(Error::UnsupportedVersion(*version));
+    }
+    Ok(&MIGRATIONS[..count])
+}
+
+/// vit-servicing-station release. Only major and minor parts are relevant for db schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ServiceVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl ServiceVersion {
+    pub fn latest() -> Self {
+        MIGRATIONS.last().unwrap().since
+    }
+}
+
+impl std::fmt::Display for ServiceVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl FromStr for ServiceVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidVersion(s.to_string());
+        let mut parts = s.trim().trim_start_matches('v').split('.');
+        let major = parts
+            .next()
+            .and_then(|part| part.parse().ok())
+            .ok_or_else(invalid)?;
+        let minor = match parts.next() {
+            Some(part) => part.parse().map_err(|_| invalid())?,
+            None => 0,
+        };
+        Ok(Self { major, minor })
     }
 }
 
+/// Writes [`SETUP_DB`] migration with latest schema, as vit-servicing-station ships it
+#[derive(Default)]
+pub struct MigrationFilesBuilder;
+
 impl MigrationFilesBuilder {
     pub fn build<P: AsRef<Path>>(self, working_dir: P) -> Result<PathBuf, Error> {
         let migrations_dir = working_dir.as_ref().to_path_buf().join("migrations");
-        let version_dir = migrations_dir.join(VERSION);
+        if migrations_dir.exists() {
+            std::fs::remove_dir_all(&migrations_dir)?;
+        }
+        let version_dir = migrations_dir.join(SETUP_DB);
         std::fs::create_dir_all(&version_dir)?;
 
+        let up: Vec<&str> = MIGRATIONS.iter().map(|migration| migration.up).collect();
         let up_path = version_dir.join(UP);
         let mut up_file =
             File::create(&up_path).map_err(|_| Error::CannotCreateFile(up_path.clone()))?;
         up_file
-            .write_all(self.up_script_content.as_bytes())
+            .write_all(up.join("\n").as_bytes())
             .map_err(|_| Error::CannotWriteToFile(up_path.clone()))?;
 
+        let down: Vec<&str> = MIGRATIONS
+            .iter()
+            .rev()
+            .map(|migration| migration.down)
+            .collect();
         let down_path = version_dir.join(DOWN);
         let mut server_file =
             File::create(&down_path).map_err(|_| Error::CannotWriteToFile(down_path.clone()))?;
-        server_file.write_all(self.down_script_content.as_bytes())?;
+        server_file.write_all(down.join("\n").as_bytes())?;
 
         Ok(migrations_dir)
     }
 }
 
+/// Brings existing vit-servicing-station database to schema of another release
+pub struct DbMigrator {
+    connection: SqliteConnection,
+}
+
+impl DbMigrator {
+    pub fn open<P: AsRef<Path>>(db: P) -> Result<Self, Error> {
+        let db = db.as_ref();
+        Ok(Self {
+            connection: SqliteConnection::establish(&db.to_string_lossy())
+                .map_err(|e| Error::Connection(db.to_path_buf(), e.to_string()))?,
+        })
+    }
+
+    /// Schema changes already present in database, detected from its schema, since
+    /// [`SETUP_DB`] is edited in place and diesel does not keep track of them
+    pub fn applied(&self) -> &'static [Migration] {
+        let count = MIGRATIONS
+            .iter()
+            .take_while(|migration| self.connection.batch_execute(migration.probe).is_ok())
+            .count();
+        &MIGRATIONS[..count]
+    }
+
+    /// Applies missing schema changes or reverts newer ones. Returns executed steps.
+    /// `__diesel_schema_migrations` is updated as well, so vit-servicing-station does not
+    /// try to run [`SETUP_DB`] again on existing schema
+    pub fn migrate_to(&self, version: &ServiceVersion) -> Result<Vec<MigrationStep>, Error> {
+        let applied = self.applied().len();
+        let expected = migrations_for(version)?.len();
+        let mut steps = Vec::new();
+
+        self.connection
+            .transaction(|| self.sync_diesel_migrations())
+            .map_err(|e| Error::MigrationFailed(SETUP_DB, e.to_string()))?;
+        for migration in &MIGRATIONS[applied.min(expected)..expected] {
+            self.execute(migration.up)
+                .map_err(|e| Error::MigrationFailed(migration.name, e.to_string()))?;
+            steps.push(MigrationStep::Up(migration.name));
+        }
+        for migration in MIGRATIONS[expected..applied.max(expected)].iter().rev() {
+            self.execute(migration.down)
+                .map_err(|e| Error::MigrationFailed(migration.name, e.to_string()))?;
+            steps.push(MigrationStep::Down(migration.name));
+        }
+        Ok(steps)
+    }
+
+    fn execute(&self, script: &str) -> Result<(), diesel::result::Error> {
+        self.connection.transaction(|| {
+            self.connection.batch_execute(script)?;
+            self.sync_diesel_migrations()
+        })
+    }
+
+    /// Records [`SETUP_DB`] as run by diesel if its schema is present, removes it otherwise
+    fn sync_diesel_migrations(&self) -> Result<(), diesel::result::Error> {
+        self.connection.batch_execute(
+            "CREATE TABLE IF NOT EXISTS __diesel_schema_migrations (
+                version VARCHAR(50) PRIMARY KEY NOT NULL,
+                run_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+            )",
+        )?;
+        let script = if self.connection.batch_execute(MIGRATIONS[0].probe).is_ok() {
+            format!(
+                "INSERT OR IGNORE INTO __diesel_schema_migrations (version) VALUES ('{}')",
+                SETUP_DB_VERSION
+            )
+        } else {
+            format!(
+                "DELETE FROM __diesel_schema_migrations WHERE version = '{}'",
+                SETUP_DB_VERSION
+            )
+        };
+        self.connection.batch_execute(&script)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationStep {
+    Up(&'static str),
+    Down(&'static str),
+}
+
+impl std::fmt::Display for MigrationStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Up(name) => write!(f, "applied {}", name),
+            Self::Down(name) => write!(f, "reverted {}", name),
+        }
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Error)]
 pub enum Error {
@@ -50,6 +249,94 @@ pub enum Error {
     CannotCreateFile(PathBuf),
     #[error("cannot write file: {0}")]
     CannotWriteToFile(PathBuf),
+    #[error("invalid vit-servicing-station version: '{0}', expected e.g. 3.6")]
+    InvalidVersion(String),
+    #[error("vit-servicing-station {0} is older than any supported db schema")]
+    UnsupportedVersion(ServiceVersion),
+    #[error("cannot open database {0}: {1}")]
+    Connection(PathBuf, String),
+    #[error("migration {0} failed: {1}")]
+    MigrationFailed(&'static str, String),
     #[error(transparent)]
     Io(#[from] std::io::Error),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::dsl::sql;
+    use diesel::sql_types::Text;
+    use diesel::RunQueryDsl;
+
+    fn version(s: &str) -> ServiceVersion {
+        s.parse().unwrap()
+    }
+
+    fn diesel_versions(migrator: &DbMigrator) -> Vec<String> {
+        diesel::select(sql::<Text>("version FROM __diesel_schema_migrations"))
+            .load(&migrator.connection)
+            .unwrap()
+    }
+
+    #[test]
+    fn database_is_migrated_between_versions() {
+        assert_eq!(version("v3.6.1"), version("3.6"));
+        assert!(version("3.10") > version("3.6"));
+        assert!("three".parse::<ServiceVersion>().is_err());
+        assert_eq!(migrations_for(&version("3.5")).unwrap().len(), 2);
+        assert!(migrations_for(&version("1.9")).is_err());
+
+        let migrator = DbMigrator::open(":memory:").unwrap();
+        assert!(migrator.applied().is_empty());
+
+        let steps = migrator.migrate_to(&version("3.4")).unwrap();
+        assert_eq!(
+            steps,
+            vec![MigrationStep::Up(SETUP_DB), MigrationStep::Up("goals")]
+        );
+        assert_eq!(migrator.applied().len(), 2);
+        assert_eq!(diesel_versions(&migrator), vec![SETUP_DB_VERSION]);
+
+        migrator.migrate_to(&ServiceVersion::latest()).unwrap();
+        assert_eq!(migrator.applied().len(), MIGRATIONS.len());
+
+        let steps = migrator.migrate_to(&version("2.0")).unwrap();
+        assert_eq!(
+            steps,
+            vec![
+                MigrationStep::Down("voting_groups"),
+                MigrationStep::Down("goals"),
+            ]
+        );
+        assert_eq!(migrator.applied().len(), 1);
+        assert_eq!(diesel_versions(&migrator), vec![SETUP_DB_VERSION]);
+
+        migrator.execute(MIGRATIONS[0].down).unwrap();
+        assert!(diesel_versions(&migrator).is_empty());
+    }
+
+    #[test]
+    fn untracked_database_is_recorded_as_migrated_by_diesel() {
+        let migrator = DbMigrator::open(":memory:").unwrap();
+        migrator.connection.batch_execute(MIGRATIONS[0].up).unwrap();
+
+        assert!(migrator.migrate_to(&version("2.0")).unwrap().is_empty());
+        assert_eq!(diesel_versions(&migrator), vec![SETUP_DB_VERSION]);
+    }
+
+    #[test]
+    fn migration_files_contain_latest_schema() {
+        let working_dir = assert_fs::TempDir::new().unwrap();
+        let migrations_dir = MigrationFilesBuilder::default()
+            .build(working_dir.path())
+            .unwrap();
+        let up = std::fs::read_to_string(migrations_dir.join(SETUP_DB).join(UP)).unwrap();
+        let down = std::fs::read_to_string(migrations_dir.join(SETUP_DB).join(DOWN)).unwrap();
+
+        let migrator = DbMigrator::open(":memory:").unwrap();
+        migrator.connection.batch_execute(&up).unwrap();
+        assert_eq!(migrator.applied().len(), MIGRATIONS.len());
+        migrator.connection.batch_execute(&down).unwrap();
+        assert!(migrator.applied().is_empty());
+    }
+}
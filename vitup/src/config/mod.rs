@@ -1,9 +1,15 @@
 mod blockchain;
 mod builder;
 mod initials;
+mod loader;
+mod metrics;
 mod migrations;
+mod report;
+mod reviews;
 mod service;
 mod static_data;
+mod time_plan;
+mod topology;
 mod vote_plan;
 mod vote_time;
 
@@ -12,24 +18,37 @@ pub mod mode;
 
 use crate::config::builder::convert_to_human_date;
 use crate::config::vote_time::FORMAT;
+use crate::mode::standard::Disruption;
 use crate::Result;
 pub use blockchain::Blockchain;
 pub use builder::ConfigBuilder;
-pub use certs::CertificatesBuilder;
+pub use certs::{CertificatesBuilder, TlsSettings};
 use chain_addr::Discrimination;
 pub use initials::{
-    Block0Initial, Block0Initials, Initials, Role, SnapshotError, SnapshotInitial, SnapshotInitials,
+    Block0Initial, Block0Initials, Delegation, DelegationError, DelegationWeight, Delegator,
+    Distribution, DistributionError, DistributionShape, Initials, Representative, Role,
+    SnapshotError, SnapshotInitial, SnapshotInitials,
 };
-pub use migrations::{Error as MigrationError, MigrationFilesBuilder};
+pub use loader::{load_layered, ConfigFormat, ConfigOverride, Error as LoaderError};
+pub use metrics::Metrics;
+pub use migrations::{
+    DbMigrator, Error as MigrationError, MigrationFilesBuilder, MigrationStep, ServiceVersion,
+};
+use rand_chacha::ChaChaRng;
+use rand_core::SeedableRng;
+pub use report::Report;
+pub use reviews::{Error as ReviewsError, Range, RankingProportions, ReviewsProfile};
 use serde::{Deserialize, Serialize};
 pub use service::Service;
 use snapshot_lib::VoterHIR;
-pub use static_data::StaticData;
+pub use static_data::{CurrentFundDates, StaticData};
 use std::path::Path;
 use std::time::Duration;
 use time::format_description::{self, FormatItem};
+pub use time_plan::{PlanError, PlanLimits, TimePlan, TimeTargets};
+pub use topology::{Error as TopologyError, NodeRole, NodeSetup, Topology};
 use valgrind::Protocol;
-pub use vote_plan::VotePlan;
+pub use vote_plan::{VotePlan, VotePlanDefinition};
 pub use vote_time::{VoteBlockchainTime, VoteTime, FORMAT as VOTE_TIME_FORMAT};
 
 #[derive(Clone, Debug, Deserialize, Serialize, Default)]
@@ -44,12 +63,39 @@ pub struct Config {
     pub data: StaticData,
     #[serde(default)]
     pub service: Service,
+    #[serde(default)]
+    pub topology: Topology,
+    #[serde(default)]
+    pub reviews: ReviewsProfile,
+    #[serde(default)]
+    pub metrics: Metrics,
+    #[serde(default)]
+    pub report: Report,
+    /// disruptions scheduled as soon as deployment is started in standard mode,
+    /// e.g. `{ "target": "Leader1", "action": "kill", "delay": 60, "duration": 30 }`
+    #[serde(default)]
+    pub disruptions: Vec<Disruption>,
+    /// seed for all random generators used while building deployment
+    #[serde(default)]
+    pub seed: Option<u64>,
 }
 
 impl Config {
+    /// Random generator seeded with `seed` from config (or from entropy if not defined).
+    /// Each consumer uses its own stream, so sequences are reproducible and
+    /// independent of each other.
+    pub fn rng(&self, stream: RandomStream) -> ChaChaRng {
+        let mut rng = rng_from_seed(self.seed);
+        rng.set_stream(stream as u64);
+        rng
+    }
+
     pub fn protocol<P: AsRef<Path>>(&self, working_dir: P) -> Result<Protocol> {
         if self.service.https {
-            Ok(CertificatesBuilder::default().build(working_dir)?.into())
+            Ok(CertificatesBuilder::default()
+                .settings(self.service.tls.clone())
+                .build(working_dir)?
+                .into())
         } else {
             Ok(Default::default())
         }
@@ -133,13 +179,44 @@ impl Config {
 }
 
 pub fn read_config<P: AsRef<Path>>(config: P) -> Result<Config> {
+    read_config_with_overrides(config, &[])
+}
+
+/// Reads json, yaml or toml config, resolving `extends:` bases
+/// and applying `--set` overrides on top of the merged result.
+pub fn read_config_with_overrides<P: AsRef<Path>>(
+    config: P,
+    overrides: &[ConfigOverride],
+) -> Result<Config> {
     let config = config.as_ref();
     if !config.exists() {
         return Err(crate::error::Error::CannotFindConfig(config.to_path_buf()));
     }
 
-    let contents = std::fs::read_to_string(&config)?;
-    serde_json::from_str(&contents).map_err(Into::into)
+    let mut value = load_layered(config)?;
+    for config_override in overrides {
+        config_override.apply(&mut value)?;
+    }
+    serde_json::from_value(value).map_err(Into::into)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RandomStream {
+    Block0 = 1,
+    VotePlans = 2,
+    Qr = 3,
+    Snapshot = 4,
+    Reviews = 5,
+    Data = 6,
+    Network = 7,
+    QrEncryption = 8,
+}
+
+pub fn rng_from_seed(seed: Option<u64>) -> ChaChaRng {
+    match seed {
+        Some(seed) => ChaChaRng::seed_from_u64(seed),
+        None => ChaChaRng::from_entropy(),
+    }
 }
 
 pub fn date_format() -> Vec<FormatItem<'static>> {
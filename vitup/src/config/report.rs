@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Number of most recent reports kept in reports folder by default
+pub const REPORTS_KEPT: usize = 10;
+
+/// Post-mortem report gathered when deployment ends or fails, e.g.:
+///
+/// ```yaml
+/// report:
+///   dir: ./reports
+///   archive: true
+///   keep: 5
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Report {
+    /// set to false to skip collecting artefacts on shutdown
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// folder in which timestamped report folders (or archives) are created
+    #[serde(default = "default_dir")]
+    pub dir: PathBuf,
+    /// pack report into tar.gz archive instead of leaving plain folder
+    #[serde(default)]
+    pub archive: bool,
+    /// how many most recent reports are kept, older ones are removed
+    #[serde(default = "default_keep")]
+    pub keep: usize,
+    /// copy also persistent storage of nodes, which can be large
+    #[serde(default)]
+    pub storage: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_dir() -> PathBuf {
+    PathBuf::from("./reports")
+}
+
+fn default_keep() -> usize {
+    REPORTS_KEPT
+}
+
+impl Default for Report {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            dir: default_dir(),
+            archive: false,
+            keep: default_keep(),
+            storage: false,
+        }
+    }
+}
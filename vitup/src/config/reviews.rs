@@ -0,0 +1,222 @@
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+use vit_servicing_station_lib::db::models::community_advisors_reviews::ReviewRanking;
+
+/// Profile of generated community advisors reviews. Every distribution is defined
+/// as weights, e.g. `{ "0": 1, "4": 3 }` means rating 4 is three times more likely than 0.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ReviewsProfile {
+    #[serde(default = "default_count")]
+    pub count: Range,
+    #[serde(default = "default_rating")]
+    pub impact_alignment_rating: BTreeMap<u8, u32>,
+    #[serde(default = "default_rating")]
+    pub feasibility_rating: BTreeMap<u8, u32>,
+    #[serde(default = "default_rating")]
+    pub auditability_rating: BTreeMap<u8, u32>,
+    #[serde(default)]
+    pub ranking: RankingProportions,
+    #[serde(default = "default_note_length")]
+    pub note_length: Range,
+    #[serde(default = "default_challenge_types")]
+    pub challenge_types: Vec<String>,
+}
+
+impl Default for ReviewsProfile {
+    fn default() -> Self {
+        Self {
+            count: default_count(),
+            impact_alignment_rating: default_rating(),
+            feasibility_rating: default_rating(),
+            auditability_rating: default_rating(),
+            ranking: Default::default(),
+            note_length: default_note_length(),
+            challenge_types: default_challenge_types(),
+        }
+    }
+}
+
+/// Inclusive range of values drawn uniformly
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Range {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl Range {
+    pub fn fixed(value: usize) -> Self {
+        Self {
+            min: value,
+            max: value,
+        }
+    }
+
+    pub fn sample<R: RngCore>(&self, rng: &mut R) -> usize {
+        self.min + (rng.next_u64() % (self.max - self.min + 1) as u64) as usize
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RankingProportions {
+    #[serde(default)]
+    pub excellent: u32,
+    #[serde(default)]
+    pub good: u32,
+    #[serde(default)]
+    pub filtered_out: u32,
+}
+
+impl Default for RankingProportions {
+    fn default() -> Self {
+        Self {
+            excellent: 1,
+            good: 1,
+            filtered_out: 0,
+        }
+    }
+}
+
+impl RankingProportions {
+    pub fn sample<R: RngCore>(&self, rng: &mut R) -> ReviewRanking {
+        let weights = [
+            (ReviewRanking::Excellent, self.excellent),
+            (ReviewRanking::Good, self.good),
+            (ReviewRanking::FilteredOut, self.filtered_out),
+        ];
+        choose_weighted(weights.into_iter(), rng)
+    }
+}
+
+impl ReviewsProfile {
+    pub fn sample_rating<R: RngCore>(weights: &BTreeMap<u8, u32>, rng: &mut R) -> i32 {
+        choose_weighted(
+            weights
+                .iter()
+                .map(|(rating, weight)| (*rating as i32, *weight)),
+            rng,
+        )
+    }
+
+    /// Whether proposals from challenge of given type (`simple`, `community-choice`, `native`)
+    /// receive community advisors reviews shaped by this profile
+    pub fn is_reviewed(&self, challenge_type: &str) -> bool {
+        self.challenge_types
+            .iter()
+            .any(|x| x.eq_ignore_ascii_case(challenge_type))
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        for (name, range) in [("count", &self.count), ("note_length", &self.note_length)] {
+            if range.min > range.max {
+                return Err(Error::InvalidRange(name.to_string()));
+            }
+        }
+        for (name, weights) in [
+            ("impact_alignment_rating", &self.impact_alignment_rating),
+            ("feasibility_rating", &self.feasibility_rating),
+            ("auditability_rating", &self.auditability_rating),
+        ] {
+            if weights.values().sum::<u32>() == 0 {
+                return Err(Error::EmptyDistribution(name.to_string()));
+            }
+            if let Some(rating) = weights.keys().find(|rating| !(0..=4).contains(*rating)) {
+                return Err(Error::RatingOutOfRange(*rating));
+            }
+        }
+        let ranking = &self.ranking;
+        if ranking.excellent + ranking.good + ranking.filtered_out == 0 {
+            return Err(Error::EmptyDistribution("ranking".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Picks value with probability proportional to its weight. Total weight must not be zero.
+fn choose_weighted<T, I, R>(weights: I, rng: &mut R) -> T
+where
+    I: Iterator<Item = (T, u32)> + Clone,
+    R: RngCore,
+{
+    let total: u64 = weights.clone().map(|(_, weight)| weight as u64).sum();
+    let mut point = rng.next_u64() % total;
+    for (value, weight) in weights {
+        if point < weight as u64 {
+            return value;
+        }
+        point -= weight as u64;
+    }
+    unreachable!("point is always lower than total weight")
+}
+
+fn default_count() -> Range {
+    Range::fixed(3)
+}
+
+fn default_note_length() -> Range {
+    Range { min: 0, max: 10 }
+}
+
+fn default_rating() -> BTreeMap<u8, u32> {
+    (0..=4).map(|rating| (rating, 1)).collect()
+}
+
+fn default_challenge_types() -> Vec<String> {
+    vec![
+        "simple".to_string(),
+        "community-choice".to_string(),
+        "native".to_string(),
+    ]
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("reviews '{0}' range has min greater than max")]
+    InvalidRange(String),
+    #[error("reviews '{0}' distribution should have at least one non zero weight")]
+    EmptyDistribution(String),
+    #[error("review rating should be in range 0..=4, got: {0}")]
+    RatingOutOfRange(u8),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::ChaChaRng;
+    use rand_core::SeedableRng;
+
+    #[test]
+    fn zero_weight_values_are_never_picked() {
+        let mut rng = ChaChaRng::seed_from_u64(1);
+        let ranking = RankingProportions {
+            excellent: 0,
+            good: 0,
+            filtered_out: 1,
+        };
+        let ratings: BTreeMap<u8, u32> = [(2, 0), (4, 1)].into_iter().collect();
+
+        for _ in 0..100 {
+            assert_eq!(ranking.sample(&mut rng), ReviewRanking::FilteredOut);
+            assert_eq!(ReviewsProfile::sample_rating(&ratings, &mut rng), 4);
+            assert!((2..=4).contains(&Range { min: 2, max: 4 }.sample(&mut rng)));
+        }
+    }
+
+    #[test]
+    fn invalid_profile_is_rejected() {
+        let profile = ReviewsProfile {
+            ranking: RankingProportions {
+                excellent: 0,
+                good: 0,
+                filtered_out: 0,
+            },
+            ..Default::default()
+        };
+        assert!(matches!(
+            profile.validate(),
+            Err(Error::EmptyDistribution(_))
+        ));
+        assert!(ReviewsProfile::default().validate().is_ok());
+    }
+}
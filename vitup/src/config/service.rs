@@ -1,8 +1,11 @@
+use crate::config::certs::TlsSettings;
 use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Service {
     pub version: String,
     pub https: bool,
+    #[serde(default)]
+    pub tls: TlsSettings,
 }
 
 impl Default for Service {
@@ -10,6 +13,7 @@ impl Default for Service {
         Self {
             version: "3.6".to_string(),
             https: false,
+            tls: Default::default(),
         }
     }
 }
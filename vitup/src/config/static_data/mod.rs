@@ -2,7 +2,7 @@ mod current;
 mod info;
 mod next;
 
-pub use current::CurrentFund;
+pub use current::{CurrentFund, CurrentFundDates};
 use info::FundInfo;
 pub use next::NextFund;
 use serde::{Deserialize, Serialize};
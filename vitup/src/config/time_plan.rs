@@ -0,0 +1,322 @@
+use super::{Blockchain, CurrentFundDates, VoteBlockchainTime, VoteTime};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use time::{Duration, OffsetDateTime};
+
+/// Target dates of fund timeline. Block0 date is optional, if not provided
+/// it is placed one epoch before vote start.
+#[derive(Debug, Clone)]
+pub struct TimeTargets {
+    pub block0: Option<OffsetDateTime>,
+    pub snapshot: OffsetDateTime,
+    pub vote_start: OffsetDateTime,
+    pub tally_start: OffsetDateTime,
+    pub tally_end: OffsetDateTime,
+    pub next_fund: OffsetDateTime,
+}
+
+/// Bounds of blockchain settings considered by planner
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlanLimits {
+    pub min_slot_duration: u8,
+    pub max_slot_duration: u8,
+    pub min_slots_per_epoch: u32,
+    pub max_slots_per_epoch: u32,
+    /// maximum accepted difference between target and planned date
+    pub tolerance_secs: u64,
+}
+
+impl Default for PlanLimits {
+    fn default() -> Self {
+        Self {
+            min_slot_duration: 1,
+            max_slot_duration: 20,
+            min_slots_per_epoch: 10,
+            max_slots_per_epoch: 1_000_000,
+            tolerance_secs: 0,
+        }
+    }
+}
+
+/// Settings which put vote start, tally start and tally end on epoch boundaries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimePlan {
+    pub slot_duration: u8,
+    pub slots_per_epoch: u32,
+    pub block0_time: OffsetDateTime,
+    pub vote_start: u32,
+    pub tally_start: u32,
+    pub tally_end: u32,
+}
+
+impl TimePlan {
+    fn epoch_duration(&self) -> i64 {
+        self.slot_duration as i64 * self.slots_per_epoch as i64
+    }
+
+    pub fn epoch_start(&self, epoch: u32) -> OffsetDateTime {
+        self.block0_time + Duration::seconds(self.epoch_duration() * epoch as i64)
+    }
+
+    pub fn vote_time(&self) -> VoteTime {
+        VoteTime::Blockchain(VoteBlockchainTime {
+            vote_start: self.vote_start,
+            tally_start: self.tally_start,
+            tally_end: self.tally_end,
+            slots_per_epoch: self.slots_per_epoch,
+        })
+    }
+
+    pub fn apply_to_blockchain(&self, blockchain: &mut Blockchain) {
+        blockchain.slot_duration = self.slot_duration;
+        blockchain.block0_time = self.block0_time;
+    }
+}
+
+impl TimeTargets {
+    /// Finds slot duration and slots per epoch within limits for which all vote dates
+    /// fall on epoch boundaries. Each planned date has to be within tolerance from its own
+    /// target. Among matching settings the one with the smallest deviation from targets
+    /// and then the longest epoch is chosen.
+    pub fn plan(&self, limits: &PlanLimits) -> Result<TimePlan, PlanError> {
+        self.check_order()?;
+        if limits.min_slot_duration == 0
+            || limits.min_slot_duration > limits.max_slot_duration
+            || limits.min_slots_per_epoch == 0
+            || limits.min_slots_per_epoch > limits.max_slots_per_epoch
+        {
+            return Err(PlanError::InvalidLimits);
+        }
+
+        let vote_duration = seconds(self.tally_start - self.vote_start);
+        let tally_duration = seconds(self.tally_end - self.tally_start);
+        let tolerance = limits.tolerance_secs as i64;
+
+        let mut best: Option<(i64, TimePlan)> = None;
+        let mut closest: Option<(i64, u8, u32)> = None;
+        for slot_duration in limits.min_slot_duration..=limits.max_slot_duration {
+            // only epochs not longer than the shortest span can put each date on its own epoch
+            let max_slots = (vote_duration.min(tally_duration) / slot_duration as i64)
+                .min(limits.max_slots_per_epoch as i64);
+            for slots_per_epoch in limits.min_slots_per_epoch as i64..=max_slots {
+                let epoch = slot_duration as i64 * slots_per_epoch;
+                let block0_time = self
+                    .block0
+                    .unwrap_or_else(|| self.vote_start - Duration::seconds(epoch));
+                // epoch closest to target date (but not before given one) and distance
+                // between its start and target
+                let place = |target: OffsetDateTime, min_epoch: i64| {
+                    let offset = seconds(target - block0_time);
+                    let epochs = ((offset + epoch / 2) / epoch).max(min_epoch);
+                    (epochs, (offset - epochs * epoch).abs())
+                };
+                let (vote_start, vote_start_error) = place(self.vote_start, 0);
+                let (tally_start, tally_start_error) = place(self.tally_start, vote_start + 1);
+                let (tally_end, tally_end_error) = place(self.tally_end, tally_start + 1);
+                let error = vote_start_error.max(tally_start_error).max(tally_end_error);
+
+                if closest.map(|(e, _, _)| error < e).unwrap_or(true) {
+                    closest = Some((error, slot_duration, slots_per_epoch as u32));
+                }
+                if error > tolerance {
+                    continue;
+                }
+                let better = match &best {
+                    None => true,
+                    Some((best_error, plan)) => {
+                        error < *best_error
+                            || (error == *best_error && epoch > plan.epoch_duration())
+                    }
+                };
+                if better {
+                    best = Some((
+                        error,
+                        TimePlan {
+                            slot_duration,
+                            slots_per_epoch: slots_per_epoch as u32,
+                            block0_time,
+                            vote_start: vote_start as u32,
+                            tally_start: tally_start as u32,
+                            tally_end: tally_end as u32,
+                        },
+                    ));
+                }
+            }
+        }
+
+        best.map(|(_, plan)| plan).ok_or_else(|| match closest {
+            Some((error, slot_duration, slots_per_epoch)) => PlanError::NoMatch {
+                tolerance: limits.tolerance_secs,
+                error,
+                slot_duration,
+                slots_per_epoch,
+            },
+            None => PlanError::SpanTooShort {
+                span: vote_duration.min(tally_duration),
+                min_epoch: limits.min_slot_duration as i64 * limits.min_slots_per_epoch as i64,
+            },
+        })
+    }
+
+    /// Fund dates matching plan. Next snapshot keeps the same distance to next fund
+    /// as current snapshot has to vote start.
+    pub fn fund_dates(&self, plan: &TimePlan, mut dates: CurrentFundDates) -> CurrentFundDates {
+        dates.snapshot_time = self.snapshot;
+        dates.next_vote_start_time = self.next_fund;
+        dates.next_snapshot_time =
+            self.next_fund - (plan.epoch_start(plan.vote_start) - self.snapshot);
+        dates
+    }
+
+    fn check_order(&self) -> Result<(), PlanError> {
+        let invalid = |earlier: &str, later: &str| PlanError::InvalidOrder {
+            earlier: earlier.to_string(),
+            later: later.to_string(),
+        };
+        if self
+            .block0
+            .map(|block0| block0 > self.vote_start)
+            .unwrap_or(false)
+        {
+            return Err(invalid("block0", "vote start"));
+        }
+        if self.snapshot > self.vote_start {
+            return Err(invalid("snapshot", "vote start"));
+        }
+        for ((earlier, earlier_date), (later, later_date)) in [
+            (
+                ("vote start", self.vote_start),
+                ("tally start", self.tally_start),
+            ),
+            (
+                ("tally start", self.tally_start),
+                ("tally end", self.tally_end),
+            ),
+            (("tally end", self.tally_end), ("next fund", self.next_fund)),
+        ] {
+            if later_date <= earlier_date {
+                return Err(invalid(earlier, later));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn seconds(duration: Duration) -> i64 {
+    duration.whole_seconds()
+}
+
+#[derive(Debug, Error)]
+pub enum PlanError {
+    #[error("'{earlier}' should be before '{later}'")]
+    InvalidOrder { earlier: String, later: String },
+    #[error("plan limits are empty (min greater than max or zero)")]
+    InvalidLimits,
+    #[error("vote and tally should last at least one epoch ({min_epoch} s with given limits), but one of them lasts {span} s")]
+    SpanTooShort { span: i64, min_epoch: i64 },
+    #[error("dates cannot be placed on epoch boundaries within {tolerance} s tolerance. Closest match: slot_duration {slot_duration}, slots_per_epoch {slots_per_epoch} is {error} s off. Consider moving dates, widening slot limits or increasing tolerance")]
+    NoMatch {
+        tolerance: u64,
+        error: i64,
+        slot_duration: u8,
+        slots_per_epoch: u32,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    fn targets() -> TimeTargets {
+        TimeTargets {
+            block0: None,
+            snapshot: datetime!(2022-06-01 10:00 UTC),
+            vote_start: datetime!(2022-06-02 12:00 UTC),
+            tally_start: datetime!(2022-06-09 12:00 UTC),
+            tally_end: datetime!(2022-06-10 12:00 UTC),
+            next_fund: datetime!(2022-08-01 12:00 UTC),
+        }
+    }
+
+    #[test]
+    fn dates_are_placed_on_epoch_boundaries() {
+        let targets = targets();
+        let plan = targets.plan(&PlanLimits::default()).unwrap();
+
+        assert_eq!(plan.epoch_start(plan.vote_start), targets.vote_start);
+        assert_eq!(plan.epoch_start(plan.tally_start), targets.tally_start);
+        assert_eq!(plan.epoch_start(plan.tally_end), targets.tally_end);
+        // whole day is the longest epoch dividing both spans
+        assert_eq!(
+            plan.slot_duration as u32 * plan.slots_per_epoch,
+            24 * 60 * 60
+        );
+
+        let dates = targets.fund_dates(&plan, Default::default());
+        assert_eq!(dates.next_snapshot_time, datetime!(2022-07-31 10:00 UTC));
+    }
+
+    #[test]
+    fn impossible_dates_are_explained() {
+        let mut targets = targets();
+        targets.tally_start = targets.vote_start - Duration::hours(1);
+        assert!(matches!(
+            targets.plan(&PlanLimits::default()),
+            Err(PlanError::InvalidOrder { .. })
+        ));
+
+        let mut targets = self::targets();
+        targets.block0 = Some(datetime!(2022-06-02 11:59:59 UTC));
+        let limits = PlanLimits {
+            min_slot_duration: 2,
+            max_slot_duration: 2,
+            ..Default::default()
+        };
+        assert!(matches!(
+            targets.plan(&limits),
+            Err(PlanError::NoMatch { error: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn each_date_is_within_tolerance_from_own_target() {
+        let block0 = datetime!(2022-06-02 12:00 UTC);
+        // every span is 4 s longer than whole epochs, so errors would add up
+        // if spans were rounded instead of dates
+        let targets = TimeTargets {
+            block0: Some(block0),
+            snapshot: block0,
+            vote_start: block0 + Duration::seconds(104),
+            tally_start: block0 + Duration::seconds(208),
+            tally_end: block0 + Duration::seconds(312),
+            next_fund: block0 + Duration::days(30),
+        };
+        let limits = PlanLimits {
+            min_slot_duration: 1,
+            max_slot_duration: 1,
+            min_slots_per_epoch: 10,
+            max_slots_per_epoch: 10,
+            tolerance_secs: 5,
+        };
+        let plan = targets.plan(&limits).unwrap();
+
+        for (epoch, target) in [
+            (plan.vote_start, targets.vote_start),
+            (plan.tally_start, targets.tally_start),
+            (plan.tally_end, targets.tally_end),
+        ] {
+            assert!((plan.epoch_start(epoch) - target).abs() <= Duration::seconds(5));
+        }
+
+        let limits = PlanLimits {
+            tolerance_secs: 3,
+            ..limits
+        };
+        assert!(matches!(
+            targets.plan(&limits),
+            Err(PlanError::NoMatch { error: 4, .. })
+        ));
+    }
+}
@@ -0,0 +1,245 @@
+use crate::builders::{LEADER_1, WALLET_NODE};
+use jormungandr_automation::jormungandr::LogLevel;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Network layout of jormungandr nodes. By default 3 leaders and one passive node
+/// (used by wallet proxy) are created. Explicit `nodes` list takes precedence over
+/// `leaders`/`passive` counts.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Topology {
+    #[serde(default = "default_leaders")]
+    pub leaders: usize,
+    #[serde(default = "default_passive")]
+    pub passive: usize,
+    #[serde(default)]
+    pub nodes: Vec<NodeSetup>,
+    #[serde(default)]
+    pub wallet_proxy_node: Option<String>,
+}
+
+impl Default for Topology {
+    fn default() -> Self {
+        Self {
+            leaders: default_leaders(),
+            passive: default_passive(),
+            nodes: Vec::new(),
+            wallet_proxy_node: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct NodeSetup {
+    pub alias: String,
+    #[serde(default)]
+    pub role: NodeRole,
+    #[serde(default)]
+    pub trusted_peers: Vec<String>,
+    #[serde(default)]
+    pub log_level: Option<String>,
+    #[serde(default = "default_persistent")]
+    pub persistent: bool,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeRole {
+    Leader,
+    Passive,
+}
+
+impl NodeSetup {
+    pub fn log_level(&self) -> Result<Option<LogLevel>, Error> {
+        self.log_level
+            .as_ref()
+            .map(|level| {
+                LogLevel::from_str(level).map_err(|_| Error::UnknownLogLevel(level.clone()))
+            })
+            .transpose()
+    }
+}
+
+impl Default for NodeRole {
+    fn default() -> Self {
+        Self::Passive
+    }
+}
+
+impl Topology {
+    pub fn new(leaders: usize, passive: usize) -> Self {
+        Self {
+            leaders,
+            passive,
+            ..Default::default()
+        }
+    }
+
+    /// Nodes definitions. If no explicit list is given, leaders are named `Leader1..N`
+    /// and each one trusts all previous leaders. First passive node is named
+    /// `Wallet_Node`, the rest `Passive1..N`, and all of them trust every leader.
+    pub fn nodes(&self) -> Vec<NodeSetup> {
+        if !self.nodes.is_empty() {
+            return self.nodes.clone();
+        }
+
+        let leaders: Vec<String> = (1..=self.leaders).map(|i| format!("Leader{}", i)).collect();
+
+        let mut nodes: Vec<NodeSetup> = leaders
+            .iter()
+            .enumerate()
+            .map(|(idx, alias)| NodeSetup {
+                alias: alias.clone(),
+                role: NodeRole::Leader,
+                trusted_peers: leaders[..idx].to_vec(),
+                log_level: None,
+                persistent: true,
+            })
+            .collect();
+
+        nodes.extend((0..self.passive).map(|idx| NodeSetup {
+            alias: if idx == 0 {
+                WALLET_NODE.to_string()
+            } else {
+                format!("Passive{}", idx)
+            },
+            role: NodeRole::Passive,
+            trusted_peers: leaders.clone(),
+            log_level: None,
+            persistent: true,
+        }));
+        nodes
+    }
+
+    pub fn leaders_aliases(&self) -> Vec<String> {
+        self.nodes()
+            .into_iter()
+            .filter(|node| node.role == NodeRole::Leader)
+            .map(|node| node.alias)
+            .collect()
+    }
+
+    /// Node which wallet proxy forwards node requests to. Defaults to first passive node
+    /// or first leader if network has no passive nodes.
+    pub fn wallet_proxy_node(&self) -> String {
+        if let Some(alias) = &self.wallet_proxy_node {
+            return alias.clone();
+        }
+        let nodes = self.nodes();
+        nodes
+            .iter()
+            .find(|node| node.role == NodeRole::Passive)
+            .or_else(|| nodes.first())
+            .map(|node| node.alias.clone())
+            .unwrap_or_else(|| LEADER_1.to_string())
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        let nodes = self.nodes();
+        if !nodes.iter().any(|node| node.role == NodeRole::Leader) {
+            return Err(Error::NoLeaders);
+        }
+
+        let mut aliases = HashSet::new();
+        for node in &nodes {
+            if !aliases.insert(node.alias.clone()) {
+                return Err(Error::DuplicatedAlias(node.alias.clone()));
+            }
+        }
+
+        for node in &nodes {
+            node.log_level()?;
+            if let Some(peer) = node
+                .trusted_peers
+                .iter()
+                .find(|peer| !aliases.contains(*peer) || *peer == &node.alias)
+            {
+                return Err(Error::UnknownTrustedPeer {
+                    alias: node.alias.clone(),
+                    peer: peer.clone(),
+                });
+            }
+        }
+
+        let wallet_proxy_node = self.wallet_proxy_node();
+        if !aliases.contains(&wallet_proxy_node) {
+            return Err(Error::UnknownWalletProxyNode(wallet_proxy_node));
+        }
+        Ok(())
+    }
+}
+
+fn default_leaders() -> usize {
+    3
+}
+
+fn default_passive() -> usize {
+    1
+}
+
+fn default_persistent() -> bool {
+    true
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("topology should contain at least one leader")]
+    NoLeaders,
+    #[error("node alias '{0}' is defined more than once")]
+    DuplicatedAlias(String),
+    #[error("node '{alias}' has unknown trusted peer '{peer}'")]
+    UnknownTrustedPeer { alias: String, peer: String },
+    #[error("unknown log level: {0}")]
+    UnknownLogLevel(String),
+    #[error("wallet proxy node '{0}' is not defined in topology")]
+    UnknownWalletProxyNode(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::{LEADER_2, LEADER_3};
+
+    #[test]
+    fn default_topology_matches_legacy_layout() {
+        let topology = Topology::default();
+        let nodes = topology.nodes();
+
+        assert_eq!(
+            nodes.iter().map(|n| n.alias.as_str()).collect::<Vec<_>>(),
+            vec![LEADER_1, LEADER_2, LEADER_3, WALLET_NODE]
+        );
+        assert_eq!(nodes[2].trusted_peers, vec![LEADER_1, LEADER_2]);
+        assert_eq!(nodes[3].trusted_peers, vec![LEADER_1, LEADER_2, LEADER_3]);
+        assert_eq!(topology.wallet_proxy_node(), WALLET_NODE);
+        assert!(topology.validate().is_ok());
+    }
+
+    #[test]
+    fn single_node_topology_points_proxy_to_leader() {
+        let topology = Topology::new(1, 0);
+        assert_eq!(topology.nodes().len(), 1);
+        assert_eq!(topology.wallet_proxy_node(), LEADER_1);
+        assert!(topology.validate().is_ok());
+    }
+
+    #[test]
+    fn unknown_trusted_peer_is_rejected() {
+        let topology = Topology {
+            nodes: vec![NodeSetup {
+                alias: "a".to_string(),
+                role: NodeRole::Leader,
+                trusted_peers: vec!["b".to_string()],
+                log_level: None,
+                persistent: false,
+            }],
+            ..Default::default()
+        };
+        assert!(matches!(
+            topology.validate(),
+            Err(Error::UnknownTrustedPeer { .. })
+        ));
+    }
+}
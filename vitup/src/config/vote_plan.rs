@@ -1,5 +1,7 @@
-use crate::config::VoteTime;
+use crate::config::{Role, VoteTime};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use vit_servicing_station_lib::db::models::vote_options::VoteOptions;
 
 #[derive(Clone, Debug, Deserialize, Serialize, Default)]
 pub struct VotePlan {
@@ -7,4 +9,68 @@ pub struct VotePlan {
     pub vote_time: VoteTime,
     #[serde(default)]
     pub private: bool,
+    /// Vote plans per challenge or category. If empty, all `data.current_fund.proposals`
+    /// are split into vote plans named after the fund.
+    #[serde(default)]
+    pub definitions: Vec<VotePlanDefinition>,
+    /// election committee file produced by `vitup generate election-committee`. Its member
+    /// keys are used by private vote plans and its committee ids are added to block0
+    #[serde(default)]
+    pub election_committee: Option<PathBuf>,
+}
+
+/// Single challenge (or category) vote plan. Settings which are not defined
+/// are taken from fund-wide configuration.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct VotePlanDefinition {
+    pub name: String,
+    pub proposals: usize,
+    /// comma separated vote options, e.g. "yes,no,abstain"
+    #[serde(default)]
+    pub options: Option<String>,
+    #[serde(default)]
+    pub private: Option<bool>,
+    /// voting token role. If not defined, vote plan is created for each group in fund
+    #[serde(default)]
+    pub role: Option<Role>,
+    /// committee wallet alias which owns and tallies vote plan
+    #[serde(default)]
+    pub committee: Option<String>,
+}
+
+impl VotePlanDefinition {
+    pub fn options(&self, default: &VoteOptions) -> VoteOptions {
+        self.options
+            .as_ref()
+            .map(|options| VoteOptions::parse_coma_separated_value(options))
+            .unwrap_or_else(|| default.clone())
+    }
+
+    pub fn private(&self, default: bool) -> bool {
+        self.private.unwrap_or(default)
+    }
+}
+
+impl VotePlan {
+    /// Whether any of defined vote plans is private
+    pub fn any_private(&self) -> bool {
+        self.private
+            || self
+                .definitions
+                .iter()
+                .any(|definition| definition.private(self.private))
+    }
+
+    /// Committee aliases used by vote plan definitions, apart from default one
+    pub fn committees(&self, default: &str) -> Vec<String> {
+        let mut committees: Vec<String> = self
+            .definitions
+            .iter()
+            .filter_map(|definition| definition.committee.clone())
+            .filter(|committee| committee != default)
+            .collect();
+        committees.sort();
+        committees.dedup();
+        committees
+    }
 }
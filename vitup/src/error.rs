@@ -71,6 +71,8 @@ pub enum Error {
     #[error(transparent)]
     ImportError(#[from] crate::cli::import::ImportError),
     #[error(transparent)]
+    Diff(#[from] crate::cli::DiffError),
+    #[error(transparent)]
     Validate(#[from] crate::cli::ValidateError),
     #[error(transparent)]
     ControllerError(#[from] hersir::controller::Error),
@@ -79,17 +81,41 @@ pub enum Error {
     #[error(transparent)]
     Builder(#[from] crate::builders::Error),
     #[error(transparent)]
+    Committee(#[from] crate::builders::CommitteeError),
+    #[error(transparent)]
+    Templates(#[from] crate::builders::TemplatesError),
+    #[error(transparent)]
+    Bundle(#[from] crate::builders::utils::BundleError),
+    #[error(transparent)]
     Certs(#[from] crate::config::certs::Error),
     #[error(transparent)]
+    ConfigLoader(#[from] crate::config::LoaderError),
+    #[error(transparent)]
+    Reviews(#[from] crate::config::ReviewsError),
+    #[error(transparent)]
+    TimePlan(#[from] crate::config::PlanError),
+    #[error(transparent)]
+    Migration(#[from] crate::config::MigrationError),
+    #[error(transparent)]
     Data(#[from] crate::mode::standard::DataError),
     #[error(transparent)]
     Main(#[from] crate::mode::standard::VitControllerError),
     #[error(transparent)]
+    Metrics(#[from] crate::mode::monitor::MetricsError),
+    #[error(transparent)]
+    InteractiveVote(#[from] crate::mode::interactive::VoteError),
+    #[error(transparent)]
+    Disruption(#[from] crate::mode::standard::DisruptionError),
+    #[error(transparent)]
     WalletProxyController(#[from] WalletProxyControllerError),
     #[error("Cannot find snapshot file in: {0}")]
     CannotFindSnapshotFile(PathBuf),
     #[error("Cannot find config in: {0}")]
     CannotFindConfig(PathBuf),
+    #[error("Cannot find database in: {0}")]
+    CannotFindDatabase(PathBuf),
+    #[error("configuration is required, use --config")]
+    ConfigRequired,
     #[error("synchronization for nodes has failed. {}. Timeout was: {} s", info, timeout.as_secs())]
     SyncTimeoutOccurred { info: String, timeout: Duration },
     #[error("{info}")]
@@ -111,6 +137,4 @@ pub enum Error {
     EnvironmentIsDown,
     #[error("wrong format for snapshot data")]
     SnapshotIntialReadError,
-    #[error("no challenge id found for proposal {proposal_id}")]
-    NoChallengeIdFound { proposal_id: String },
 }
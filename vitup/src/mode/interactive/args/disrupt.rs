@@ -0,0 +1,51 @@
+use super::super::VitInteractiveCommandExec;
+use crate::mode::standard::{send_signal, DisruptionAction, DisruptionError, Signal};
+use structopt::StructOpt;
+
+/// Kills, restarts, pauses or isolates node, vit station or wallet proxy
+#[derive(StructOpt, Debug)]
+pub struct Disrupt {
+    /// alias of node, vit station or wallet proxy, as printed by `show status`
+    #[structopt(short = "t", long = "target")]
+    pub target: String,
+    /// one of: kill, restart, pause, resume, isolate, reconnect
+    #[structopt(short = "a", long = "action")]
+    pub action: DisruptionAction,
+}
+
+impl Disrupt {
+    pub fn exec(&self, command: &mut VitInteractiveCommandExec) -> Result<(), DisruptionError> {
+        let target = self.target.as_str();
+        if let Some(result) = command.vit_controller.disrupt_service(target, self.action) {
+            return result.map(|()| println!("{} {}", self.action, target));
+        }
+
+        let node = command
+            .controller
+            .nodes_mut()
+            .iter_mut()
+            .find(|node| node.alias() == target)
+            .ok_or_else(|| DisruptionError::UnknownTarget(target.to_string()))?;
+
+        match self.action {
+            DisruptionAction::Kill => send_signal(node.pid(), Signal::Kill)?,
+            DisruptionAction::Pause => send_signal(node.pid(), Signal::Stop)?,
+            DisruptionAction::Resume => send_signal(node.pid(), Signal::Cont)?,
+            DisruptionAction::Restart | DisruptionAction::Isolate | DisruptionAction::Reconnect => {
+                let respawn = command.vit_controller.respawn_mut()?;
+                // node may be already down
+                let _ = send_signal(node.pid(), Signal::Kill);
+                let spawn_params = respawn
+                    .network_params
+                    .node_spawn_params(target, self.action == DisruptionAction::Isolate)
+                    .ok_or_else(|| DisruptionError::UnknownTarget(target.to_string()))?;
+                *node = respawn
+                    .controller
+                    .spawn_node(spawn_params)
+                    .map_err(|e| DisruptionError::Restart(target.to_string(), e.to_string()))?;
+            }
+        }
+        println!("{} {}", self.action, target);
+        Ok(())
+    }
+}
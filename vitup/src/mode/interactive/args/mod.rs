@@ -1,2 +1,4 @@
 pub mod describe;
+pub mod disrupt;
 pub mod show;
+pub mod vote;
@@ -1,4 +1,6 @@
 use super::super::VitInteractiveCommandExec;
+use super::vote::{node, Error as VoteError};
+use crate::Result;
 use chain_impl_mockchain::block::BlockDate;
 use hersir::controller::interactive::args::show::ShowStatus as BasicShowStatus;
 use jormungandr_lib::interfaces::BlockchainConfiguration;
@@ -24,13 +26,12 @@ pub enum Show {
     Logs(hersir::controller::interactive::args::show::ShowLogs),
     /// Active Vote Plans
     VotePlans(hersir::controller::interactive::args::show::ActiveVotePlans),
-    // TODO: not sure what to do here
-    //// Vote timing
-    // VoteTime(hersir::controller::interactive::args::show::VoteTimeStatus),
+    /// Vote timeline relative to current block date
+    VoteTime(VoteTimeStatus),
 }
 
 impl Show {
-    pub fn exec(&self, command: &mut VitInteractiveCommandExec) {
+    pub fn exec(&self, command: &mut VitInteractiveCommandExec) -> Result<()> {
         match self {
             Show::Status(status) => status.exec(command),
             Show::Stats(stats) => stats.exec(command.controller_mut()),
@@ -40,8 +41,9 @@ impl Show {
             Show::PeerStats(peer_stats) => peer_stats.exec(command.controller_mut()),
             Show::Logs(logs) => logs.exec(command.controller_mut()),
             Show::VotePlans(active_vote_plan) => active_vote_plan.exec(command.controller_mut()),
-            // Show::VoteTime(vote_status) => vote_status.exec(command)?,
+            Show::VoteTime(vote_status) => vote_status.exec(command)?,
         };
+        Ok(())
     }
 }
 
@@ -71,22 +73,29 @@ impl ShowStatus {
 
 #[derive(StructOpt, Debug)]
 pub struct VoteTimeStatus {
+    /// node which is queried for vote plans. By default first one
     #[structopt(short = "a", long = "alias")]
     pub alias: Option<String>,
 }
 
 impl VoteTimeStatus {
-    pub fn exec(&self, command: &mut VitInteractiveCommandExec) {
-        let controller = &command.controller_mut();
+    pub fn exec(
+        &self,
+        command: &mut VitInteractiveCommandExec,
+    ) -> std::result::Result<(), VoteError> {
+        let controller = &command.controller;
 
         let blockchain_configuration = &controller
             .controller()
             .settings()
             .block0
             .blockchain_configuration;
-        let node = controller.nodes().iter().next().unwrap();
-        let vote_plans = node.rest().vote_plan_statuses().unwrap();
-        let vote_plan = vote_plans.first().unwrap();
+        let node = node(controller, &self.alias)?;
+        let vote_plans = node.rest().vote_plan_statuses()?;
+        let vote_plan = vote_plans
+            .first()
+            .ok_or_else(|| VoteError::VotePlanNotActive("any".to_string()))?;
+        let now = OffsetDateTime::now_utc();
 
         let mut dates = vec![
             (
@@ -101,7 +110,7 @@ impl VoteTimeStatus {
                 "Tally period end",
                 self.calculate_date(blockchain_configuration, vote_plan.committee_end.into()),
             ),
-            ("> Current time", OffsetDateTime::now_utc()),
+            ("> Current time", now),
         ];
 
         dates.sort_by(|a, b| a.1.cmp(&b.1));
@@ -117,7 +126,30 @@ impl VoteTimeStatus {
         for (alias, date) in dates {
             println!("{}: {}", alias, date);
         }
+        println!(
+            "Current block date: {}",
+            self.calculate_block_date(blockchain_configuration, now)
+        );
         println!("======================================");
+        Ok(())
+    }
+
+    pub fn calculate_block_date(
+        &self,
+        blockchain_configuration: &BlockchainConfiguration,
+        date: OffsetDateTime,
+    ) -> BlockDate {
+        let slot_duration: u8 = blockchain_configuration.slot_duration.into();
+        let slots_per_epoch: u32 = blockchain_configuration.slots_per_epoch.into();
+        let epoch_duration = slot_duration as i64 * slots_per_epoch as i64;
+
+        let block0_date_secs = blockchain_configuration.block0_date.to_secs() as i64;
+        let elapsed = (date.unix_timestamp() - block0_date_secs).max(0);
+
+        BlockDate {
+            epoch: (elapsed / epoch_duration) as u32,
+            slot_id: ((elapsed % epoch_duration) / slot_duration as i64) as u32,
+        }
     }
 
     pub fn calculate_date(
@@ -139,3 +171,63 @@ impl VoteTimeStatus {
         OffsetDateTime::from_unix_timestamp(timestamp).unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chain_addr::Discrimination;
+    use chain_impl_mockchain::chaintypes::ConsensusVersion;
+    use chain_impl_mockchain::fee::LinearFee;
+    use jormungandr_lib::interfaces::{Block0Date, NumberOfSlotsPerEpoch, SlotDuration};
+
+    fn blockchain_configuration() -> BlockchainConfiguration {
+        let mut configuration = BlockchainConfiguration::new(
+            Discrimination::Production,
+            ConsensusVersion::Bft,
+            LinearFee::new(0, 0, 0),
+        );
+        configuration.block0_date = Block0Date(1_600_000_000);
+        configuration.slot_duration = SlotDuration::new(2).unwrap();
+        configuration.slots_per_epoch = NumberOfSlotsPerEpoch::new(60).unwrap();
+        configuration
+    }
+
+    fn at(secs: i64) -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp(secs).unwrap()
+    }
+
+    #[test]
+    fn block_date_is_calculated_from_block0_date() {
+        let status = VoteTimeStatus { alias: None };
+        let configuration = blockchain_configuration();
+
+        let block_date = |secs| status.calculate_block_date(&configuration, at(secs));
+        assert_eq!(block_date(1_600_000_000), BlockDate::first());
+        assert_eq!(
+            block_date(1_600_000_000 + 2 * 120 + 7),
+            BlockDate {
+                epoch: 2,
+                slot_id: 3
+            }
+        );
+        // dates before block0 are clamped to first block date
+        assert_eq!(block_date(1_500_000_000), BlockDate::first());
+    }
+
+    #[test]
+    fn block_date_and_date_conversions_roundtrip() {
+        let status = VoteTimeStatus { alias: None };
+        let configuration = blockchain_configuration();
+        let block_date = BlockDate {
+            epoch: 5,
+            slot_id: 59,
+        };
+
+        let date = status.calculate_date(&configuration, block_date);
+        assert_eq!(date, at(1_600_000_000 + 5 * 120 + 59 * 2));
+        assert_eq!(
+            status.calculate_block_date(&configuration, date),
+            block_date
+        );
+    }
+}
@@ -0,0 +1,367 @@
+use super::super::VitInteractiveCommandExec;
+use crate::builders::{CommitteeError, CommitteeMemberSecrets, ElectionCommitteeDef};
+use chain_impl_mockchain::certificate::DecryptedPrivateTally;
+use chain_impl_mockchain::key::Hash;
+use chain_impl_mockchain::testing::scenario::template::VotePlanDef;
+use chain_impl_mockchain::vote::Choice;
+use hersir::builder::VotePlanSettings;
+use hersir::controller::UserInteractionController;
+use jormungandr_automation::jormungandr::{JormungandrProcess, RestError};
+use jormungandr_lib::interfaces::{PrivateTallyState, Tally, VotePlanStatus};
+use rand_chacha::ChaChaRng;
+use rand_core::SeedableRng;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use structopt::StructOpt;
+use thiserror::Error;
+use thor::FragmentSender;
+
+#[derive(StructOpt, Debug)]
+pub enum Vote {
+    /// Casts vote from defined wallet
+    Cast(VoteCast),
+    /// Sends tally as committee member. Private vote plans are decrypted
+    /// with secrets of election committee members from configuration
+    Tally(VoteTally),
+    /// Prints votes cast and results per proposal
+    Results(VoteResults),
+}
+
+impl Vote {
+    pub fn exec(&self, command: &mut VitInteractiveCommandExec) -> Result<(), Error> {
+        match self {
+            Vote::Cast(cast) => cast.exec(command),
+            Vote::Tally(tally) => tally.exec(command),
+            Vote::Results(results) => results.exec(command),
+        }
+    }
+}
+
+#[derive(StructOpt, Debug)]
+pub struct VoteCast {
+    /// wallet alias, as printed by `describe wallets`
+    #[structopt(short = "w", long = "wallet")]
+    pub wallet: String,
+    /// vote plan alias, as printed by `describe vote-plan`
+    #[structopt(short = "v", long = "vote-plan")]
+    pub vote_plan: String,
+    /// proposal index in vote plan
+    #[structopt(short = "p", long = "proposal")]
+    pub proposal: u8,
+    /// index of chosen option, e.g. 0 for yes and 1 for no
+    #[structopt(short = "c", long = "choice")]
+    pub choice: u8,
+    /// node which receives fragment. By default first one
+    #[structopt(short = "n", long = "node")]
+    pub node: Option<String>,
+}
+
+impl VoteCast {
+    pub fn exec(&self, command: &mut VitInteractiveCommandExec) -> Result<(), Error> {
+        let controller = &command.controller;
+        let node = node(controller, &self.node)?;
+        let vote_plan = vote_plan(controller, &self.vote_plan)?;
+        let wallet = command
+            .vit_controller
+            .wallet(&self.wallet, controller.controller())?;
+
+        let check = FragmentSender::from(&controller.controller().settings().block0)
+            .send_vote_cast(
+                wallet,
+                &vote_plan.into(),
+                self.proposal,
+                &Choice::new(self.choice),
+                node,
+            )?;
+        println!(
+            "vote cast from {} sent to {}: {}",
+            self.wallet,
+            node.alias(),
+            check.fragment_id()
+        );
+        Ok(())
+    }
+}
+
+#[derive(StructOpt, Debug)]
+pub struct VoteTally {
+    /// vote plan alias. By default all vote plans are tallied
+    #[structopt(short = "v", long = "vote-plan")]
+    pub vote_plan: Option<String>,
+    /// committee wallet alias. By default owner of vote plan
+    #[structopt(short = "c", long = "committee")]
+    pub committee: Option<String>,
+    /// node which receives fragment. By default first one
+    #[structopt(short = "n", long = "node")]
+    pub node: Option<String>,
+    /// secrets files of election committee members. By default all secrets
+    /// exported next to `vote_plan.election_committee` file are used
+    #[structopt(short = "s", long = "member-secrets")]
+    pub member_secrets: Vec<PathBuf>,
+}
+
+impl VoteTally {
+    pub fn exec(&self, command: &mut VitInteractiveCommandExec) -> Result<(), Error> {
+        let controller = &command.controller;
+        let node = node(controller, &self.node)?;
+        let settings = controller.controller().settings();
+        let fragment_sender = FragmentSender::from(&settings.block0);
+        let statuses = node.rest().vote_plan_statuses()?;
+        let election_committee = command
+            .vit_controller
+            .election_committee()
+            .map(Path::to_path_buf);
+
+        for (key, vote_plan_settings) in settings.vote_plans.iter() {
+            if matches!(&self.vote_plan, Some(alias) if *alias != key.alias) {
+                continue;
+            }
+            let vote_plan = controller.controller().defined_vote_plan(&key.alias)?;
+            let committee = self.committee.as_ref().unwrap_or(&key.owner_alias);
+            let wallet = command
+                .vit_controller
+                .wallet(committee, controller.controller())?;
+
+            let check = match vote_plan_settings {
+                VotePlanSettings::Public(_) => {
+                    fragment_sender.send_public_vote_tally(wallet, &vote_plan.into(), node)?
+                }
+                VotePlanSettings::Private { keys, .. } => {
+                    let status = vote_plan_status(&statuses, &vote_plan)?;
+                    let shares = match &election_committee {
+                        Some(committee_file) => self.decrypt_tally(committee_file, status),
+                        // without external committee, member keys were generated by hersir
+                        None => keys
+                            .decrypt_tally(&status.clone().into())
+                            .map_err(|e| CommitteeError::Tally(e.to_string())),
+                    }
+                    .map_err(|e| Error::CannotDecryptTally(key.alias.clone(), e.to_string()))?;
+                    fragment_sender.send_private_vote_tally(
+                        wallet,
+                        &vote_plan.into(),
+                        shares,
+                        node,
+                    )?
+                }
+            };
+            println!(
+                "tally of {} sent by {}: {}",
+                key.alias,
+                committee,
+                check.fragment_id()
+            );
+        }
+        Ok(())
+    }
+
+    fn decrypt_tally(
+        &self,
+        committee_file: &Path,
+        status: &VotePlanStatus,
+    ) -> Result<DecryptedPrivateTally, CommitteeError> {
+        let committee = ElectionCommitteeDef::from_file(committee_file)?;
+        let secrets = self.member_secrets(&committee, committee_file)?;
+        committee.decrypt_tally(
+            &status.clone().into(),
+            &secrets,
+            &mut ChaChaRng::from_entropy(),
+        )
+    }
+
+    /// Secrets given with `--member-secrets` or, if none, all secrets exported next to
+    /// committee file. Ordered as member keys of committee
+    fn member_secrets(
+        &self,
+        committee: &ElectionCommitteeDef,
+        committee_file: &Path,
+    ) -> Result<Vec<CommitteeMemberSecrets>, CommitteeError> {
+        if self.member_secrets.is_empty() {
+            return committee.member_secrets(committee_file);
+        }
+        let secrets = self
+            .member_secrets
+            .iter()
+            .map(CommitteeMemberSecrets::from_file)
+            .collect::<Result<Vec<_>, _>>()?;
+        committee.order_secrets(secrets)
+    }
+}
+
+#[derive(StructOpt, Debug)]
+pub struct VoteResults {
+    /// vote plan alias. By default results of all vote plans are printed
+    #[structopt(short = "v", long = "vote-plan")]
+    pub vote_plan: Option<String>,
+    /// node which is queried. By default first one
+    #[structopt(short = "n", long = "node")]
+    pub node: Option<String>,
+}
+
+impl VoteResults {
+    pub fn exec(&self, command: &mut VitInteractiveCommandExec) -> Result<(), Error> {
+        let controller = &command.controller;
+        let node = node(controller, &self.node)?;
+        let statuses = node.rest().vote_plan_statuses()?;
+
+        for vote_plan in controller.controller().defined_vote_plans() {
+            if matches!(&self.vote_plan, Some(alias) if *alias != vote_plan.alias()) {
+                continue;
+            }
+            let status = vote_plan_status(&statuses, &vote_plan)?;
+            println!("======================================");
+            println!("{} ({})", vote_plan.alias(), vote_plan.id());
+            for proposal in &status.proposals {
+                let results = match &proposal.tally {
+                    Tally::Public { result } => format!("{:?}", result.results),
+                    Tally::Private {
+                        state: PrivateTallyState::Decrypted { result },
+                    } => format!("{:?}", result.results),
+                    Tally::Private {
+                        state: PrivateTallyState::Encrypted { .. },
+                    } => "encrypted".to_string(),
+                };
+                println!(
+                    "\t#{} {}: votes cast: {}, results: {}",
+                    proposal.index, proposal.proposal_id, proposal.votes_cast, results
+                );
+            }
+        }
+        println!("======================================");
+        Ok(())
+    }
+}
+
+pub(super) fn node<'a>(
+    controller: &'a UserInteractionController,
+    alias: &Option<String>,
+) -> Result<&'a JormungandrProcess, Error> {
+    match alias {
+        Some(alias) => controller
+            .nodes()
+            .iter()
+            .find(|node| node.alias() == alias.as_str())
+            .ok_or_else(|| Error::NodeNotFound(alias.clone())),
+        None => controller.nodes().iter().next().ok_or(Error::NoNodes),
+    }
+}
+
+fn vote_plan(controller: &UserInteractionController, alias: &str) -> Result<VotePlanDef, Error> {
+    controller
+        .controller()
+        .defined_vote_plan(alias)
+        .map_err(|_| Error::VotePlanNotFound(alias.to_string()))
+}
+
+fn vote_plan_status<'a>(
+    statuses: &'a [VotePlanStatus],
+    vote_plan: &VotePlanDef,
+) -> Result<&'a VotePlanStatus, Error> {
+    let id = Hash::from_str(&vote_plan.id()).unwrap();
+    statuses
+        .iter()
+        .find(|status| status.id == id.into())
+        .ok_or_else(|| Error::VotePlanNotActive(vote_plan.alias()))
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("no node is running")]
+    NoNodes,
+    #[error("node '{0}' not found")]
+    NodeNotFound(String),
+    #[error("wallet '{0}' not found")]
+    WalletNotFound(String),
+    #[error("vote plan '{0}' not found")]
+    VotePlanNotFound(String),
+    #[error("vote plan '{0}' is not active on node")]
+    VotePlanNotActive(String),
+    #[error("cannot decrypt tally of '{0}': {1}")]
+    CannotDecryptTally(String, String),
+    #[error(transparent)]
+    Rest(#[from] RestError),
+    #[error(transparent)]
+    FragmentSender(#[from] thor::FragmentSenderError),
+    #[error(transparent)]
+    Controller(#[from] hersir::controller::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::ElectionCommittee;
+    use assert_fs::TempDir;
+
+    fn write_secrets(path: PathBuf, secrets: &CommitteeMemberSecrets) -> PathBuf {
+        std::fs::write(&path, serde_json::to_string(secrets).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn tally_uses_given_member_secrets() {
+        let mut rng = ChaChaRng::seed_from_u64(1);
+        let committee = ElectionCommittee::generate("member", 2, 1, b"crs", &mut rng).unwrap();
+        let def = committee.def();
+        let dir = TempDir::new().unwrap();
+        let committee_file = dir.path().join("committee.json");
+        std::fs::write(&committee_file, serde_json::to_string(&def).unwrap()).unwrap();
+
+        // exported secrets next to committee file and copies handed over by members
+        let given_dir = TempDir::new().unwrap();
+        let mut given = Vec::new();
+        for secrets in committee.secrets() {
+            write_secrets(
+                dir.path().join(format!("{}_secrets.json", secrets.alias)),
+                &secrets,
+            );
+            let copy = CommitteeMemberSecrets {
+                alias: format!("given_{}", secrets.alias),
+                ..secrets
+            };
+            given.push(write_secrets(
+                given_dir.path().join(format!("{}.json", copy.alias)),
+                &copy,
+            ));
+        }
+
+        let tally = |secrets: &[PathBuf]| {
+            let mut args = vec!["tally".to_string()];
+            for path in secrets {
+                args.push("-s".to_string());
+                args.push(path.display().to_string());
+            }
+            VoteTally::from_iter_safe(&args).unwrap()
+        };
+        let aliases = |secrets: Vec<CommitteeMemberSecrets>| -> Vec<String> {
+            secrets.into_iter().map(|secrets| secrets.alias).collect()
+        };
+
+        let given_in_reverse: Vec<PathBuf> = given.iter().rev().cloned().collect();
+        let loaded = tally(&given_in_reverse)
+            .member_secrets(&def, &committee_file)
+            .unwrap();
+        assert_eq!(aliases(loaded), vec!["given_member_1", "given_member_2"]);
+
+        let loaded = tally(&[]).member_secrets(&def, &committee_file).unwrap();
+        assert_eq!(aliases(loaded), vec!["member_1", "member_2"]);
+
+        assert!(matches!(
+            tally(&given[1..]).member_secrets(&def, &committee_file),
+            Err(CommitteeError::MissingMemberSecrets { index: 0 })
+        ));
+    }
+
+    #[test]
+    fn cast_args_require_wallet_vote_plan_proposal_and_choice() {
+        let cast = VoteCast::from_iter_safe(&[
+            "cast", "-w", "alice", "-v", "fund_1", "-p", "2", "-c", "1",
+        ])
+        .unwrap();
+        assert_eq!(cast.wallet, "alice");
+        assert_eq!(cast.vote_plan, "fund_1");
+        assert_eq!(cast.proposal, 2);
+        assert_eq!(cast.choice, 1);
+        assert_eq!(cast.node, None);
+
+        assert!(VoteCast::from_iter_safe(&["cast", "-w", "alice", "-v", "fund_1"]).is_err());
+    }
+}
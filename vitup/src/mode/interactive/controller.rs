@@ -1,8 +1,27 @@
-use crate::mode::standard::{VitStationController, WalletProxyController};
+use super::args::vote::Error as VoteError;
+use crate::mode::spawn::NetworkSpawnParams;
+use crate::mode::standard::{
+    send_signal, DisruptionAction, DisruptionError, Signal, VitController, VitStationController,
+    WalletProxyController,
+};
+use hersir::controller::Controller;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use thor::Wallet;
 
 pub struct VitUserInteractionController {
     vit_stations: Vec<VitStationController>,
     proxies: Vec<WalletProxyController>,
+    wallets: HashMap<String, Wallet>,
+    respawn: Option<RespawnSettings>,
+    election_committee: Option<PathBuf>,
+}
+
+/// Settings needed to start processes of deployment again after disruption
+pub struct RespawnSettings {
+    pub controller: VitController,
+    pub network_params: NetworkSpawnParams,
+    pub version: String,
 }
 
 impl Default for VitUserInteractionController {
@@ -16,6 +35,9 @@ impl VitUserInteractionController {
         Self {
             vit_stations: Vec::new(),
             proxies: Vec::new(),
+            wallets: HashMap::new(),
+            respawn: None,
+            election_committee: None,
         }
     }
 
@@ -35,6 +57,114 @@ impl VitUserInteractionController {
         &mut self.proxies
     }
 
+    /// Wallet used by interactive commands. It is kept between commands, so spending
+    /// counter follows fragments already sent from it
+    pub fn wallet(
+        &mut self,
+        alias: &str,
+        controller: &Controller,
+    ) -> Result<&mut Wallet, VoteError> {
+        if !self.wallets.contains_key(alias) {
+            let wallet = controller
+                .controlled_wallet(alias)
+                .ok_or_else(|| VoteError::WalletNotFound(alias.to_string()))?;
+            self.wallets.insert(alias.to_string(), wallet);
+        }
+        Ok(self.wallets.get_mut(alias).unwrap())
+    }
+
+    /// Committee file from `vote_plan.election_committee`. Private tallies are
+    /// decrypted with secrets of its members
+    pub fn set_election_committee(&mut self, election_committee: Option<PathBuf>) {
+        self.election_committee = election_committee;
+    }
+
+    pub fn election_committee(&self) -> Option<&Path> {
+        self.election_committee.as_deref()
+    }
+
+    pub fn set_respawn_settings(&mut self, respawn: RespawnSettings) {
+        self.respawn = Some(respawn);
+    }
+
+    pub fn respawn_mut(&mut self) -> Result<&mut RespawnSettings, DisruptionError> {
+        self.respawn
+            .as_mut()
+            .ok_or(DisruptionError::NoRunningDeployment)
+    }
+
+    /// Applies disruption to vit station or wallet proxy. Returns `None` if there is
+    /// no such service, so target may be node
+    pub fn disrupt_service(
+        &mut self,
+        target: &str,
+        action: DisruptionAction,
+    ) -> Option<Result<(), DisruptionError>> {
+        let unsupported = || DisruptionError::UnsupportedAction {
+            target: target.to_string(),
+            action,
+        };
+        let restart_error =
+            |e: crate::error::Error| DisruptionError::Restart(target.to_string(), e.to_string());
+
+        if let Some(vit_station) = self
+            .vit_stations
+            .iter_mut()
+            .find(|vit_station| vit_station.alias() == target)
+        {
+            return Some(match action {
+                DisruptionAction::Kill => {
+                    vit_station.shutdown();
+                    Ok(())
+                }
+                DisruptionAction::Pause => send_signal(vit_station.pid(), Signal::Stop),
+                DisruptionAction::Resume => send_signal(vit_station.pid(), Signal::Cont),
+                DisruptionAction::Restart => self
+                    .respawn
+                    .as_ref()
+                    .ok_or(DisruptionError::NoRunningDeployment)
+                    .and_then(|respawn| {
+                        vit_station.shutdown();
+                        *vit_station = respawn
+                            .controller
+                            .start_vit_station(respawn.version.clone())
+                            .map_err(restart_error)?;
+                        Ok(())
+                    }),
+                DisruptionAction::Isolate | DisruptionAction::Reconnect => Err(unsupported()),
+            });
+        }
+
+        if let Some(proxy) = self
+            .proxies
+            .iter_mut()
+            .find(|proxy| proxy.alias() == target)
+        {
+            return Some(match action {
+                DisruptionAction::Kill => {
+                    proxy.shutdown();
+                    Ok(())
+                }
+                DisruptionAction::Pause => send_signal(proxy.pid(), Signal::Stop),
+                DisruptionAction::Resume => send_signal(proxy.pid(), Signal::Cont),
+                DisruptionAction::Restart => self
+                    .respawn
+                    .as_ref()
+                    .ok_or(DisruptionError::NoRunningDeployment)
+                    .and_then(|respawn| {
+                        proxy.shutdown();
+                        *proxy = respawn
+                            .controller
+                            .spawn_wallet_proxy_custom(&mut respawn.network_params.proxy_params())
+                            .map_err(restart_error)?;
+                        Ok(())
+                    }),
+                DisruptionAction::Isolate | DisruptionAction::Reconnect => Err(unsupported()),
+            });
+        }
+        None
+    }
+
     pub fn finalize(self) {
         for mut proxy in self.proxies {
             proxy.shutdown();
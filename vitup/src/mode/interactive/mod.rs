@@ -1,8 +1,9 @@
 mod args;
 mod controller;
 
-pub use args::{describe, show};
-pub use controller::VitUserInteractionController;
+pub use args::vote::Error as VoteError;
+pub use args::{describe, disrupt, show, vote};
+pub use controller::{RespawnSettings, VitUserInteractionController};
 use hersir::controller::interactive::args::explorer;
 use hersir::controller::interactive::args::send;
 use hersir::controller::UserInteractionController;
@@ -45,12 +46,13 @@ impl InteractiveCommandExec for VitInteractiveCommandExec {
             Ok(interactive) => {
                 if let Err(err) = {
                     match interactive {
-                        VitInteractiveCommand::Show(show) => {
-                            show.exec(self);
-                            Ok(())
-                        }
+                        VitInteractiveCommand::Show(show) => show.exec(self),
                         VitInteractiveCommand::Exit => Ok(()),
                         VitInteractiveCommand::Describe(describe) => describe.exec(self),
+                        VitInteractiveCommand::Vote(vote) => vote.exec(self).map_err(Into::into),
+                        VitInteractiveCommand::Disrupt(disrupt) => {
+                            disrupt.exec(self).map_err(Into::into)
+                        }
                         VitInteractiveCommand::Send(send) => {
                             send.exec(self.controller_mut()).map_err(Into::into)
                         }
@@ -81,4 +83,8 @@ pub enum VitInteractiveCommand {
     Describe(describe::Describe),
     /// send fragments
     Send(send::Send),
+    /// Casts votes, tallies and prints results
+    Vote(vote::Vote),
+    /// Kills, restarts, pauses or isolates node, vit station or wallet proxy
+    Disrupt(disrupt::Disrupt),
 }
@@ -1,3 +1,4 @@
+use crate::config::TlsSettings;
 use crate::Result;
 use assert_fs::TempDir;
 use serde::{Deserialize, Serialize};
@@ -12,6 +13,8 @@ pub struct Config {
     pub working_directory: PathBuf,
     pub protocol: Protocol,
     pub local: bool,
+    #[serde(default)]
+    pub tls: TlsSettings,
 }
 
 impl Default for Config {
@@ -22,6 +25,7 @@ impl Default for Config {
             working_directory: TempDir::new().unwrap().into_persistent().to_path_buf(),
             protocol: Default::default(),
             local: true,
+            tls: Default::default(),
         }
     }
 }
@@ -92,7 +92,7 @@ impl Context {
         }
 
         let mock_controller = MockBootstrap::new(id.clone())
-            .https()
+            .https(self.config.tls.clone())
             .working_directory(self.config.working_directory.clone())
             .spawn()?;
         let port = mock_controller.port();
@@ -103,7 +103,7 @@ impl Context {
     pub fn start_mock(&mut self, id: MockId, port: u16) -> Result<u16, Error> {
         let mock_controller = MockBootstrap::new(id.clone())
             .port(port)
-            .https()
+            .https(self.config.tls.clone())
             .working_directory(self.config.working_directory.clone())
             .spawn()?;
         let port = mock_controller.port();
@@ -1,6 +1,6 @@
 use crate::client::rest::VitupDisruptionRestClient;
 use crate::client::rest::VitupRest;
-use crate::config::CertificatesBuilder;
+use crate::config::{CertificatesBuilder, TlsSettings};
 use crate::mode::mock::config::write_config;
 use crate::mode::mock::farm::context::MockId;
 use crate::mode::mock::Configuration;
@@ -44,7 +44,7 @@ pub struct MockBootstrap {
     mock_id: MockId,
     configuration: Configuration,
     working_directory: PathBuf,
-    https: bool,
+    https: Option<TlsSettings>,
 }
 
 impl MockBootstrap {
@@ -59,7 +59,7 @@ impl MockBootstrap {
                 protocol: valgrind::Protocol::Http,
                 local: false,
             },
-            https: true,
+            https: Some(Default::default()),
             working_directory: PathBuf::new(),
         }
     }
@@ -74,14 +74,16 @@ impl MockBootstrap {
         self
     }
 
-    pub fn https(mut self) -> Self {
-        self.https = true;
+    pub fn https(mut self, tls: TlsSettings) -> Self {
+        self.https = Some(tls);
         self
     }
 
     pub fn spawn(mut self) -> Result<MockController, Error> {
-        if self.https {
-            let certs = CertificatesBuilder::default().build(&self.working_directory)?;
+        if let Some(tls) = &self.https {
+            let certs = CertificatesBuilder::default()
+                .settings(tls.clone())
+                .build(self.working_directory.join(&self.mock_id))?;
             self.configuration.protocol = certs.into();
         }
 
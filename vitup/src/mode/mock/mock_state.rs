@@ -1,20 +1,20 @@
 use super::{snapshot::VoterSnapshot, Configuration as MockConfig, LedgerState};
 use crate::builders::utils::SessionSettingsExtension;
-use crate::builders::VitBackendSettingsBuilder;
-use crate::config::Config;
+use crate::builders::{TemplatesGenerator, VitBackendSettingsBuilder};
 use crate::config::SnapshotError;
+use crate::config::{Config, RandomStream};
 use crate::mode::mock::NetworkCongestion;
 use crate::mode::mock::NetworkCongestionMode;
 use crate::mode::standard::VitController;
 use chain_impl_mockchain::testing::TestGen;
 use hersir::{builder::Wallet as WalletSettings, config::SessionSettings};
 use jormungandr_lib::interfaces::{NodeState, NodeStats, NodeStatsDto};
+use rand_chacha::ChaChaRng;
 use thiserror::Error;
 use thor::WalletAlias;
 use valgrind::VitVersion;
 use vit_servicing_station_lib::db::models::funds::Fund;
 use vit_servicing_station_tests::common::data::ArbitrarySnapshotGenerator;
-use vit_servicing_station_tests::common::data::ArbitraryValidVotingTemplateGenerator;
 use vit_servicing_station_tests::common::data::Snapshot;
 use vit_servicing_station_tests::common::data::ValidVotePlanGenerator;
 
@@ -30,6 +30,7 @@ pub struct MockState {
     network_congestion: NetworkCongestion,
     block_account_endpoint_counter: u32,
     controller: VitController,
+    snapshot_rng: ChaChaRng,
 }
 
 impl MockState {
@@ -38,7 +39,7 @@ impl MockState {
             std::fs::remove_dir_all(&config.working_dir)?;
         }
         let session_settings = SessionSettings::from_dir(&config.working_dir);
-        let mut template_generator = ArbitraryValidVotingTemplateGenerator::new();
+        let mut template_generator = TemplatesGenerator::from_config(&params).build()?;
         let (controller, vit_parameters) = VitBackendSettingsBuilder::default()
             .config(&params)
             .session_settings(session_settings)
@@ -46,6 +47,7 @@ impl MockState {
 
         let mut generator = ValidVotePlanGenerator::new(vit_parameters);
         let mut vit_state = generator.build(&mut template_generator);
+        controller.vote_plan_layout().apply(&mut vit_state);
         vit_state
             .funds_mut()
             .extend(ArbitrarySnapshotGenerator::default().funds());
@@ -60,6 +62,7 @@ impl MockState {
                 .count() as i32;
         }
 
+        let mut snapshot_rng = params.rng(RandomStream::Snapshot);
         Ok(Self {
             available: true,
             error_code: 400,
@@ -72,10 +75,12 @@ impl MockState {
             voters: VoterSnapshot::from_config_or_default(
                 controller.defined_wallets(),
                 &params.initials.snapshot,
+                &mut snapshot_rng,
             )?,
             block0_bin: jortestkit::file::get_file_as_byte_vec(controller.block0_file())?,
             block_account_endpoint_counter: 0,
             controller,
+            snapshot_rng,
         })
     }
 
@@ -93,6 +98,13 @@ impl MockState {
         self.controller.defined_wallets()
     }
 
+    /// Wallets together with the seeded random stream used for snapshot generation
+    pub fn defined_wallets_and_snapshot_rng(
+        &mut self,
+    ) -> (Vec<(WalletAlias, &WalletSettings)>, &mut ChaChaRng) {
+        (self.controller.defined_wallets(), &mut self.snapshot_rng)
+    }
+
     pub fn reset_block_account_endpoint(&mut self) {
         self.block_account_endpoint_counter = 0;
     }
@@ -226,6 +238,8 @@ pub enum Error {
     #[error(transparent)]
     Builder(#[from] crate::builders::Error),
     #[error(transparent)]
+    Templates(#[from] crate::builders::TemplatesError),
+    #[error(transparent)]
     Ledger(#[from] super::ledger_state::Error),
     #[error(transparent)]
     Io(#[from] std::io::Error),
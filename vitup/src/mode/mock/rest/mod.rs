@@ -152,7 +152,7 @@ pub async fn start_rest_server(context: ContextLock) -> Result<(), Error> {
                 .and_then(command_error_code);
 
             let fund = {
-                let root = warp::path!("fund");
+                let root = warp::path!("fund" / ..);
 
                 let fund_id = warp::path!("id" / i32)
                     .and(warp::post())
@@ -249,8 +249,7 @@ pub async fn start_rest_server(context: ContextLock) -> Result<(), Error> {
                         .or(pending)
                         .or(reset)
                         .or(update)
-                        .or(forget)
-                        .or(block_account),
+                        .or(forget),
                 )
                 .boxed()
             };
@@ -304,6 +303,7 @@ pub async fn start_rest_server(context: ContextLock) -> Result<(), Error> {
                     .or(availability)
                     .or(set_error_code)
                     .or(fund)
+                    .or(block_account)
                     .or(fragment_strategy)
                     .or(network_strategy)
                     .or(version)
@@ -1052,17 +1052,15 @@ async fn command_create_snapshot(
     config: SnapshotInitials,
     context: ContextLock,
 ) -> Result<impl Reply, Rejection> {
-    let context_lock = context.lock().unwrap();
-    let state = context_lock.state();
+    let mut context_lock = context.lock().unwrap();
+    let (defined_wallets, rng) = context_lock.state_mut().defined_wallets_and_snapshot_rng();
 
-    let voters_hirs = config
-        .as_voters_hirs(state.defined_wallets())
-        .map_err(|err| {
-            warp::reject::custom(GeneralException {
-                summary: err.to_string(),
-                code: 500,
-            })
-        })?;
+    let voters_hirs = config.as_voters_hirs(defined_wallets, rng).map_err(|err| {
+        warp::reject::custom(GeneralException {
+            summary: err.to_string(),
+            code: 500,
+        })
+    })?;
 
     Ok(HandlerResult(Ok(voters_hirs)))
 }
@@ -4,6 +4,7 @@ use jormungandr_lib::crypto::account::Identifier;
 use proptest::{
     arbitrary::Arbitrary, prelude::*, strategy::BoxedStrategy, test_runner::TestRunner,
 };
+use rand_chacha::ChaChaRng;
 use snapshot_lib::VoterHIR;
 use std::collections::BTreeMap;
 use thor::WalletAlias;
@@ -19,12 +20,13 @@ impl VoterSnapshot {
     pub fn from_config_or_default(
         defined_wallets: Vec<(WalletAlias, &WalletSettings)>,
         snapshot_config: &Option<SnapshotInitials>,
+        rng: &mut ChaChaRng,
     ) -> Result<Self, SnapshotError> {
         if let Some(snapshot_config) = snapshot_config {
             let mut snapshot = Self::default();
             snapshot.update_tag(
                 snapshot_config.tag.clone(),
-                snapshot_config.as_voters_hirs(defined_wallets)?,
+                snapshot_config.as_voters_hirs(defined_wallets, rng)?,
             );
             Ok(snapshot)
         } else {
@@ -1,4 +1,5 @@
-use super::{VitStationMonitorController, WalletProxyMonitorController};
+use super::{MetricsExporter, VitStationMonitorController, WalletProxyMonitorController};
+use crate::config::{Metrics, Topology};
 use crate::mode::standard::{
     ValidVotePlanParameters, ValidVotingTemplateGenerator, VitController as InnerController,
     WalletProxySpawnParams,
@@ -9,6 +10,9 @@ use hersir::controller::MonitorNode;
 use hersir::controller::ProgressBarController;
 use indicatif::MultiProgress;
 use indicatif::ProgressBar;
+use jormungandr_automation::jormungandr::NodeAlias;
+use std::net::SocketAddr;
+use std::path::Path;
 use std::sync::Arc;
 
 pub struct MonitorController {
@@ -56,6 +60,21 @@ impl MonitorController {
         self.progress_bar.add(pb)
     }
 
+    pub fn rest_addresses(&self, topology: &Topology) -> Vec<(NodeAlias, SocketAddr)> {
+        self.inner.rest_addresses(topology)
+    }
+
+    /// Exporter of periodic status snapshots of nodes defined in topology
+    pub fn metrics_exporter<P: AsRef<Path>>(
+        &self,
+        settings: &Metrics,
+        topology: &Topology,
+        working_dir: P,
+    ) -> Result<MetricsExporter> {
+        MetricsExporter::new(settings, self.rest_addresses(topology), working_dir)
+            .map_err(Into::into)
+    }
+
     pub fn spawn_vit_station(
         &mut self,
         vote_plan_parameters: ValidVotePlanParameters,
@@ -71,6 +90,15 @@ impl MonitorController {
         Ok(VitStationMonitorController::new(vit_station, progress_bar))
     }
 
+    /// Starts vit station again on its existing database
+    pub fn start_vit_station(&mut self, version: String) -> Result<VitStationMonitorController> {
+        let vit_station = self.inner.start_vit_station(version)?;
+        let progress_bar =
+            self.build_progress_bar(vit_station.alias(), vit_station.address().to_string());
+
+        Ok(VitStationMonitorController::new(vit_station, progress_bar))
+    }
+
     pub fn spawn_wallet_proxy_custom(
         &mut self,
         params: &mut WalletProxySpawnParams,
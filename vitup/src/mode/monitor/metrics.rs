@@ -0,0 +1,321 @@
+use super::{VitStationMonitorController, WalletProxyMonitorController};
+use crate::config::Metrics;
+use jormungandr_automation::jormungandr::{JormungandrRest, NodeAlias};
+use jormungandr_lib::interfaces::{NodeStats, PrivateTallyState, Tally, VotePlanStatus};
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use thiserror::Error;
+use time::OffsetDateTime;
+use tokio::runtime::Runtime;
+use warp::Filter;
+
+/// Single machine readable snapshot of deployment, one line in metrics file
+#[derive(Serialize, Debug)]
+pub struct MetricsSnapshot {
+    #[serde(with = "time::serde::rfc3339")]
+    pub timestamp: OffsetDateTime,
+    pub nodes: Vec<NodeMetrics>,
+    /// vote plans as seen by first responding node
+    pub vote_plans: Vec<VotePlanStatus>,
+    pub services: Vec<ServiceMetrics>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct NodeMetrics {
+    pub alias: NodeAlias,
+    pub up: bool,
+    pub stats: Option<NodeStats>,
+    pub fragments: Option<FragmentCounts>,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct FragmentCounts {
+    pub pending: usize,
+    pub in_block: usize,
+    pub rejected: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ServiceMetrics {
+    pub alias: String,
+    pub up: bool,
+}
+
+/// Collects snapshots of running deployment and writes them to JSON-lines file
+/// and/or exposes latest one on Prometheus endpoint
+pub struct MetricsExporter {
+    interval: std::time::Duration,
+    nodes: Vec<(NodeAlias, JormungandrRest)>,
+    file: Option<PathBuf>,
+    latest: Arc<Mutex<String>>,
+    last_export: Option<Instant>,
+    // keeps Prometheus endpoint alive
+    _runtime: Option<Runtime>,
+}
+
+impl MetricsExporter {
+    /// `nodes` are REST addresses of spawned nodes
+    pub fn new<P: AsRef<Path>>(
+        settings: &Metrics,
+        nodes: Vec<(NodeAlias, SocketAddr)>,
+        working_dir: P,
+    ) -> Result<Self, Error> {
+        let latest = Arc::new(Mutex::new(String::new()));
+
+        let runtime = match settings.prometheus {
+            Some(address) => {
+                let runtime = Runtime::new()?;
+                let latest = latest.clone();
+                let route = warp::path!("metrics").and(warp::get()).map(move || {
+                    warp::reply::with_header(
+                        latest.lock().unwrap().clone(),
+                        "content-type",
+                        "text/plain; version=0.0.4",
+                    )
+                });
+                let _guard = runtime.enter();
+                let (address, server) = warp::serve(route).try_bind_ephemeral(address)?;
+                runtime.spawn(server);
+                println!("metrics exposed on http://{}/metrics", address);
+                Some(runtime)
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            interval: settings.interval(),
+            nodes: rest_clients(nodes),
+            file: settings
+                .file
+                .as_ref()
+                .map(|file| working_dir.as_ref().join(file)),
+            latest,
+            last_export: None,
+            _runtime: runtime,
+        })
+    }
+
+    pub fn interval(&self) -> std::time::Duration {
+        self.interval
+    }
+
+    pub fn collect(
+        &self,
+        vit_station: &VitStationMonitorController,
+        wallet_proxy: &WalletProxyMonitorController,
+    ) -> MetricsSnapshot {
+        let (nodes, vote_plans) = collect_nodes(&self.nodes);
+
+        MetricsSnapshot {
+            timestamp: OffsetDateTime::now_utc(),
+            nodes,
+            vote_plans,
+            services: vec![
+                ServiceMetrics {
+                    alias: vit_station.alias(),
+                    up: vit_station.check_running(),
+                },
+                ServiceMetrics {
+                    alias: wallet_proxy.alias(),
+                    up: wallet_proxy.check_running(),
+                },
+            ],
+        }
+    }
+
+    pub fn export(&mut self, snapshot: &MetricsSnapshot) -> Result<(), Error> {
+        self.last_export = Some(Instant::now());
+        *self.latest.lock().unwrap() = prometheus_text(snapshot);
+
+        if let Some(file) = &self.file {
+            let mut file = OpenOptions::new().create(true).append(true).open(file)?;
+            writeln!(file, "{}", serde_json::to_string(snapshot)?)?;
+        }
+        Ok(())
+    }
+
+    /// Collects and exports snapshot if interval passed since last export. Errors are
+    /// only reported, so broken export does not stop long running deployment
+    pub fn tick(
+        &mut self,
+        vit_station: &VitStationMonitorController,
+        wallet_proxy: &WalletProxyMonitorController,
+    ) {
+        if matches!(self.last_export, Some(last) if last.elapsed() < self.interval) {
+            return;
+        }
+        let snapshot = self.collect(vit_station, wallet_proxy);
+        if let Err(e) = self.export(&snapshot) {
+            println!("cannot export metrics: {}", e);
+        }
+    }
+}
+
+/// REST clients of nodes listening on given addresses
+pub fn rest_clients(nodes: Vec<(NodeAlias, SocketAddr)>) -> Vec<(NodeAlias, JormungandrRest)> {
+    nodes
+        .into_iter()
+        .map(|(alias, address)| {
+            (
+                alias,
+                JormungandrRest::new(format!("http://{}/api", address)),
+            )
+        })
+        .collect()
+}
+
+/// Stats and fragment counts of each node together with vote plans as seen by
+/// first responding node. Nodes which do not respond are reported as down
+pub fn collect_nodes(
+    nodes: &[(NodeAlias, JormungandrRest)],
+) -> (Vec<NodeMetrics>, Vec<VotePlanStatus>) {
+    let mut vote_plans = None;
+    let nodes = nodes
+        .iter()
+        .map(|(alias, rest)| {
+            let stats = rest.stats().ok().and_then(|dto| dto.stats);
+            let fragments = rest.fragment_logs().ok().map(|logs| {
+                let mut counts = FragmentCounts::default();
+                for log in logs.values() {
+                    if log.is_pending() {
+                        counts.pending += 1;
+                    } else if log.is_in_a_block() {
+                        counts.in_block += 1;
+                    } else if log.is_rejected() {
+                        counts.rejected += 1;
+                    }
+                }
+                counts
+            });
+            if vote_plans.is_none() {
+                vote_plans = rest.vote_plan_statuses().ok();
+            }
+            NodeMetrics {
+                alias: alias.clone(),
+                up: stats.is_some(),
+                stats,
+                fragments,
+            }
+        })
+        .collect();
+    (nodes, vote_plans.unwrap_or_default())
+}
+
+/// Prometheus text exposition format
+pub fn prometheus_text(snapshot: &MetricsSnapshot) -> String {
+    let mut out = String::new();
+    let mut gauge = |name: &str, labels: &str, value: &dyn std::fmt::Display| {
+        writeln!(out, "vitup_{}{{{}}} {}", name, labels, value).unwrap();
+    };
+
+    for node in &snapshot.nodes {
+        let labels = format!("node=\"{}\"", node.alias);
+        gauge("node_up", &labels, &(node.up as u8));
+        if let Some(stats) = &node.stats {
+            if let Some(height) = &stats.last_block_height {
+                gauge("node_block_height", &labels, height);
+            }
+            gauge("node_blocks_received", &labels, &stats.block_recv_cnt);
+            gauge("node_tx_received", &labels, &stats.tx_recv_cnt);
+            gauge("node_tx_rejected", &labels, &stats.tx_rejected_cnt);
+            gauge("node_peers_connected", &labels, &stats.peer_connected_cnt);
+        }
+        if let Some(fragments) = &node.fragments {
+            for (status, count) in [
+                ("pending", fragments.pending),
+                ("in_block", fragments.in_block),
+                ("rejected", fragments.rejected),
+            ] {
+                gauge(
+                    "node_fragments",
+                    &format!("{},status=\"{}\"", labels, status),
+                    &count,
+                );
+            }
+        }
+    }
+
+    for vote_plan in &snapshot.vote_plans {
+        for proposal in &vote_plan.proposals {
+            let labels = format!(
+                "vote_plan=\"{}\",proposal=\"{}\"",
+                vote_plan.id, proposal.index
+            );
+            gauge("proposal_votes_cast", &labels, &proposal.votes_cast);
+            let results = match &proposal.tally {
+                Tally::Public { result } => Some(&result.results),
+                Tally::Private {
+                    state: PrivateTallyState::Decrypted { result },
+                } => Some(&result.results),
+                Tally::Private { .. } => None,
+            };
+            for (option, value) in results.into_iter().flatten().enumerate() {
+                gauge(
+                    "proposal_result",
+                    &format!("{},option=\"{}\"", labels, option),
+                    value,
+                );
+            }
+        }
+    }
+
+    for service in &snapshot.services {
+        gauge(
+            "service_up",
+            &format!("service=\"{}\"", service.alias),
+            &(service.up as u8),
+        );
+    }
+    out
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("cannot start metrics endpoint")]
+    Warp(#[from] warp::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_is_exported_in_prometheus_format() {
+        let snapshot = MetricsSnapshot {
+            timestamp: OffsetDateTime::now_utc(),
+            nodes: vec![NodeMetrics {
+                alias: "Leader1".to_string(),
+                up: false,
+                stats: None,
+                fragments: Some(FragmentCounts {
+                    pending: 1,
+                    in_block: 2,
+                    rejected: 0,
+                }),
+            }],
+            vote_plans: Vec::new(),
+            services: vec![ServiceMetrics {
+                alias: "vit_station".to_string(),
+                up: true,
+            }],
+        };
+
+        let text = prometheus_text(&snapshot);
+        assert!(text.contains("vitup_node_up{node=\"Leader1\"} 0\n"));
+        assert!(text.contains("vitup_node_fragments{node=\"Leader1\",status=\"in_block\"} 2\n"));
+        assert!(text.contains("vitup_service_up{service=\"vit_station\"} 1\n"));
+        assert!(serde_json::to_string(&snapshot)
+            .unwrap()
+            .starts_with("{\"timestamp\""));
+    }
+}
@@ -1,7 +1,12 @@
 mod main;
+mod metrics;
 mod vit_station;
 mod wallet;
 
 pub use main::MonitorController;
+pub use metrics::{
+    collect_nodes, rest_clients, Error as MetricsError, FragmentCounts, MetricsExporter,
+    MetricsSnapshot, NodeMetrics,
+};
 pub use vit_station::VitStationMonitorController;
 pub use wallet::WalletProxyMonitorController;
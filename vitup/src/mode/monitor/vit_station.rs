@@ -3,6 +3,7 @@ use crate::Result;
 use hersir::controller::ProgressBarController;
 use hersir::style;
 use jormungandr_automation::jormungandr::{NodeAlias, Status};
+use std::path::PathBuf;
 
 pub struct VitStationMonitorController {
     controller: VitStationController,
@@ -28,6 +29,10 @@ impl VitStationMonitorController {
         Ok(self.controller.status())
     }
 
+    pub fn db_path(&self) -> PathBuf {
+        self.controller.db_path()
+    }
+
     pub fn is_up(&self) -> bool {
         match self.status() {
             Ok(status) => status == Status::Running,
@@ -35,6 +40,11 @@ impl VitStationMonitorController {
         }
     }
 
+    /// Checks health endpoint, not only process status
+    pub fn check_running(&self) -> bool {
+        self.controller.check_running()
+    }
+
     pub fn finish_monitoring(&self) {
         self.progress_bar.finish_with_message("monitoring shutdown");
     }
@@ -43,6 +53,15 @@ impl VitStationMonitorController {
         &self.progress_bar
     }
 
+    pub fn pid(&self) -> u32 {
+        self.controller.pid()
+    }
+
+    /// Kills process without consuming controller, so it can be replaced by restarted one
+    pub fn kill(&mut self) {
+        self.controller.shutdown();
+    }
+
     pub fn shutdown(mut self) {
         self.controller.shutdown();
     }
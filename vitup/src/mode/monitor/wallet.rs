@@ -34,6 +34,11 @@ impl WalletProxyMonitorController {
         }
     }
 
+    /// Checks health endpoint, not only process status
+    pub fn check_running(&self) -> bool {
+        self.controller.check_running()
+    }
+
     pub fn finish_monitoring(&self) {
         self.progress_bar.finish_with_message("monitoring shutdown");
     }
@@ -42,6 +47,10 @@ impl WalletProxyMonitorController {
         &self.progress_bar
     }
 
+    pub fn pid(&self) -> u32 {
+        self.controller.pid()
+    }
+
     pub fn shutdown(&mut self) {
         self.controller.shutdown();
     }
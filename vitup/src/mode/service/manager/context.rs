@@ -1,34 +1,66 @@
 pub type ControlContextLock = Arc<Mutex<ControlContext>>;
+use super::job::{archive, load_history, save, Error as JobError, ExitReason, Job, JobStatus};
 use super::ServerStopper;
 use crate::config::Config;
+use crate::mode::standard::{
+    DataUpdate, Disruption, DisruptionError, DisruptionSchedule, UpdateError,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::ffi::OsString;
 use std::fmt;
 use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use uuid::Uuid;
 
 pub struct ControlContext {
     server_stopper: Option<ServerStopper>,
     setup: Config,
     address: SocketAddr,
     working_directory: PathBuf,
+    history_directory: PathBuf,
     state: State,
     should_stop: bool,
-    should_start: bool,
     api_token: Option<String>,
+    queue: VecDeque<Job>,
+    current: Option<Job>,
+    history: Vec<Job>,
+    vit_station_db: Option<PathBuf>,
+    disruptions: DisruptionSchedule,
 }
 
 impl ControlContext {
+    /// History of runs is kept next to working directory (`{working_dir}_history`),
+    /// since working directory is wiped before each run
     pub fn new<P: AsRef<Path>>(working_dir: P, setup: Config, api_token: Option<String>) -> Self {
+        let working_directory = working_dir.as_ref().to_path_buf();
+        let mut history_name = working_directory
+            .file_name()
+            .map(ToOwned::to_owned)
+            .unwrap_or_else(|| OsString::from("vit_backend"));
+        history_name.push("_history");
+        let history_directory = working_directory.with_file_name(history_name);
+
+        let history = load_history(&history_directory).unwrap_or_else(|e| {
+            println!("cannot load jobs history: {}", e);
+            Vec::new()
+        });
+
         Self {
             server_stopper: None,
             setup,
-            working_directory: working_dir.as_ref().to_path_buf(),
+            working_directory,
+            history_directory,
             address: ([0, 0, 0, 0], 3030).into(),
             state: State::Idle,
             should_stop: false,
-            should_start: false,
             api_token,
+            queue: VecDeque::new(),
+            current: None,
+            history,
+            vit_station_db: None,
+            disruptions: DisruptionSchedule::default(),
         }
     }
 
@@ -36,10 +68,6 @@ impl ControlContext {
         self.server_stopper = Some(server_stopper)
     }
 
-    pub fn set_parameters(&mut self, setup: Config) {
-        self.setup = setup;
-    }
-
     pub fn server_stopper(&self) -> &Option<ServerStopper> {
         &self.server_stopper
     }
@@ -52,6 +80,10 @@ impl ControlContext {
         &self.working_directory
     }
 
+    pub fn history_directory(&self) -> &PathBuf {
+        &self.history_directory
+    }
+
     pub fn setup(&self) -> &Config {
         &self.setup
     }
@@ -72,14 +104,6 @@ impl ControlContext {
         self.should_stop
     }
 
-    pub fn request_to_start(&self) -> bool {
-        self.should_start
-    }
-
-    pub fn start(&mut self) {
-        self.should_start = true;
-    }
-
     pub fn stop(&mut self) {
         self.should_stop = true;
     }
@@ -93,9 +117,127 @@ impl ControlContext {
     }
 
     pub fn clear_requests(&mut self) {
-        self.should_start = false;
         self.should_stop = false;
     }
+
+    /// Puts deployment request at the end of queue. Returns its job id
+    pub fn new_job(&mut self, config: Config) -> Uuid {
+        let job = Job::new(config);
+        let id = job.id;
+        self.queue.push_back(job);
+        id
+    }
+
+    /// Takes first job from queue, if no other job is in progress
+    pub fn start_next_job(&mut self) -> Option<Job> {
+        if self.current.is_some() || self.state != State::Idle {
+            return None;
+        }
+        let mut job = self.queue.pop_front()?;
+        job.started();
+        self.disruptions.clear();
+        self.current = Some(job.clone());
+        Some(job)
+    }
+
+    /// Archives current job together with artefacts from working directory and moves it
+    /// to history. Job is reported as finished only after its history folder is complete
+    pub fn finish_job(&mut self, exit_reason: ExitReason) -> Option<Job> {
+        let mut job = self.current.take()?;
+        job.finished(exit_reason);
+        match archive(&job, &self.working_directory, &self.history_directory) {
+            Ok(job_dir) => println!("job {} archived in {:?}", job.id, job_dir),
+            Err(e) => println!("cannot archive job {}: {}", job.id, e),
+        }
+        self.disruptions.clear_pending();
+        self.history.push(job.clone());
+        Some(job)
+    }
+
+    /// Removes queued job or requests stop of running one. Cancelled job is saved
+    /// in history, so it is still listed after service restart
+    pub fn cancel_job(&mut self, id: Uuid) -> Result<JobStatus, JobError> {
+        if let Some(position) = self.queue.iter().position(|job| job.id == id) {
+            let mut job = self.queue[position].clone();
+            job.finished(ExitReason::Cancelled);
+            save(&job, &self.history_directory)?;
+            self.queue.remove(position);
+            self.history.push(job);
+            return Ok(JobStatus::Finished);
+        }
+        match &self.current {
+            Some(job) if job.id == id => {
+                self.stop();
+                Ok(JobStatus::Running)
+            }
+            _ => Err(self
+                .history
+                .iter()
+                .find(|job| job.id == id)
+                .map(|_| JobError::JobFinished(id))
+                .unwrap_or(JobError::JobNotFound(id))),
+        }
+    }
+
+    /// Database of servicing station of current deployment, available while it is running
+    pub fn set_vit_station_db(&mut self, vit_station_db: Option<PathBuf>) {
+        self.vit_station_db = vit_station_db;
+    }
+
+    /// Changes static data of running deployment without restart. Configuration of
+    /// current job is replaced, so its history reflects data after update
+    pub fn update_data(&mut self, update: &DataUpdate) -> Result<(), UpdateError> {
+        let (job, db) = match (&mut self.current, &self.vit_station_db) {
+            (Some(job), Some(db)) if self.state == State::Running => (job, db),
+            _ => return Err(UpdateError::NoRunningDeployment),
+        };
+        job.config = update.apply(db, &job.config)?;
+        Ok(())
+    }
+
+    /// Schedules disruption of node, vit station or wallet proxy of running deployment.
+    /// Target is validated when disruption is applied
+    pub fn disrupt(&mut self, disruption: Disruption) -> Result<(), DisruptionError> {
+        if self.state != State::Running {
+            return Err(DisruptionError::NoRunningDeployment);
+        }
+        self.disruptions.schedule(disruption);
+        Ok(())
+    }
+
+    /// Pending and applied disruptions of current (or last) job
+    pub fn disruptions(&self) -> &DisruptionSchedule {
+        &self.disruptions
+    }
+
+    pub fn disruptions_mut(&mut self) -> &mut DisruptionSchedule {
+        &mut self.disruptions
+    }
+
+    /// Finished jobs, current one and queue, in that order
+    pub fn jobs(&self) -> Vec<&Job> {
+        self.history
+            .iter()
+            .chain(self.current.iter())
+            .chain(self.queue.iter())
+            .collect()
+    }
+
+    pub fn job(&self, id: Uuid) -> Result<&Job, JobError> {
+        self.jobs()
+            .into_iter()
+            .find(|job| job.id == id)
+            .ok_or(JobError::JobNotFound(id))
+    }
+
+    /// Folder with artefacts of given job. Running job uses working directory
+    pub fn job_directory(&self, id: Uuid) -> Result<PathBuf, JobError> {
+        match self.job(id)?.status {
+            JobStatus::Queued => Err(JobError::JobNotStarted(id)),
+            JobStatus::Running => Ok(self.working_directory.clone()),
+            JobStatus::Finished => Ok(self.history_directory.join(id.to_string())),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
@@ -111,3 +253,111 @@ impl fmt::Display for State {
         write!(f, "{:?}", self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+
+    fn context(dir: &TempDir) -> ControlContext {
+        ControlContext::new(dir.path().join("vit_backend"), Config::default(), None)
+    }
+
+    #[test]
+    fn jobs_are_started_in_order_one_at_a_time() {
+        let dir = TempDir::new().unwrap();
+        let mut context = context(&dir);
+        let first = context.new_job(Config::default());
+        let second = context.new_job(Config::default());
+        assert_eq!(context.job(second).unwrap().status, JobStatus::Queued);
+        assert!(matches!(
+            context.job_directory(second),
+            Err(JobError::JobNotStarted(_))
+        ));
+
+        assert_eq!(context.start_next_job().unwrap().id, first);
+        assert!(context.start_next_job().is_none());
+        assert_eq!(context.job(first).unwrap().status, JobStatus::Running);
+        assert_eq!(
+            context.job_directory(first).unwrap(),
+            *context.working_directory()
+        );
+
+        let finished = context.finish_job(ExitReason::Stopped).unwrap();
+        assert_eq!(finished.id, first);
+        assert_eq!(finished.status, JobStatus::Finished);
+        assert_eq!(
+            context.job_directory(first).unwrap(),
+            context.history_directory().join(first.to_string())
+        );
+
+        *context.state_mut() = State::Starting;
+        assert!(context.start_next_job().is_none());
+        *context.state_mut() = State::Idle;
+        assert_eq!(context.start_next_job().unwrap().id, second);
+
+        let ids: Vec<Uuid> = context.jobs().iter().map(|job| job.id).collect();
+        assert_eq!(ids, vec![first, second]);
+    }
+
+    #[test]
+    fn cancel_removes_queued_job_or_stops_running_one() {
+        let dir = TempDir::new().unwrap();
+        let mut context = context(&dir);
+        let running = context.new_job(Config::default());
+        let queued = context.new_job(Config::default());
+        context.start_next_job().unwrap();
+
+        assert_eq!(context.cancel_job(queued).unwrap(), JobStatus::Finished);
+        let cancelled = context.job(queued).unwrap();
+        assert_eq!(cancelled.status, JobStatus::Finished);
+        assert_eq!(cancelled.exit_reason, Some(ExitReason::Cancelled));
+        assert!(context.start_next_job().is_none());
+
+        assert!(!context.request_to_stop());
+        assert_eq!(context.cancel_job(running).unwrap(), JobStatus::Running);
+        assert!(context.request_to_stop());
+
+        assert!(matches!(
+            context.cancel_job(queued),
+            Err(JobError::JobFinished(_))
+        ));
+        assert!(matches!(
+            context.cancel_job(Uuid::new_v4()),
+            Err(JobError::JobNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn finished_and_cancelled_jobs_are_kept_after_restart() {
+        let dir = TempDir::new().unwrap();
+        let mut context = context(&dir);
+        let finished = context.new_job(Config::default());
+        let cancelled = context.new_job(Config::default());
+        context.start_next_job().unwrap();
+        context.cancel_job(cancelled).unwrap();
+
+        std::fs::create_dir_all(context.working_directory()).unwrap();
+        std::fs::write(context.working_directory().join("node.log"), "log").unwrap();
+        context.finish_job(ExitReason::Stopped).unwrap();
+        assert!(context
+            .job_directory(finished)
+            .unwrap()
+            .join("node.log")
+            .is_file());
+
+        let restarted = self::context(&dir);
+        let jobs: Vec<(Uuid, Option<ExitReason>)> = restarted
+            .jobs()
+            .iter()
+            .map(|job| (job.id, job.exit_reason.clone()))
+            .collect();
+        assert_eq!(
+            jobs,
+            vec![
+                (finished, Some(ExitReason::Stopped)),
+                (cancelled, Some(ExitReason::Cancelled)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use crate::config::Config;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+use thiserror::Error;
+use time::OffsetDateTime;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+pub const JOB_FILE: &str = "job.yaml";
+/// Number of most recent jobs which keep copy of their run artefacts in history.
+/// Older jobs keep only job description
+pub const ARCHIVED_RUNS: usize = 10;
+
+/// Single deployment requested through service mode REST API
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Job {
+    pub id: Uuid,
+    pub config: Config,
+    pub status: JobStatus,
+    #[serde(with = "time::serde::rfc3339")]
+    pub queued: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option", default)]
+    pub start: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option", default)]
+    pub end: Option<OffsetDateTime>,
+    #[serde(default)]
+    pub exit_reason: Option<ExitReason>,
+}
+
+impl Job {
+    pub fn new(config: Config) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            config,
+            status: JobStatus::Queued,
+            queued: OffsetDateTime::now_utc(),
+            start: None,
+            end: None,
+            exit_reason: None,
+        }
+    }
+
+    pub fn started(&mut self) {
+        self.status = JobStatus::Running;
+        self.start = Some(OffsetDateTime::now_utc());
+    }
+
+    pub fn finished(&mut self, exit_reason: ExitReason) {
+        self.status = JobStatus::Finished;
+        self.end = Some(OffsetDateTime::now_utc());
+        self.exit_reason = Some(exit_reason);
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Finished,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub enum ExitReason {
+    /// stopped on request
+    Stopped,
+    /// removed from queue before it was started
+    Cancelled,
+    Failed(String),
+}
+
+/// Copies run artefacts from working directory into job folder in history and saves
+/// job description next to them. Artefacts of jobs older than [`ARCHIVED_RUNS`] recent
+/// ones are removed
+pub fn archive<P: AsRef<Path>, Q: AsRef<Path>>(
+    job: &Job,
+    working_dir: P,
+    history_dir: Q,
+) -> Result<PathBuf, Error> {
+    let job_dir = history_dir.as_ref().join(job.id.to_string());
+    std::fs::create_dir_all(&job_dir)?;
+
+    let working_dir = working_dir.as_ref();
+    if working_dir.exists() {
+        for entry in WalkDir::new(working_dir) {
+            let entry = entry?;
+            let target = job_dir.join(entry.path().strip_prefix(working_dir).unwrap());
+            if entry.file_type().is_dir() {
+                std::fs::create_dir_all(&target)?;
+            } else {
+                std::fs::copy(entry.path(), &target)?;
+            }
+        }
+    }
+
+    save(job, &history_dir)?;
+    prune_history(history_dir, ARCHIVED_RUNS)?;
+    Ok(job_dir)
+}
+
+/// Saves only job description into job folder in history, e.g. for jobs cancelled
+/// before they were started
+pub fn save<P: AsRef<Path>>(job: &Job, history_dir: P) -> Result<PathBuf, Error> {
+    let job_dir = history_dir.as_ref().join(job.id.to_string());
+    std::fs::create_dir_all(&job_dir)?;
+    std::fs::write(job_dir.join(JOB_FILE), serde_yaml::to_string(job)?)?;
+    Ok(job_dir)
+}
+
+/// Removes run artefacts of all but `keep` most recent jobs. Job descriptions are left,
+/// so jobs are still listed in history
+pub fn prune_history<P: AsRef<Path>>(history_dir: P, keep: usize) -> Result<(), Error> {
+    let history_dir = history_dir.as_ref();
+    for job in load_history(history_dir)?.iter().rev().skip(keep) {
+        for entry in std::fs::read_dir(history_dir.join(job.id.to_string()))? {
+            let path = entry?.path();
+            if path.file_name() == Some(JOB_FILE.as_ref()) {
+                continue;
+            }
+            if path.is_dir() {
+                std::fs::remove_dir_all(path)?;
+            } else {
+                std::fs::remove_file(path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Loads jobs archived by previous service runs, oldest first
+pub fn load_history<P: AsRef<Path>>(history_dir: P) -> Result<Vec<Job>, Error> {
+    let history_dir = history_dir.as_ref();
+    if !history_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut jobs = Vec::new();
+    for entry in std::fs::read_dir(history_dir)? {
+        let job_file = entry?.path().join(JOB_FILE);
+        if job_file.exists() {
+            jobs.push(serde_yaml::from_str(&std::fs::read_to_string(job_file)?)?);
+        }
+    }
+    jobs.sort_by_key(|job: &Job| job.queued);
+    Ok(jobs)
+}
+
+/// Resolves path relative to job folder, rejecting paths which escape it
+pub fn job_file<P: AsRef<Path>>(job_dir: P, relative: &str) -> Result<PathBuf, Error> {
+    let relative = Path::new(relative);
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return Err(Error::InvalidPath(relative.to_path_buf()));
+    }
+    let path = job_dir.as_ref().join(relative);
+    if !path.is_file() {
+        return Err(Error::FileNotFound(relative.to_path_buf()));
+    }
+    Ok(path)
+}
+
+/// All `*.log` files from job folder packed into tar.gz archive
+pub fn logs_archive<P: AsRef<Path>>(job_dir: P) -> Result<Vec<u8>, Error> {
+    let job_dir = job_dir.as_ref();
+    let mut archive = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+
+    for entry in WalkDir::new(job_dir) {
+        let entry = entry?;
+        if entry.file_type().is_file()
+            && entry.path().extension().map(|ext| ext == "log") == Some(true)
+        {
+            archive
+                .append_path_with_name(entry.path(), entry.path().strip_prefix(job_dir).unwrap())?;
+        }
+    }
+    Ok(archive.into_inner()?.finish()?)
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("job with id {0} not found")]
+    JobNotFound(Uuid),
+    #[error("job {0} was not started yet")]
+    JobNotStarted(Uuid),
+    #[error("job {0} is already finished")]
+    JobFinished(Uuid),
+    #[error("invalid path: {0:?}")]
+    InvalidPath(PathBuf),
+    #[error("file not found: {0:?}")]
+    FileNotFound(PathBuf),
+    #[error("cannot parse job id")]
+    InvalidJobId(#[from] uuid::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Walk(#[from] walkdir::Error),
+    #[error(transparent)]
+    Yaml(#[from] serde_yaml::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+
+    #[test]
+    fn finished_job_is_archived_and_loaded_back() {
+        let working_dir = TempDir::new().unwrap();
+        let history_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(working_dir.path().join("Leader1")).unwrap();
+        std::fs::write(working_dir.path().join("Leader1/node.log"), "log").unwrap();
+        std::fs::write(working_dir.path().join("block0.bin"), "block0").unwrap();
+
+        let mut job = Job::new(Config::default());
+        job.started();
+        job.finished(ExitReason::Stopped);
+        let job_dir = archive(&job, working_dir.path(), history_dir.path()).unwrap();
+
+        let history = load_history(history_dir.path()).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].id, job.id);
+        assert_eq!(history[0].exit_reason, Some(ExitReason::Stopped));
+
+        assert!(job_file(&job_dir, "Leader1/node.log").is_ok());
+        assert!(job_file(&job_dir, "../job.yaml").is_err());
+
+        let logs = logs_archive(&job_dir).unwrap();
+        let mut logs = tar::Archive::new(flate2::read::GzDecoder::new(logs.as_slice()));
+        let names: Vec<PathBuf> = logs
+            .entries()
+            .unwrap()
+            .map(|entry| entry.unwrap().path().unwrap().to_path_buf())
+            .collect();
+        assert_eq!(names, vec![PathBuf::from("Leader1/node.log")]);
+    }
+
+    #[test]
+    fn artefacts_of_old_jobs_are_pruned() {
+        let working_dir = TempDir::new().unwrap();
+        let history_dir = TempDir::new().unwrap();
+        std::fs::write(working_dir.path().join("block0.bin"), "block0").unwrap();
+
+        let jobs: Vec<Job> = (0..3)
+            .map(|idx| {
+                let mut job = Job::new(Config::default());
+                job.queued = OffsetDateTime::UNIX_EPOCH + time::Duration::seconds(idx);
+                job.finished(ExitReason::Stopped);
+                archive(&job, working_dir.path(), history_dir.path()).unwrap();
+                job
+            })
+            .collect();
+
+        prune_history(history_dir.path(), 2).unwrap();
+
+        assert_eq!(load_history(history_dir.path()).unwrap().len(), 3);
+        let has_artefacts = |job: &Job| {
+            history_dir
+                .path()
+                .join(job.id.to_string())
+                .join("block0.bin")
+                .exists()
+        };
+        assert!(!has_artefacts(&jobs[0]));
+        assert!(has_artefacts(&jobs[1]));
+        assert!(has_artefacts(&jobs[2]));
+    }
+}
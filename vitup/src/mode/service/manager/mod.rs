@@ -1,9 +1,11 @@
 mod context;
 pub mod file_lister;
+pub mod job;
 mod rest;
 mod service;
 
 pub use context::{ControlContext, ControlContextLock, State};
+pub use job::{Error as JobError, ExitReason, Job, JobStatus};
 pub use jortestkit::web::api_token::*;
 pub use rest::{start_rest_server, ServerStopper};
 pub use service::ManagerService;
@@ -1,17 +1,23 @@
+use super::job::{job_file, logs_archive};
 use super::{
-    file_lister, APIToken, APITokenManager, ControlContext, ControlContextLock, State,
+    file_lister, APIToken, APITokenManager, ControlContext, ControlContextLock, JobError, State,
     API_TOKEN_HEADER,
 };
 use crate::config::Config;
+use crate::mode::standard::{DataUpdate, Disruption, DisruptionError, UpdateError};
 use futures::FutureExt;
 use futures::{channel::mpsc, StreamExt};
 use jortestkit::web::api_token::TokenError;
 use std::convert::Infallible;
 use std::sync::Arc;
+use uuid::Uuid;
 use warp::http::StatusCode;
 use warp::reject::Reject;
 use warp::{Filter, Rejection, Reply};
 impl Reject for file_lister::Error {}
+impl Reject for JobError {}
+impl Reject for UpdateError {}
+impl Reject for DisruptionError {}
 
 #[derive(Clone)]
 pub struct ServerStopper(mpsc::Sender<()>);
@@ -80,6 +86,30 @@ pub async fn start_rest_server(context: ControlContextLock) {
             .and_then(status_handler)
             .boxed();
 
+        let update_data = warp::path!("data" / "update")
+            .and(warp::put())
+            .and(with_context.clone())
+            .and(warp::body::json())
+            .and_then(update_data_handler)
+            .boxed();
+
+        let disruption = {
+            let root = warp::path!("disruption" / ..).boxed();
+
+            let schedule = warp::path::end()
+                .and(warp::post())
+                .and(with_context.clone())
+                .and(warp::body::json())
+                .and_then(disruption_handler);
+
+            let status = warp::path::end()
+                .and(warp::get())
+                .and(with_context.clone())
+                .and_then(disruption_status_handler);
+
+            root.and(schedule.or(status)).boxed()
+        };
+
         let api_token_filter = if is_token_enabled {
             warp::header::header(API_TOKEN_HEADER)
                 .and(with_context.clone())
@@ -91,12 +121,60 @@ pub async fn start_rest_server(context: ControlContextLock) {
             warp::any().boxed()
         };
 
+        root.and(api_token_filter.clone())
+            .and(start.or(stop).or(status).or(update_data).or(disruption))
+            .boxed()
+    };
+
+    let jobs = {
+        let root = warp::path!("control" / "jobs" / ..).boxed();
+
+        let list = warp::path::end()
+            .and(warp::get())
+            .and(with_context.clone())
+            .and_then(jobs_handler);
+
+        let get = warp::path!(String)
+            .and(warp::get())
+            .and(with_context.clone())
+            .and_then(job_handler);
+
+        let cancel = warp::path!(String / "cancel")
+            .and(warp::post())
+            .and(with_context.clone())
+            .and_then(job_cancel_handler);
+
+        let logs = warp::path!(String / "logs")
+            .and(warp::get())
+            .and(with_context.clone())
+            .and_then(job_logs_handler);
+
+        let files_list = warp::path!(String / "files" / "list")
+            .and(warp::get())
+            .and(with_context.clone())
+            .and_then(job_files_handler);
+
+        let files_get = warp::path!(String / "files" / "get" / ..)
+            .and(warp::path::tail())
+            .and(warp::get())
+            .and(with_context.clone())
+            .and_then(job_file_handler);
+
         root.and(api_token_filter)
-            .and(start.or(stop).or(status))
+            .and(
+                list.or(get)
+                    .or(cancel)
+                    .or(logs)
+                    .or(files_list)
+                    .or(files_get),
+            )
             .boxed()
     };
 
-    let api = root.and(files.or(commands)).recover(report_invalid).boxed();
+    let api = root
+        .and(files.or(commands).or(jobs))
+        .recover(report_invalid)
+        .boxed();
 
     let server = warp::serve(api);
     let (_, server_fut) = server.bind_with_graceful_shutdown(([0, 0, 0, 0], 3030), stopper_rx);
@@ -113,33 +191,15 @@ pub async fn start_handler(
     parameters: Config,
 ) -> Result<impl Reply, Rejection> {
     let mut context_lock = context.lock().unwrap();
-    context_lock.set_parameters(parameters);
-    let state = context_lock.state();
-    if *state == State::Idle {
-        context_lock.start();
-        return Ok("start event received".to_owned()).map(|r| warp::reply::json(&r));
-    }
-    Ok(format!(
-        "Wrong state to stop operation ('{}'), plase wait until state is '{}'",
-        state,
-        State::Idle
-    ))
-    .map(|r| warp::reply::json(&r))
+    let id = context_lock.new_job(parameters);
+    Ok(id).map(|r| warp::reply::json(&r))
 }
 
 pub async fn start_default_handler(context: ControlContextLock) -> Result<impl Reply, Rejection> {
     let mut context_lock = context.lock().unwrap();
-    let state = context_lock.state();
-    if *state == State::Idle {
-        context_lock.start();
-        return Ok("start event received".to_owned()).map(|r| warp::reply::json(&r));
-    }
-    Ok(format!(
-        "Wrong state to stop operation ('{}'), plase wait until state is '{}'",
-        state,
-        State::Idle
-    ))
-    .map(|r| warp::reply::json(&r))
+    let setup = context_lock.setup().clone();
+    let id = context_lock.new_job(setup);
+    Ok(id).map(|r| warp::reply::json(&r))
 }
 
 pub async fn stop_handler(context: ControlContextLock) -> Result<impl Reply, Rejection> {
@@ -162,6 +222,90 @@ pub async fn status_handler(context: ControlContextLock) -> Result<impl Reply, R
     Ok(context_lock.state()).map(|r| warp::reply::json(&r))
 }
 
+pub async fn update_data_handler(
+    context: ControlContextLock,
+    update: DataUpdate,
+) -> Result<impl Reply, Rejection> {
+    let mut context_lock = context.lock().unwrap();
+    context_lock.update_data(&update)?;
+    Ok("data updated".to_owned()).map(|r| warp::reply::json(&r))
+}
+
+pub async fn disruption_handler(
+    context: ControlContextLock,
+    disruption: Disruption,
+) -> Result<impl Reply, Rejection> {
+    let mut context_lock = context.lock().unwrap();
+    let message = format!("disruption scheduled: {}", disruption);
+    context_lock.disrupt(disruption)?;
+    Ok(message).map(|r| warp::reply::json(&r))
+}
+
+pub async fn disruption_status_handler(
+    context: ControlContextLock,
+) -> Result<impl Reply, Rejection> {
+    let context_lock = context.lock().unwrap();
+    Ok(context_lock.disruptions().status()).map(|r| warp::reply::json(&r))
+}
+
+fn parse_job_id(id: &str) -> Result<Uuid, Rejection> {
+    Uuid::parse_str(id).map_err(|e| warp::reject::custom(JobError::from(e)))
+}
+
+pub async fn jobs_handler(context: ControlContextLock) -> Result<impl Reply, Rejection> {
+    let context_lock = context.lock().unwrap();
+    Ok(context_lock.jobs()).map(|r| warp::reply::json(&r))
+}
+
+pub async fn job_handler(id: String, context: ControlContextLock) -> Result<impl Reply, Rejection> {
+    let id = parse_job_id(&id)?;
+    let context_lock = context.lock().unwrap();
+    Ok(context_lock.job(id)?).map(|r| warp::reply::json(&r))
+}
+
+pub async fn job_cancel_handler(
+    id: String,
+    context: ControlContextLock,
+) -> Result<impl Reply, Rejection> {
+    let id = parse_job_id(&id)?;
+    let mut context_lock = context.lock().unwrap();
+    Ok(context_lock.cancel_job(id)?).map(|r| warp::reply::json(&r))
+}
+
+pub async fn job_logs_handler(
+    id: String,
+    context: ControlContextLock,
+) -> Result<impl Reply, Rejection> {
+    let id = parse_job_id(&id)?;
+    let job_dir = context.lock().unwrap().job_directory(id)?;
+    let logs = logs_archive(job_dir)?;
+    Ok(warp::reply::with_header(
+        warp::reply::with_header(logs, "content-type", "application/gzip"),
+        "content-disposition",
+        format!("attachment; filename=\"{}_logs.tar.gz\"", id),
+    ))
+}
+
+pub async fn job_files_handler(
+    id: String,
+    context: ControlContextLock,
+) -> Result<impl Reply, Rejection> {
+    let id = parse_job_id(&id)?;
+    let job_dir = context.lock().unwrap().job_directory(id)?;
+    Ok(file_lister::dump_json(job_dir)?).map(|r| warp::reply::json(&r))
+}
+
+pub async fn job_file_handler(
+    id: String,
+    tail: warp::path::Tail,
+    context: ControlContextLock,
+) -> Result<impl Reply, Rejection> {
+    let id = parse_job_id(&id)?;
+    let job_dir = context.lock().unwrap().job_directory(id)?;
+    let content = std::fs::read(job_file(job_dir, tail.as_str())?).map_err(JobError::from)?;
+    Ok(content)
+}
+
 async fn report_invalid(r: Rejection) -> Result<impl Reply, Infallible> {
     if let Some(e) = r.find::<file_lister::Error>() {
         // It was our specific error type, do whatever we want. We
@@ -170,6 +314,31 @@ async fn report_invalid(r: Rejection) -> Result<impl Reply, Infallible> {
             e.to_string(),
             StatusCode::BAD_REQUEST,
         ))
+    } else if let Some(e) = r.find::<JobError>() {
+        let status = match e {
+            JobError::JobNotFound(_) | JobError::FileNotFound(_) => StatusCode::NOT_FOUND,
+            JobError::Io(_) | JobError::Walk(_) | JobError::Yaml(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            _ => StatusCode::BAD_REQUEST,
+        };
+        Ok(warp::reply::with_status(e.to_string(), status))
+    } else if let Some(e) = r.find::<UpdateError>() {
+        let status = match e {
+            UpdateError::RequiresNewDeployment(_) => StatusCode::CONFLICT,
+            UpdateError::ProposalNotFound(_) | UpdateError::ReviewNotFound(_) => {
+                StatusCode::NOT_FOUND
+            }
+            UpdateError::Connection(..) | UpdateError::Db(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            UpdateError::NoRunningDeployment => StatusCode::BAD_REQUEST,
+        };
+        Ok(warp::reply::with_status(e.to_string(), status))
+    } else if let Some(e) = r.find::<DisruptionError>() {
+        let status = match e {
+            DisruptionError::NoRunningDeployment => StatusCode::CONFLICT,
+            _ => StatusCode::BAD_REQUEST,
+        };
+        Ok(warp::reply::with_status(e.to_string(), status))
     } else {
         // Do prettier error reporting for the default error here.
         Ok(warp::reply::with_status(
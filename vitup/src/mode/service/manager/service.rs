@@ -1,4 +1,4 @@
-use super::{start_rest_server, ControlContextLock};
+use super::{start_rest_server, ControlContextLock, ExitReason, Job};
 use crate::config::Config;
 use tokio::runtime::Runtime;
 
@@ -23,8 +23,12 @@ impl ManagerService {
         });
     }
 
-    pub fn request_to_start(&self) -> bool {
-        self.control_context.lock().unwrap().request_to_start()
+    pub fn next_job(&self) -> Option<Job> {
+        self.control_context.lock().unwrap().start_next_job()
+    }
+
+    pub fn finish_job(&self, exit_reason: ExitReason) -> Option<Job> {
+        self.control_context.lock().unwrap().finish_job(exit_reason)
     }
 
     pub fn request_to_stop(&self) -> bool {
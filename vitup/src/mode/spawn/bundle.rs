@@ -0,0 +1,192 @@
+use super::{NetworkSpawnParams, PostMortem};
+use crate::builders::utils::{BundleError, BundleManifest, DeploymentTree};
+use crate::config::NodeSetup;
+use crate::mode::standard::{
+    dump_settings_to_file, spawn_wallet_proxy_process, start_vit_station_process, VitSettings,
+    VitStationControllerError, WalletProxyError, STORAGE, VIT_CONFIG,
+};
+use crate::{error::Error, Result};
+use jormungandr_lib::interfaces::NodeConfig;
+use std::fs::File;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::sync::mpsc::channel;
+
+/// Servicing station folder in deployments started by vitup
+const VIT_STATION_DIR: &str = "vit_station";
+
+/// Artifacts of bundled deployment. Network is started from them as they are,
+/// so block0, keys and servicing station data are the same as in source deployment
+pub struct BundledDeployment {
+    block0: PathBuf,
+    database: PathBuf,
+    nodes: Vec<(NodeSetup, PathBuf, PathBuf)>,
+}
+
+impl BundledDeployment {
+    /// Locates artifacts in extracted bundle. Fails if any of them was not bundled
+    pub fn locate<P: AsRef<Path>>(manifest: &BundleManifest, root: P) -> Result<Self> {
+        let root = root.as_ref();
+        let tree = DeploymentTree::new("");
+
+        let block0 = manifest.deployment_file(root, tree.block0_path())?;
+        let database = manifest
+            .deployment_file(root, tree.database_path())
+            .or_else(|_| {
+                manifest.deployment_file(root, Path::new(VIT_STATION_DIR).join(STORAGE))
+            })?;
+        if !manifest.has_deployment_dir(tree.qr_codes_path()) {
+            return Err(
+                BundleError::MissingArtifact(tree.qr_codes_path().display().to_string()).into(),
+            );
+        }
+
+        let nodes = manifest
+            .config
+            .topology
+            .nodes()
+            .into_iter()
+            .map(|node| {
+                let config = manifest.deployment_file(root, tree.node_config(&node.alias))?;
+                let secret = manifest.deployment_file(root, tree.node_secret(&node.alias))?;
+                Ok((node, config, secret))
+            })
+            .collect::<std::result::Result<_, BundleError>>()?;
+
+        Ok(Self {
+            block0,
+            database,
+            nodes,
+        })
+    }
+}
+
+/// Jormungandr processes started from bundled configuration, killed when dropped
+#[derive(Default)]
+struct NodeProcesses(Vec<Child>);
+
+impl NodeProcesses {
+    fn spawn(
+        &mut self,
+        jormungandr: &Path,
+        block0: &Path,
+        config: &Path,
+        secret: &Path,
+        dir: &Path,
+    ) -> Result<()> {
+        let log = File::create(dir.join("node.log"))?;
+        let mut command = Command::new(jormungandr);
+        command
+            .arg("--genesis-block")
+            .arg(block0)
+            .arg("--config")
+            .arg(config)
+            .arg("--secret")
+            .arg(secret)
+            .stdout(log.try_clone()?)
+            .stderr(log);
+        println!("Starting node: {:?}", command);
+        self.0.push(command.spawn()?);
+        Ok(())
+    }
+}
+
+impl Drop for NodeProcesses {
+    fn drop(&mut self) {
+        for child in &mut self.0 {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+pub fn spawn_network(
+    network_spawn_params: NetworkSpawnParams,
+    bundle: BundledDeployment,
+    post_mortem: &mut PostMortem,
+) -> Result<()> {
+    let (tx, rx): (std::sync::mpsc::Sender<()>, std::sync::mpsc::Receiver<()>) = channel();
+
+    let working_directory = network_spawn_params.working_directory.clone();
+    std::fs::create_dir_all(&working_directory)?;
+    let block0 = working_directory.join("block0.bin");
+    std::fs::copy(&bundle.block0, &block0)?;
+
+    let mut nodes = NodeProcesses::default();
+    let mut rest_addresses: Vec<(String, SocketAddr)> = Vec::new();
+    for (node, config, secret) in &bundle.nodes {
+        let dir = working_directory.join(&node.alias);
+        std::fs::create_dir_all(&dir)?;
+
+        let mut node_config: NodeConfig = serde_yaml::from_str(&std::fs::read_to_string(config)?)?;
+        // bundled storage points to folder on machine where deployment was built
+        node_config.storage = node.persistent.then(|| dir.join("storage"));
+        let config = dir.join("config.yaml");
+        std::fs::write(&config, serde_yaml::to_string(&node_config)?)?;
+
+        nodes.spawn(
+            &network_spawn_params.session_settings.jormungandr,
+            &block0,
+            &config,
+            secret,
+            &dir,
+        )?;
+        rest_addresses.push((node.alias.clone(), node_config.rest.listen));
+    }
+
+    let mut session_settings = network_spawn_params.session_settings();
+    let vit_settings = VitSettings::new(&mut session_settings);
+
+    let (alias, settings) = vit_settings
+        .vit_stations
+        .iter()
+        .next()
+        .ok_or(VitStationControllerError::NoVitStationDefinedInSettings)?;
+    let dir = working_directory.join(alias);
+    std::fs::create_dir_all(&dir)?;
+    let config_file = dir.join(VIT_CONFIG);
+    let db_file = dir.join(STORAGE);
+    dump_settings_to_file(config_file.to_str().unwrap(), settings).unwrap();
+    std::fs::copy(&bundle.database, &db_file)?;
+    let _vit_station = start_vit_station_process(
+        alias,
+        settings,
+        &config_file,
+        &db_file,
+        &block0,
+        network_spawn_params.version(),
+    )?;
+
+    let (alias, settings) = vit_settings
+        .wallet_proxies
+        .iter()
+        .next()
+        .ok_or(WalletProxyError::NoWalletProxiesDefinedInSettings)?;
+    let proxy_node = network_spawn_params.topology().wallet_proxy_node();
+    let node_rest_address = rest_addresses
+        .iter()
+        .find(|(alias, _)| *alias == proxy_node)
+        .map(|(_, address)| *address)
+        .ok_or(Error::ProxyNotFound { alias: proxy_node })?;
+    let _wallet_proxy = spawn_wallet_proxy_process(
+        alias,
+        settings,
+        &network_spawn_params.proxy_params(),
+        node_rest_address,
+        &block0,
+    )?;
+
+    println!("Waiting for Ctrl-C to exit..");
+    ctrlc::set_handler(move || {
+        println!("Shutting down..");
+        tx.send(()).expect("Could not send signal on channel.");
+    })
+    .expect("Error setting Ctrl-C handler");
+
+    rx.recv().expect("Could not receive from channel.");
+    post_mortem.capture(rest_addresses);
+    println!("Exited");
+    drop(nodes);
+    Ok(())
+}
@@ -1,7 +1,9 @@
-use super::NetworkSpawnParams;
+use super::{NetworkSpawnParams, PostMortem};
 use crate::builders::VitBackendSettingsBuilder;
 use crate::config::Config;
-use crate::mode::interactive::{VitInteractiveCommandExec, VitUserInteractionController};
+use crate::mode::interactive::{
+    RespawnSettings, VitInteractiveCommandExec, VitUserInteractionController,
+};
 use crate::Result;
 use hersir::controller::UserInteractionController;
 use jortestkit::prelude::UserInteraction;
@@ -11,10 +13,12 @@ pub fn spawn_network(
     network_spawn_params: NetworkSpawnParams,
     config: Config,
     template_generator: &mut dyn ValidVotingTemplateGenerator,
+    post_mortem: &mut PostMortem,
 ) -> Result<()> {
     let (mut vit_controller, vit_parameters) = VitBackendSettingsBuilder::default()
         .config(&config)
         .session_settings(network_spawn_params.session_settings())
+        .protocol(network_spawn_params.protocol())
         .build()?;
 
     let mut nodes_list = vec![];
@@ -27,7 +31,7 @@ pub fn spawn_network(
     let vit_station = vit_controller.spawn_vit_station(
         vit_parameters,
         template_generator,
-        config.service.version,
+        config.service.version.clone(),
     )?;
 
     let user_integration = vit_interaction();
@@ -36,6 +40,12 @@ pub fn spawn_network(
     let mut vit_interaction_controller: VitUserInteractionController = Default::default();
     let nodes = interaction_controller.nodes_mut();
     nodes.extend(nodes_list);
+    vit_interaction_controller.set_respawn_settings(RespawnSettings {
+        controller: vit_controller.clone(),
+        network_params: network_spawn_params.clone(),
+        version: config.service.version.clone(),
+    });
+    vit_interaction_controller.set_election_committee(config.vote_plan.election_committee.clone());
     vit_interaction_controller.proxies_mut().push(wallet_proxy);
     vit_interaction_controller
         .vit_stations_mut()
@@ -47,6 +57,7 @@ pub fn spawn_network(
     };
 
     user_integration.interact(&mut command_exec)?;
+    post_mortem.capture(vit_controller.rest_addresses(network_spawn_params.topology()));
     command_exec.tear_down();
     Ok(())
 }
@@ -1,10 +1,14 @@
+mod bundle;
 mod interactive;
 mod monitor;
+mod report;
 mod service;
 mod standard;
 
-use crate::builders::{LEADER_1, LEADER_2, LEADER_3, WALLET_NODE};
-use crate::config::{mode::Mode, Config};
+pub use bundle::BundledDeployment;
+pub use report::{Error as ReportError, FinalState, LogErrors, PostMortem, Summary};
+
+use crate::config::{mode::Mode, Config, NodeRole, NodeSetup, Topology};
 use crate::mode::standard::{ValidVotingTemplateGenerator, WalletProxySpawnParams};
 use crate::Result;
 use hersir::config::{SessionSettings, SpawnParams};
@@ -20,12 +24,34 @@ pub fn spawn_network(
     generator: &mut dyn ValidVotingTemplateGenerator,
     config: Config,
 ) -> Result<()> {
-    match mode {
-        Mode::Standard => standard::spawn_network(network_spawn_params, config, generator),
-        Mode::Monitor => monitor::spawn_network(network_spawn_params, config, generator),
-        Mode::Interactive => interactive::spawn_network(network_spawn_params, config, generator),
+    let mut post_mortem = PostMortem::new(&config.report, &network_spawn_params.working_directory);
+    let result = match mode {
+        Mode::Standard => {
+            standard::spawn_network(network_spawn_params, config, generator, &mut post_mortem)
+        }
+        Mode::Monitor => {
+            monitor::spawn_network(network_spawn_params, config, generator, &mut post_mortem)
+        }
+        Mode::Interactive => {
+            interactive::spawn_network(network_spawn_params, config, generator, &mut post_mortem)
+        }
+        // every job gets its own report, this one covers only failures of service itself
         Mode::Service => service::spawn_network(network_spawn_params, config, generator),
-    }
+    };
+    post_mortem.finish(&result);
+    result
+}
+
+/// Starts network from artifacts of extracted bundle, see [`BundledDeployment`]
+pub fn spawn_bundle(
+    network_spawn_params: NetworkSpawnParams,
+    bundle: BundledDeployment,
+    config: &Config,
+) -> Result<()> {
+    let mut post_mortem = PostMortem::new(&config.report, &network_spawn_params.working_directory);
+    let result = bundle::spawn_network(network_spawn_params, bundle, &mut post_mortem);
+    post_mortem.finish(&result);
+    result
 }
 
 #[derive(Debug, Clone)]
@@ -35,6 +61,7 @@ pub struct NetworkSpawnParams {
     protocol: Protocol,
     session_settings: SessionSettings,
     version: String,
+    topology: Topology,
     working_directory: PathBuf,
 }
 
@@ -45,6 +72,7 @@ impl NetworkSpawnParams {
         session_settings: SessionSettings,
         token: Option<String>,
         version: String,
+        topology: Topology,
         working_directory: P,
     ) -> Self {
         Self {
@@ -53,6 +81,7 @@ impl NetworkSpawnParams {
             protocol,
             session_settings,
             version,
+            topology,
             working_directory: working_directory.as_ref().to_path_buf(),
         }
     }
@@ -60,6 +89,10 @@ impl NetworkSpawnParams {
         self.session_settings.clone()
     }
 
+    pub fn protocol(&self) -> Protocol {
+        self.protocol.clone()
+    }
+
     pub fn token(&self) -> Option<String> {
         self.token.clone()
     }
@@ -68,36 +101,68 @@ impl NetworkSpawnParams {
         self.version.clone()
     }
 
+    pub fn topology(&self) -> &Topology {
+        &self.topology
+    }
+
     pub fn nodes_params(&self) -> Vec<SpawnParams> {
-        vec![
-            self.leader_node(LEADER_1),
-            self.leader_node(LEADER_2),
-            self.leader_node(LEADER_3),
-            self.passive_node(WALLET_NODE),
-        ]
+        self.topology
+            .nodes()
+            .iter()
+            .map(|node| self.node_params(node))
+            .collect()
     }
 
-    fn leader_node(&self, alias: &str) -> SpawnParams {
-        SpawnParams::new(alias)
-            .leader()
-            .persistence_mode(PersistenceMode::Persistent)
-            .jormungandr(self.session_settings.jormungandr.clone())
+    /// Spawn parameters of single node, used when node is restarted. Isolated node
+    /// has no trusted peers and does not listen for p2p connections
+    pub fn node_spawn_params(&self, alias: &str, isolated: bool) -> Option<SpawnParams> {
+        let node = self
+            .topology
+            .nodes()
+            .into_iter()
+            .find(|node| node.alias == alias)?;
+        let spawn_params = self.node_params(&node);
+        if isolated {
+            Some(spawn_params.trusted_peers(Vec::new()).listen_address(None))
+        } else {
+            Some(spawn_params)
+        }
     }
 
-    fn passive_node(&self, alias: &str) -> SpawnParams {
-        SpawnParams::new(alias)
-            .passive()
-            .persistence_mode(PersistenceMode::Persistent)
-            .persistent_fragment_log(self.working_directory.clone().join("persistent_log"))
-            .jormungandr(self.session_settings.jormungandr.clone())
-            .mempool(Mempool {
-                pool_max_entries: 1_000_000.into(),
-                ..Default::default()
-            })
+    fn node_params(&self, node: &NodeSetup) -> SpawnParams {
+        let persistence_mode = if node.persistent {
+            PersistenceMode::Persistent
+        } else {
+            PersistenceMode::InMemory
+        };
+
+        let mut spawn_params = SpawnParams::new(&node.alias)
+            .persistence_mode(persistence_mode)
+            .jormungandr(self.session_settings.jormungandr.clone());
+
+        if let Ok(Some(log_level)) = node.log_level() {
+            spawn_params = spawn_params.log_level(log_level);
+        }
+
+        match node.role {
+            NodeRole::Leader => spawn_params.leader(),
+            NodeRole::Passive => {
+                spawn_params = spawn_params.passive().mempool(Mempool {
+                    pool_max_entries: 1_000_000.into(),
+                    ..Default::default()
+                });
+                if node.alias == self.topology.wallet_proxy_node() {
+                    spawn_params = spawn_params.persistent_fragment_log(
+                        self.working_directory.clone().join("persistent_log"),
+                    );
+                }
+                spawn_params
+            }
+        }
     }
 
     pub fn proxy_params(&self) -> WalletProxySpawnParams {
-        let mut params = WalletProxySpawnParams::new(WALLET_NODE);
+        let mut params = WalletProxySpawnParams::new(self.topology.wallet_proxy_node());
         params
             .with_base_address(self.endpoint.clone())
             .with_protocol(self.protocol.clone());
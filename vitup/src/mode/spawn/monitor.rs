@@ -1,21 +1,23 @@
-use super::NetworkSpawnParams;
+use super::{NetworkSpawnParams, PostMortem};
 use crate::builders::VitBackendSettingsBuilder;
 use crate::config::Config;
 use crate::mode::monitor::MonitorController;
 use crate::Result;
-use std::sync::mpsc::channel;
+use std::sync::mpsc::{channel, RecvTimeoutError};
 use vit_servicing_station_tests::common::data::ValidVotingTemplateGenerator;
 
 pub fn spawn_network(
     network_spawn_params: NetworkSpawnParams,
     config: Config,
     template_generator: &mut dyn ValidVotingTemplateGenerator,
+    post_mortem: &mut PostMortem,
 ) -> Result<()> {
     let (tx, rx): (std::sync::mpsc::Sender<()>, std::sync::mpsc::Receiver<()>) = channel();
 
     let (vit_controller, vit_parameters) = VitBackendSettingsBuilder::default()
         .config(&config)
         .session_settings(network_spawn_params.session_settings())
+        .protocol(network_spawn_params.protocol())
         .build()?;
 
     let hersir_monitor_controller = hersir::controller::MonitorController::new(
@@ -35,15 +37,31 @@ pub fn spawn_network(
         nodes_list.push(monitor_controller.spawn_node(spawn_param)?);
     }
 
-    let _vit_station = monitor_controller.spawn_vit_station(
+    let vit_station = monitor_controller.spawn_vit_station(
         vit_parameters,
         template_generator,
         config.service.version,
     )?;
-    let _wallet_proxy =
+    let wallet_proxy =
         monitor_controller.spawn_wallet_proxy_custom(&mut network_spawn_params.proxy_params())?;
 
-    rx.recv().expect("Could not receive from channel.");
+    if config.metrics.is_enabled() {
+        let mut exporter = monitor_controller.metrics_exporter(
+            &config.metrics,
+            network_spawn_params.topology(),
+            network_spawn_params.session_settings().root.path(),
+        )?;
+        loop {
+            exporter.tick(&vit_station, &wallet_proxy);
+            match rx.recv_timeout(exporter.interval()) {
+                Err(RecvTimeoutError::Timeout) => continue,
+                _ => break,
+            }
+        }
+    } else {
+        rx.recv().expect("Could not receive from channel.");
+    }
+    post_mortem.capture(monitor_controller.rest_addresses(network_spawn_params.topology()));
     monitor_controller.finalize();
 
     Ok(())
@@ -0,0 +1,427 @@
+use crate::config::Report;
+use crate::mode::monitor::{collect_nodes, rest_clients, NodeMetrics};
+use crate::mode::service::manager::ExitReason;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use jormungandr_automation::jormungandr::NodeAlias;
+use jormungandr_lib::interfaces::VotePlanStatus;
+use serde::Serialize;
+use std::io::{BufRead, BufReader};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use time::format_description;
+use time::OffsetDateTime;
+use walkdir::WalkDir;
+
+pub const SUMMARY_FILE: &str = "summary.json";
+const REPORT_PREFIX: &str = "vitup_";
+/// folder with persistent storage of node, skipped unless `storage` is set in settings
+const NODE_STORAGE: &str = "storage";
+/// error lines kept per log file, the rest is only counted
+const MAX_ERRORS_PER_LOG: usize = 50;
+
+/// Shutdown hook which gathers artefacts of deployment (node logs, vit station
+/// log and database, proxy logs, block0 etc.) into timestamped report folder
+/// together with summary of final state and errors found in logs. If deployment
+/// panics before [`PostMortem::finish`] is called, report is written on drop
+pub struct PostMortem {
+    settings: Report,
+    working_dir: PathBuf,
+    final_state: Option<FinalState>,
+    finished: bool,
+}
+
+/// State of nodes queried right before deployment was shut down
+#[derive(Serialize, Debug)]
+pub struct FinalState {
+    pub nodes: Vec<NodeMetrics>,
+    pub vote_plans: Vec<VotePlanStatus>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Summary {
+    #[serde(with = "time::serde::rfc3339")]
+    pub timestamp: OffsetDateTime,
+    pub exit_reason: ExitReason,
+    /// empty if deployment failed before it could be queried
+    pub final_state: Option<FinalState>,
+    pub log_errors: Vec<LogErrors>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct LogErrors {
+    /// relative to report folder
+    pub file: PathBuf,
+    pub count: usize,
+    /// first lines with errors as (line number, content)
+    pub lines: Vec<(usize, String)>,
+}
+
+impl PostMortem {
+    pub fn new<P: AsRef<Path>>(settings: &Report, working_dir: P) -> Self {
+        Self {
+            settings: settings.clone(),
+            working_dir: working_dir.as_ref().to_path_buf(),
+            final_state: None,
+            finished: false,
+        }
+    }
+
+    /// Queries nodes while they are still running. Should be called right before
+    /// deployment is shut down
+    pub fn capture(&mut self, nodes: Vec<(NodeAlias, SocketAddr)>) {
+        if !self.settings.enabled {
+            return;
+        }
+        let (nodes, vote_plans) = collect_nodes(&rest_clients(nodes));
+        self.final_state = Some(FinalState { nodes, vote_plans });
+    }
+
+    /// Writes report for deployment which ended with given result. Errors are only
+    /// printed, so they never hide original outcome of deployment
+    pub fn finish<T, E: std::fmt::Display>(mut self, result: &std::result::Result<T, E>) {
+        let exit_reason = match result {
+            Ok(_) => ExitReason::Stopped,
+            Err(e) => ExitReason::Failed(e.to_string()),
+        };
+        self.report(exit_reason);
+    }
+
+    fn report(&mut self, exit_reason: ExitReason) {
+        self.finished = true;
+        if !self.settings.enabled {
+            return;
+        }
+        match self.write(exit_reason) {
+            Ok(report) => println!("post-mortem report saved in {:?}", report),
+            Err(e) => println!("cannot write post-mortem report: {}", e),
+        }
+    }
+
+    fn write(&mut self, exit_reason: ExitReason) -> Result<PathBuf, Error> {
+        let timestamp = OffsetDateTime::now_utc();
+        let name = timestamp.format(&format_description::parse(
+            "[year][month][day]_[hour][minute][second]",
+        )?)?;
+        let report_dir = self.settings.dir.join(format!("{}{}", REPORT_PREFIX, name));
+
+        copy_artefacts(
+            &self.working_dir,
+            &report_dir,
+            &self.settings.dir,
+            self.settings.storage,
+        )?;
+        let summary = Summary {
+            timestamp,
+            exit_reason,
+            final_state: self.final_state.take(),
+            log_errors: scan_logs(&report_dir)?,
+        };
+        std::fs::write(
+            report_dir.join(SUMMARY_FILE),
+            serde_json::to_string_pretty(&summary)?,
+        )?;
+        print_summary(&summary);
+
+        let report = if self.settings.archive {
+            archive(&report_dir)?
+        } else {
+            report_dir
+        };
+        prune_reports(&self.settings.dir, self.settings.keep)?;
+        Ok(report)
+    }
+}
+
+impl Drop for PostMortem {
+    fn drop(&mut self) {
+        if !self.finished {
+            let reason = if std::thread::panicking() {
+                "deployment panicked"
+            } else {
+                "deployment ended without result"
+            };
+            self.report(ExitReason::Failed(reason.to_string()));
+        }
+    }
+}
+
+/// Packs report folder into tar.gz archive next to it and removes the folder
+fn archive(report_dir: &Path) -> Result<PathBuf, Error> {
+    let mut archive_path = report_dir.as_os_str().to_os_string();
+    archive_path.push(".tar.gz");
+    let mut archive = tar::Builder::new(GzEncoder::new(
+        std::fs::File::create(&archive_path)?,
+        Compression::default(),
+    ));
+    archive.append_dir_all(report_dir.file_name().unwrap(), report_dir)?;
+    archive.into_inner()?.finish()?;
+    std::fs::remove_dir_all(report_dir)?;
+    Ok(archive_path.into())
+}
+
+/// Removes all but `keep` most recent reports (folders or archives) from reports folder.
+/// Report names contain timestamp, so they are ordered by name
+pub fn prune_reports<P: AsRef<Path>>(reports: P, keep: usize) -> Result<(), Error> {
+    let mut names = Vec::new();
+    for entry in std::fs::read_dir(reports.as_ref())? {
+        let path = entry?.path();
+        let is_report = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| name.starts_with(REPORT_PREFIX))
+            .unwrap_or(false);
+        if is_report {
+            names.push(path);
+        }
+    }
+    names.sort();
+    for path in names.iter().rev().skip(keep) {
+        if path.is_dir() {
+            std::fs::remove_dir_all(path)?;
+        } else {
+            std::fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Copies working directory into report folder, skipping reports folder if it
+/// happens to be placed inside working directory, and persistent storage of nodes
+/// unless `storage` is set
+fn copy_artefacts(
+    working_dir: &Path,
+    report_dir: &Path,
+    reports: &Path,
+    storage: bool,
+) -> Result<(), Error> {
+    std::fs::create_dir_all(report_dir)?;
+    if !working_dir.exists() {
+        return Ok(());
+    }
+    let reports = reports.canonicalize()?;
+
+    let entries = WalkDir::new(working_dir).into_iter().filter_entry(|entry| {
+        let is_storage = entry.file_type().is_dir() && entry.file_name() == NODE_STORAGE;
+        (storage || !is_storage)
+            && entry
+                .path()
+                .canonicalize()
+                .map(|path| !path.starts_with(&reports))
+                .unwrap_or(true)
+    });
+    for entry in entries {
+        let entry = entry?;
+        let target = report_dir.join(entry.path().strip_prefix(working_dir).unwrap());
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else if entry.file_type().is_file() {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Error lines from all `*.log` files in report folder
+pub fn scan_logs<P: AsRef<Path>>(report_dir: P) -> Result<Vec<LogErrors>, Error> {
+    let report_dir = report_dir.as_ref();
+    let mut log_errors = Vec::new();
+
+    for entry in WalkDir::new(report_dir).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file()
+            || entry.path().extension().map(|ext| ext == "log") != Some(true)
+        {
+            continue;
+        }
+
+        let mut errors = LogErrors {
+            file: entry.path().strip_prefix(report_dir).unwrap().to_path_buf(),
+            count: 0,
+            lines: Vec::new(),
+        };
+        let reader = BufReader::new(std::fs::File::open(entry.path())?);
+        for (index, line) in reader.lines().enumerate() {
+            // logs may contain binary garbage after crash
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => continue,
+            };
+            if is_error(&line) {
+                errors.count += 1;
+                if errors.lines.len() < MAX_ERRORS_PER_LOG {
+                    errors.lines.push((index + 1, line));
+                }
+            }
+        }
+        if errors.count > 0 {
+            log_errors.push(errors);
+        }
+    }
+    Ok(log_errors)
+}
+
+fn is_error(line: &str) -> bool {
+    let lowercase = line.to_lowercase();
+    line.contains("ERROR")
+        || lowercase.contains("\"level\":\"error\"")
+        || lowercase.contains("panicked at")
+}
+
+fn print_summary(summary: &Summary) {
+    println!("======================================");
+    println!("exit reason: {:?}", summary.exit_reason);
+    match &summary.final_state {
+        Some(state) => {
+            for node in &state.nodes {
+                let height = node
+                    .stats
+                    .as_ref()
+                    .and_then(|stats| stats.last_block_height.clone())
+                    .unwrap_or_else(|| "-".to_string());
+                let fragments = node
+                    .fragments
+                    .as_ref()
+                    .map(|f| {
+                        format!(
+                            "pending: {}, in block: {}, rejected: {}",
+                            f.pending, f.in_block, f.rejected
+                        )
+                    })
+                    .unwrap_or_else(|| "-".to_string());
+                println!(
+                    "{}: up: {}, block height: {}, fragments: {}",
+                    node.alias, node.up, height, fragments
+                );
+            }
+            for vote_plan in &state.vote_plans {
+                let votes: usize = vote_plan
+                    .proposals
+                    .iter()
+                    .map(|proposal| proposal.votes_cast)
+                    .sum();
+                println!("vote plan {}: votes cast: {}", vote_plan.id, votes);
+            }
+        }
+        None => println!("final state of nodes was not captured"),
+    }
+    for errors in &summary.log_errors {
+        println!("{:?}: {} error(s)", errors.file, errors.count);
+    }
+    println!("======================================");
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Walk(#[from] walkdir::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("invalid report name format")]
+    InvalidFormat(#[from] time::error::InvalidFormatDescription),
+    #[error("cannot format report name")]
+    Format(#[from] time::error::Format),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+
+    fn settings(reports: &TempDir) -> Report {
+        Report {
+            dir: reports.path().to_path_buf(),
+            ..Default::default()
+        }
+    }
+
+    fn report_paths(reports: &TempDir) -> Vec<PathBuf> {
+        let mut reports: Vec<PathBuf> = std::fs::read_dir(reports.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        reports.sort();
+        reports
+    }
+
+    #[test]
+    fn artefacts_are_collected_with_log_errors() {
+        let working_dir = TempDir::new().unwrap();
+        let reports = TempDir::new().unwrap();
+        std::fs::create_dir_all(working_dir.path().join("Leader1/storage")).unwrap();
+        std::fs::write(
+            working_dir.path().join("Leader1/node.log"),
+            "{\"level\":\"INFO\",\"msg\":\"ok\"}\n{\"level\":\"ERROR\",\"msg\":\"boom\"}\n",
+        )
+        .unwrap();
+        std::fs::write(working_dir.path().join("Leader1/storage/blocks"), "").unwrap();
+        std::fs::write(working_dir.path().join("block0.bin"), "block0").unwrap();
+
+        PostMortem::new(&settings(&reports), working_dir.path())
+            .finish(&Err::<(), _>("vit station failed"));
+
+        let report_dir = report_paths(&reports).remove(0);
+        assert!(report_dir.join("block0.bin").exists());
+        assert!(!report_dir.join("Leader1/storage").exists());
+        let summary = std::fs::read_to_string(report_dir.join(SUMMARY_FILE)).unwrap();
+        assert!(summary.contains("vit station failed"));
+
+        let log_errors = scan_logs(&report_dir).unwrap();
+        assert_eq!(log_errors.len(), 1);
+        assert_eq!(log_errors[0].file, PathBuf::from("Leader1/node.log"));
+        assert_eq!(log_errors[0].lines[0].0, 2);
+    }
+
+    #[test]
+    fn report_is_written_when_deployment_panics() {
+        let working_dir = TempDir::new().unwrap();
+        let reports = TempDir::new().unwrap();
+        let settings = settings(&reports);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _post_mortem = PostMortem::new(&settings, working_dir.path());
+            panic!("node crashed");
+        }));
+        assert!(result.is_err());
+
+        let summary =
+            std::fs::read_to_string(report_paths(&reports)[0].join(SUMMARY_FILE)).unwrap();
+        assert!(summary.contains("deployment panicked"));
+    }
+
+    #[test]
+    fn only_most_recent_reports_are_kept() {
+        let reports = TempDir::new().unwrap();
+        for name in [
+            "vitup_20220101_000000",
+            "vitup_20220102_000000",
+            "vitup_20220103_000000.tar.gz",
+        ] {
+            let path = reports.path().join(name);
+            if name.ends_with(".tar.gz") {
+                std::fs::write(path, "").unwrap();
+            } else {
+                std::fs::create_dir_all(path).unwrap();
+            }
+        }
+        std::fs::write(reports.path().join("notes.txt"), "").unwrap();
+
+        prune_reports(reports.path(), 2).unwrap();
+
+        let names: Vec<String> = report_paths(&reports)
+            .iter()
+            .map(|path| path.file_name().unwrap().to_str().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "notes.txt",
+                "vitup_20220102_000000",
+                "vitup_20220103_000000.tar.gz"
+            ]
+        );
+    }
+}
@@ -1,12 +1,24 @@
-use super::NetworkSpawnParams;
-use crate::builders::VitBackendSettingsBuilder;
+use super::{NetworkSpawnParams, PostMortem};
+use crate::builders::{TemplatesGenerator, VitBackendSettingsBuilder};
 use crate::config::Config;
-use crate::mode::monitor::MonitorController;
-use crate::mode::service::manager::{ControlContext, ControlContextLock, ManagerService, State};
-use crate::mode::standard::ValidVotingTemplateGenerator;
+use crate::mode::monitor::{
+    MonitorController, VitStationMonitorController, WalletProxyMonitorController,
+};
+use crate::mode::service::manager::{
+    ControlContext, ControlContextLock, ExitReason, ManagerService, State,
+};
+use crate::mode::standard::{
+    send_signal, Disruption, DisruptionAction, DisruptionError, Signal,
+    ValidVotingTemplateGenerator,
+};
 use crate::Result;
+use hersir::controller::MonitorNode;
 use std::sync::{Arc, Mutex};
-use vit_servicing_station_tests::common::data::ArbitraryValidVotingTemplateGenerator;
+use std::time::Duration;
+
+const REPORT_DIR: &str = "report";
+/// how often stop requests and scheduled disruptions are checked
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
 
 pub fn spawn_network(
     network_params: NetworkSpawnParams,
@@ -24,30 +36,50 @@ pub fn spawn_network(
     manager.spawn();
 
     loop {
-        //TODO: remove this dirty fix and implement CLONE for ValidVotingTemplateGenerator
-        let mut template_generator = ArbitraryValidVotingTemplateGenerator::new();
+        if let Some(job) = manager.next_job() {
+            //TODO: remove this dirty fix and implement CLONE for ValidVotingTemplateGenerator
+            let mut template_generator = TemplatesGenerator::from_config(&job.config).build()?;
 
-        if manager.request_to_start() {
             if working_dir.path().exists() {
                 std::fs::remove_dir_all(working_dir.path())?;
             }
 
-            manager.clear_requests();
-            single_run(
+            println!("starting job {}", job.id);
+            // report is written into working directory, so it is archived together with job
+            let mut report = job.config.report.clone();
+            report.dir = working_dir.path().join(REPORT_DIR);
+            let mut post_mortem = PostMortem::new(&report, working_dir.path());
+            let result = single_run(
                 control_context.clone(),
                 network_params.clone(),
+                job.config,
                 &mut template_generator,
-            )?;
+                &mut post_mortem,
+            );
+            post_mortem.finish(&result);
+            let exit_reason = match result {
+                Ok(()) => ExitReason::Stopped,
+                Err(e) => {
+                    println!("job {} failed: {}", job.id, e);
+                    *control_context.lock().unwrap().state_mut() = State::Idle;
+                    ExitReason::Failed(e.to_string())
+                }
+            };
+            manager.clear_requests();
+
+            manager.finish_job(exit_reason);
         }
 
-        std::thread::sleep(std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_secs(5));
     }
 }
 
 pub fn single_run(
     control_context: ControlContextLock,
-    network_params: NetworkSpawnParams,
+    mut network_params: NetworkSpawnParams,
+    config: Config,
     template_generator: &mut dyn ValidVotingTemplateGenerator,
+    post_mortem: &mut PostMortem,
 ) -> Result<()> {
     {
         let mut control_context = control_context.lock().unwrap();
@@ -55,10 +87,12 @@ pub fn single_run(
         *state = State::Starting;
     }
 
-    let config = control_context.lock().unwrap().setup().clone();
+    // topology can be changed between runs together with the rest of config
+    network_params.topology = config.topology.clone();
     let (vit_controller, vit_parameters) = VitBackendSettingsBuilder::default()
         .config(&config)
         .session_settings(network_params.session_settings())
+        .protocol(network_params.protocol())
         .build()?;
 
     let hersir_monitor_controller = hersir::controller::MonitorController::new(
@@ -74,16 +108,27 @@ pub fn single_run(
         nodes_list.push(monitor_controller.spawn_node(spawn_param)?);
     }
 
-    let vit_station = monitor_controller.spawn_vit_station(
+    let mut vit_station = monitor_controller.spawn_vit_station(
         vit_parameters,
         template_generator,
-        config.service.version,
+        config.service.version.clone(),
     )?;
     let mut wallet_proxy =
         monitor_controller.spawn_wallet_proxy_custom(&mut network_params.proxy_params())?;
 
+    let mut metrics_exporter = if config.metrics.is_enabled() {
+        Some(monitor_controller.metrics_exporter(
+            &config.metrics,
+            &config.topology,
+            network_params.session_settings().root.path(),
+        )?)
+    } else {
+        None
+    };
+
     {
         let mut control_context = control_context.lock().unwrap();
+        control_context.set_vit_station_db(Some(vit_station.db_path()));
         let state = control_context.state_mut();
         *state = State::Running;
     }
@@ -92,14 +137,26 @@ pub fn single_run(
         if control_context.lock().unwrap().request_to_stop() {
             {
                 let mut control_context = control_context.lock().unwrap();
+                control_context.set_vit_station_db(None);
                 let state = control_context.state_mut();
                 *state = State::Stopping;
             }
 
+            // paused processes would neither report their state nor react on shutdown
+            let _ = send_signal(vit_station.pid(), Signal::Cont);
+            let _ = send_signal(wallet_proxy.pid(), Signal::Cont);
+            for node in nodes_list.iter() {
+                let _ = send_signal(node.pid(), Signal::Cont);
+            }
+
+            post_mortem.capture(monitor_controller.rest_addresses(&config.topology));
             vit_station.shutdown();
             wallet_proxy.shutdown();
             for node in nodes_list.iter_mut() {
-                node.shutdown()?;
+                // node could have been killed by disruption
+                if let Err(e) = node.shutdown() {
+                    println!("cannot shutdown node {}: {}", node.alias(), e);
+                }
             }
 
             monitor_controller.finalize();
@@ -111,6 +168,118 @@ pub fn single_run(
             return Ok(());
         }
 
-        std::thread::sleep(std::time::Duration::from_secs(30));
+        let due = control_context.lock().unwrap().disruptions_mut().take_due();
+        for disruption in due {
+            println!("applying disruption: {}", disruption);
+            let result = disrupt(
+                &disruption,
+                &mut monitor_controller,
+                &network_params,
+                &config.service.version,
+                &mut nodes_list,
+                &mut vit_station,
+                &mut wallet_proxy,
+            );
+            if let Err(e) = &result {
+                println!("cannot apply disruption {}: {}", disruption, e);
+            }
+            control_context
+                .lock()
+                .unwrap()
+                .disruptions_mut()
+                .record(disruption, &result);
+        }
+
+        if let Some(exporter) = metrics_exporter.as_mut() {
+            exporter.tick(&vit_station, &wallet_proxy);
+        }
+        std::thread::sleep(POLL_INTERVAL);
     }
 }
+
+/// Applies disruption to node, vit station or wallet proxy of running deployment.
+/// Restarted processes replace their old controllers
+#[allow(clippy::too_many_arguments)]
+fn disrupt(
+    disruption: &Disruption,
+    monitor_controller: &mut MonitorController,
+    network_params: &NetworkSpawnParams,
+    version: &str,
+    nodes: &mut [MonitorNode],
+    vit_station: &mut VitStationMonitorController,
+    wallet_proxy: &mut WalletProxyMonitorController,
+) -> std::result::Result<(), DisruptionError> {
+    let target = disruption.target.as_str();
+    let action = disruption.action;
+    let restart_error =
+        |e: crate::error::Error| DisruptionError::Restart(target.to_string(), e.to_string());
+
+    if let Some(node) = nodes.iter_mut().find(|node| node.alias() == target) {
+        return match action {
+            DisruptionAction::Kill => send_signal(node.pid(), Signal::Kill),
+            DisruptionAction::Pause => send_signal(node.pid(), Signal::Stop),
+            DisruptionAction::Resume => send_signal(node.pid(), Signal::Cont),
+            DisruptionAction::Restart | DisruptionAction::Isolate | DisruptionAction::Reconnect => {
+                // node may be already down
+                let _ = send_signal(node.pid(), Signal::Kill);
+                let spawn_params = network_params
+                    .node_spawn_params(target, action == DisruptionAction::Isolate)
+                    .ok_or_else(|| DisruptionError::UnknownTarget(target.to_string()))?;
+                *node = monitor_controller
+                    .spawn_node(spawn_params)
+                    .map_err(restart_error)?;
+                Ok(())
+            }
+        };
+    }
+
+    if vit_station.alias() == target {
+        return match action {
+            DisruptionAction::Kill => {
+                vit_station.kill();
+                Ok(())
+            }
+            DisruptionAction::Pause => send_signal(vit_station.pid(), Signal::Stop),
+            DisruptionAction::Resume => send_signal(vit_station.pid(), Signal::Cont),
+            DisruptionAction::Restart => {
+                vit_station.kill();
+                *vit_station = monitor_controller
+                    .start_vit_station(version.to_string())
+                    .map_err(restart_error)?;
+                Ok(())
+            }
+            DisruptionAction::Isolate | DisruptionAction::Reconnect => {
+                Err(DisruptionError::UnsupportedAction {
+                    target: target.to_string(),
+                    action,
+                })
+            }
+        };
+    }
+
+    if wallet_proxy.alias() == target {
+        return match action {
+            DisruptionAction::Kill => {
+                wallet_proxy.shutdown();
+                Ok(())
+            }
+            DisruptionAction::Pause => send_signal(wallet_proxy.pid(), Signal::Stop),
+            DisruptionAction::Resume => send_signal(wallet_proxy.pid(), Signal::Cont),
+            DisruptionAction::Restart => {
+                wallet_proxy.shutdown();
+                *wallet_proxy = monitor_controller
+                    .spawn_wallet_proxy_custom(&mut network_params.proxy_params())
+                    .map_err(restart_error)?;
+                Ok(())
+            }
+            DisruptionAction::Isolate | DisruptionAction::Reconnect => {
+                Err(DisruptionError::UnsupportedAction {
+                    target: target.to_string(),
+                    action,
+                })
+            }
+        };
+    }
+
+    Err(DisruptionError::UnknownTarget(target.to_string()))
+}
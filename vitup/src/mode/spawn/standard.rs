@@ -1,33 +1,53 @@
-use super::NetworkSpawnParams;
+use super::{NetworkSpawnParams, PostMortem};
 use crate::builders::VitBackendSettingsBuilder;
 use crate::config::Config;
+use crate::mode::standard::{
+    kill_child, send_signal, Disruption, DisruptionAction, DisruptionError, DisruptionSchedule,
+    Signal, VitController, VitStationController, WalletProxyController,
+};
 use crate::Result;
-use std::sync::mpsc::channel;
+use jormungandr_automation::jormungandr::JormungandrProcess;
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::Duration;
 use vit_servicing_station_tests::common::data::ValidVotingTemplateGenerator;
 
+/// how often scheduled disruptions are checked
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 pub fn spawn_network(
     network_spawn_params: NetworkSpawnParams,
     config: Config,
     template_generator: &mut dyn ValidVotingTemplateGenerator,
+    post_mortem: &mut PostMortem,
 ) -> Result<()> {
     let (tx, rx): (std::sync::mpsc::Sender<()>, std::sync::mpsc::Receiver<()>) = channel();
 
     let (mut vit_controller, vit_parameters) = VitBackendSettingsBuilder::default()
         .config(&config)
         .session_settings(network_spawn_params.session_settings())
+        .protocol(network_spawn_params.protocol())
         .build()?;
 
     let mut nodes_list = vec![];
     for spawn_param in network_spawn_params.nodes_params() {
         nodes_list.push(vit_controller.spawn_node(spawn_param)?);
     }
-    let _wallet_proxy =
+    let mut wallet_proxy =
         vit_controller.spawn_wallet_proxy_custom(&mut network_spawn_params.proxy_params())?;
-    let _vit_station = vit_controller.spawn_vit_station(
+    let mut vit_station = vit_controller.spawn_vit_station(
         vit_parameters,
         template_generator,
         network_spawn_params.version(),
-    );
+    )?;
+
+    let mut schedule = DisruptionSchedule::default();
+    for disruption in &config.disruptions {
+        println!(
+            "scheduled disruption: {} in {}s",
+            disruption, disruption.delay
+        );
+        schedule.schedule(disruption.clone());
+    }
 
     println!("Waiting for Ctrl-C to exit..");
     ctrlc::set_handler(move || {
@@ -36,8 +56,105 @@ pub fn spawn_network(
     })
     .expect("Error setting Ctrl-C handler");
 
-    rx.recv().expect("Could not receive from channel.");
+    while let Err(RecvTimeoutError::Timeout) = rx.recv_timeout(POLL_INTERVAL) {
+        for disruption in schedule.take_due() {
+            println!("applying disruption: {}", disruption);
+            let result = disrupt(
+                &disruption,
+                &mut vit_controller,
+                &network_spawn_params,
+                &mut nodes_list,
+                &mut vit_station,
+                &mut wallet_proxy,
+            );
+            if let Err(e) = &result {
+                println!("cannot apply disruption {}: {}", disruption, e);
+            }
+            schedule.record(disruption, &result);
+        }
+    }
+
+    // paused processes would not react on shutdown
+    let _ = send_signal(vit_station.pid(), Signal::Cont);
+    let _ = send_signal(wallet_proxy.pid(), Signal::Cont);
+    for node in nodes_list.iter() {
+        let _ = send_signal(node.pid(), Signal::Cont);
+    }
+
+    post_mortem.capture(vit_controller.rest_addresses(network_spawn_params.topology()));
     println!("Exited");
-    #[allow(unreachable_code)]
     Ok(())
 }
+
+/// Applies disruption to node, vit station or wallet proxy of running deployment.
+/// Restarted processes replace their old controllers, which reaps killed ones
+fn disrupt(
+    disruption: &Disruption,
+    vit_controller: &mut VitController,
+    network_spawn_params: &NetworkSpawnParams,
+    nodes: &mut [JormungandrProcess],
+    vit_station: &mut VitStationController,
+    wallet_proxy: &mut WalletProxyController,
+) -> std::result::Result<(), DisruptionError> {
+    let target = disruption.target.as_str();
+    let action = disruption.action;
+    let restart_error =
+        |e: crate::error::Error| DisruptionError::Restart(target.to_string(), e.to_string());
+    let unsupported = || DisruptionError::UnsupportedAction {
+        target: target.to_string(),
+        action,
+    };
+
+    if let Some(node) = nodes.iter_mut().find(|node| node.alias() == target) {
+        return match action {
+            DisruptionAction::Kill => send_signal(node.pid(), Signal::Kill),
+            DisruptionAction::Pause => send_signal(node.pid(), Signal::Stop),
+            DisruptionAction::Resume => send_signal(node.pid(), Signal::Cont),
+            DisruptionAction::Restart | DisruptionAction::Isolate | DisruptionAction::Reconnect => {
+                // node may be already down
+                let _ = send_signal(node.pid(), Signal::Kill);
+                let spawn_params = network_spawn_params
+                    .node_spawn_params(target, action == DisruptionAction::Isolate)
+                    .ok_or_else(|| DisruptionError::UnknownTarget(target.to_string()))?;
+                *node = vit_controller
+                    .spawn_node(spawn_params)
+                    .map_err(restart_error)?;
+                Ok(())
+            }
+        };
+    }
+
+    if vit_station.alias() == target {
+        return match action {
+            DisruptionAction::Kill => kill_child(&mut vit_station.process),
+            DisruptionAction::Pause => send_signal(vit_station.pid(), Signal::Stop),
+            DisruptionAction::Resume => send_signal(vit_station.pid(), Signal::Cont),
+            DisruptionAction::Restart => {
+                vit_station.shutdown();
+                *vit_station = vit_controller
+                    .start_vit_station(network_spawn_params.version())
+                    .map_err(restart_error)?;
+                Ok(())
+            }
+            DisruptionAction::Isolate | DisruptionAction::Reconnect => Err(unsupported()),
+        };
+    }
+
+    if wallet_proxy.alias() == target {
+        return match action {
+            DisruptionAction::Kill => kill_child(&mut wallet_proxy.process),
+            DisruptionAction::Pause => send_signal(wallet_proxy.pid(), Signal::Stop),
+            DisruptionAction::Resume => send_signal(wallet_proxy.pid(), Signal::Cont),
+            DisruptionAction::Restart => {
+                wallet_proxy.shutdown();
+                *wallet_proxy = vit_controller
+                    .spawn_wallet_proxy_custom(&mut network_spawn_params.proxy_params())
+                    .map_err(restart_error)?;
+                Ok(())
+            }
+            DisruptionAction::Isolate | DisruptionAction::Reconnect => Err(unsupported()),
+        };
+    }
+
+    Err(DisruptionError::UnknownTarget(target.to_string()))
+}
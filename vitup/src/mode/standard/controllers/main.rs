@@ -3,10 +3,15 @@ use super::{
     vit_station::{
         dump_settings_to_file, BootstrapCommandBuilder, DbGenerator,
         Error as VitStationControllerError, RestClient, ValidVotePlanParameters,
-        ValidVotingTemplateGenerator, VitStationController, STORAGE, VIT_CONFIG,
+        ValidVotingTemplateGenerator, VitStationController, VitStationSettings, STORAGE,
+        VIT_CONFIG,
+    },
+    wallet_proxy::{
+        Error as WalletProxyError, WalletProxyController, WalletProxySettings,
+        WalletProxySpawnParams,
     },
-    wallet_proxy::{Error as WalletProxyError, WalletProxyController, WalletProxySpawnParams},
 };
+use crate::builders::VotePlanLayout;
 use crate::Result;
 use assert_fs::fixture::PathChild;
 use chain_impl_mockchain::testing::scenario::template::VotePlanDef;
@@ -14,13 +19,15 @@ use hersir::builder::ControllerError;
 use hersir::config::{Blockchain, CommitteeTemplate, SpawnParams, WalletTemplate};
 use hersir::{
     builder::{
-        NetworkBuilder, NodeAlias, NodeSetting, Settings, Topology, Wallet as WalletSettings,
+        NetworkBuilder, NodeAlias, NodeSetting, Seed, Settings, Topology, Wallet as WalletSettings,
     },
     config::SessionSettings,
 };
 use jormungandr_automation::jormungandr::{JormungandrProcess, Status, TestingDirectory};
+use rand_chacha::ChaChaRng;
 use std::{
-    path::PathBuf,
+    net::SocketAddr,
+    path::{Path, PathBuf},
     process::Command,
     sync::{Arc, Mutex},
 };
@@ -31,6 +38,7 @@ use thor::{Wallet, WalletAlias};
 pub struct VitControllerBuilder {
     committees: Vec<CommitteeTemplate>,
     controller_builder: NetworkBuilder,
+    vote_plan_layout: VotePlanLayout,
 }
 
 impl VitControllerBuilder {
@@ -38,9 +46,16 @@ impl VitControllerBuilder {
         Self {
             committees: Vec::new(),
             controller_builder: NetworkBuilder::default(),
+            vote_plan_layout: VotePlanLayout::default(),
         }
     }
 
+    /// Per-definition vote options and challenges applied on servicing station data
+    pub fn vote_plan_layout(mut self, vote_plan_layout: VotePlanLayout) -> Self {
+        self.vote_plan_layout = vote_plan_layout;
+        self
+    }
+
     pub(crate) fn committee(mut self, committee: CommitteeTemplate) -> Self {
         self.committees.push(committee);
         self
@@ -66,6 +81,12 @@ impl VitControllerBuilder {
         self
     }
 
+    /// Random source of keys generated by hersir: node secrets, committee members and wallets
+    pub fn rng(mut self, rng: ChaChaRng) -> Self {
+        self.controller_builder = self.controller_builder.seed(Seed::generate(rng));
+        self
+    }
+
     pub fn build(
         self,
         mut session_settings: SessionSettings,
@@ -75,10 +96,10 @@ impl VitControllerBuilder {
             .committees(self.committees)
             .session_settings(session_settings.clone())
             .build()?;
-        Ok(VitController::new(
-            VitSettings::new(&mut session_settings),
-            controller,
-        ))
+        let mut vit_controller =
+            VitController::new(VitSettings::new(&mut session_settings), controller);
+        vit_controller.vote_plan_layout = self.vote_plan_layout;
+        Ok(vit_controller)
     }
 }
 
@@ -96,6 +117,7 @@ pub enum Error {
 pub struct VitController {
     vit_settings: VitSettings,
     hersir_controller: hersir::controller::Controller,
+    vote_plan_layout: VotePlanLayout,
 }
 
 impl VitController {
@@ -106,9 +128,14 @@ impl VitController {
         Self {
             vit_settings,
             hersir_controller,
+            vote_plan_layout: VotePlanLayout::default(),
         }
     }
 
+    pub fn vote_plan_layout(&self) -> &VotePlanLayout {
+        &self.vote_plan_layout
+    }
+
     pub fn vit_settings(&self) -> &VitSettings {
         &self.vit_settings
     }
@@ -144,6 +171,23 @@ impl VitController {
         self.hersir_controller.defined_nodes().collect()
     }
 
+    /// REST addresses of nodes which are spawned according to vitup topology
+    pub fn rest_addresses(
+        &self,
+        topology: &crate::config::Topology,
+    ) -> Vec<(NodeAlias, SocketAddr)> {
+        let aliases: Vec<String> = topology
+            .nodes()
+            .into_iter()
+            .map(|node| node.alias)
+            .collect();
+        self.defined_nodes()
+            .into_iter()
+            .filter(|(alias, _)| aliases.contains(*alias))
+            .map(|(alias, node)| (alias.clone(), node.config.rest.listen))
+            .collect()
+    }
+
     pub fn defined_wallets(&self) -> Vec<(WalletAlias, &WalletSettings)> {
         self.hersir_controller.defined_wallets().collect()
     }
@@ -186,36 +230,37 @@ impl VitController {
         dump_settings_to_file(config_file.to_str().unwrap(), settings).unwrap();
 
         DbGenerator::new(vote_plan_parameters, working_directory)
+            .service_version(version.clone())
+            .vote_plan_layout(self.vote_plan_layout.clone())
             .build(&db_file, template_generator)?;
 
-        let mut command_builder =
-            BootstrapCommandBuilder::new(PathBuf::from("vit-servicing-station-server"));
-        let mut command = command_builder
-            .in_settings_file(&config_file)
-            .db_url(db_file.to_str().unwrap())
-            .service_version(version)
-            .block0_path(Some(
-                self.hersir_controller
-                    .block0_file()
-                    .to_str()
-                    .unwrap()
-                    .to_string(),
-            ))
-            .build();
-
-        println!("Starting vit-servicing-station: {:?}", command);
-
-        let controller = VitStationController {
-            alias: alias.into(),
-            rest_client: RestClient::from(settings),
-            process: command.spawn().unwrap(),
-            settings: settings.clone(),
-            status: Arc::new(Mutex::new(Status::Running)),
-        };
-
-        wait_for_bootstrap(&controller)?;
-
-        Ok(controller)
+        self.start_vit_station(version)
+    }
+
+    /// Starts servicing station on configuration and database left by previous
+    /// `spawn_vit_station` call, e.g. to restart it after crash
+    pub fn start_vit_station(&self, version: String) -> Result<VitStationController> {
+        let (alias, settings) = self
+            .vit_settings
+            .vit_stations
+            .iter()
+            .next()
+            .ok_or(VitStationControllerError::NoVitStationDefinedInSettings)?;
+
+        let dir = self
+            .hersir_controller
+            .working_directory()
+            .path()
+            .join(alias);
+
+        start_vit_station_process(
+            alias,
+            settings,
+            &dir.join(VIT_CONFIG),
+            &dir.join(STORAGE),
+            &self.hersir_controller.block0_file(),
+            version,
+        )
     }
 
     //TODO: move to wallet builder
@@ -240,48 +285,16 @@ impl VitController {
                 });
             };
 
-        let mut settings_overriden = settings.clone();
-        params.override_settings(&mut settings_overriden);
-
-        let block0_file = self.hersir_controller.block0_file();
-        let working_directory = self.hersir_controller.working_directory();
-
-        let dir = working_directory.child(alias);
+        let dir = self.hersir_controller.working_directory().child(alias);
         std::fs::DirBuilder::new().recursive(true).create(&dir)?;
 
-        settings_overriden.node_backend_address = Some(node_setting.config.rest.listen);
-
-        let mut command = Command::new("valgrind");
-        command
-            .arg("--address")
-            .arg(settings_overriden.base_address().to_string())
-            .arg("--vit-address")
-            .arg(&settings_overriden.base_vit_address().to_string())
-            .arg("--node-address")
-            .arg(
-                &settings_overriden
-                    .base_node_backend_address()
-                    .unwrap()
-                    .to_string(),
-            )
-            .arg("--block0")
-            .arg(block0_file.as_path().to_str().unwrap());
-
-        if let valgrind::Protocol::Https(certs) = &params.protocol {
-            command
-                .arg("--cert")
-                .arg(&certs.cert_path)
-                .arg("--key")
-                .arg(&certs.key_path);
-        }
-
-        WalletProxyController::new(
-            alias.into(),
-            settings.clone(),
-            Arc::new(Mutex::new(Status::Running)),
-            command.spawn().unwrap(),
+        spawn_wallet_proxy_process(
+            alias,
+            settings,
+            params,
+            node_setting.config.rest.listen,
+            &self.hersir_controller.block0_file(),
         )
-        .map_err(Into::into)
     }
 
     //TODO: move to wallet builder
@@ -290,6 +303,84 @@ impl VitController {
     }
 }
 
+/// Starts servicing station process from configuration and database files
+pub fn start_vit_station_process(
+    alias: &str,
+    settings: &VitStationSettings,
+    config_file: &Path,
+    db_file: &Path,
+    block0_file: &Path,
+    version: String,
+) -> Result<VitStationController> {
+    let mut command_builder =
+        BootstrapCommandBuilder::new(PathBuf::from("vit-servicing-station-server"));
+    let mut command = command_builder
+        .in_settings_file(config_file)
+        .db_url(db_file.to_str().unwrap())
+        .service_version(version)
+        .block0_path(Some(block0_file.to_str().unwrap().to_string()))
+        .build();
+
+    println!("Starting vit-servicing-station: {:?}", command);
+
+    let controller = VitStationController {
+        alias: alias.into(),
+        rest_client: RestClient::from(settings),
+        process: command.spawn().unwrap(),
+        settings: settings.clone(),
+        status: Arc::new(Mutex::new(Status::Running)),
+    };
+
+    wait_for_bootstrap(&controller)?;
+
+    Ok(controller)
+}
+
+/// Starts wallet proxy in front of node with given REST address
+pub fn spawn_wallet_proxy_process(
+    alias: &str,
+    settings: &WalletProxySettings,
+    params: &WalletProxySpawnParams,
+    node_rest_address: SocketAddr,
+    block0_file: &Path,
+) -> Result<WalletProxyController> {
+    let mut settings_overriden = settings.clone();
+    params.override_settings(&mut settings_overriden);
+    settings_overriden.node_backend_address = Some(node_rest_address);
+
+    let mut command = Command::new("valgrind");
+    command
+        .arg("--address")
+        .arg(settings_overriden.base_address().to_string())
+        .arg("--vit-address")
+        .arg(&settings_overriden.base_vit_address().to_string())
+        .arg("--node-address")
+        .arg(
+            &settings_overriden
+                .base_node_backend_address()
+                .unwrap()
+                .to_string(),
+        )
+        .arg("--block0")
+        .arg(block0_file.to_str().unwrap());
+
+    if let valgrind::Protocol::Https(certs) = &params.protocol {
+        command
+            .arg("--cert")
+            .arg(&certs.cert_path)
+            .arg("--key")
+            .arg(&certs.key_path);
+    }
+
+    WalletProxyController::new(
+        alias.into(),
+        settings.clone(),
+        Arc::new(Mutex::new(Status::Running)),
+        command.spawn().unwrap(),
+    )
+    .map_err(Into::into)
+}
+
 fn wait_for_bootstrap(controller: &VitStationController) -> std::result::Result<(), Error> {
     std::thread::sleep(std::time::Duration::from_secs(5));
 
@@ -3,9 +3,10 @@ mod vit_station;
 mod wallet_proxy;
 
 pub use vit_station::{
-    generate_database, generate_random_database, DataError, DbGenerator,
-    Error as VitStationControllerError, ValidVotePlanParameters, ValidVotingTemplateGenerator,
-    VitStationController, VitStationSettings,
+    dump_settings_to_file, generate_database, generate_random_database, DataError, DataUpdate,
+    DbGenerator, Error as VitStationControllerError, ProposalUpdate, ReviewUpdate, UpdateError,
+    ValidVotePlanParameters, ValidVotingTemplateGenerator, VitStationController,
+    VitStationSettings, STORAGE, VIT_CONFIG,
 };
 
 pub use wallet_proxy::{
@@ -13,4 +14,7 @@ pub use wallet_proxy::{
     WalletProxySettings, WalletProxySpawnParams,
 };
 
-pub use main::{Error as VitControllerError, VitController, VitControllerBuilder};
+pub use main::{
+    spawn_wallet_proxy_process, start_vit_station_process, Error as VitControllerError,
+    VitController, VitControllerBuilder,
+};
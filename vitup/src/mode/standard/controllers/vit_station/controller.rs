@@ -1,10 +1,13 @@
 #![allow(dead_code)]
 
-use super::{RestClient, Result};
+use super::{DataUpdate, RestClient, Result, UpdateError};
+use crate::config::Config;
+use crate::mode::standard::kill_child;
 use jormungandr_automation::jormungandr::NodeAlias;
 use jormungandr_automation::jormungandr::Status;
 use jormungandr_automation::testing::NamedProcess;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::process::Child;
 use std::sync::{Arc, Mutex};
 use vit_servicing_station_lib::db::models::challenges::Challenge;
@@ -48,6 +51,20 @@ impl VitStationController {
         self.settings.address
     }
 
+    pub fn db_path(&self) -> PathBuf {
+        PathBuf::from(&self.settings.db_url)
+    }
+
+    /// Changes data of running servicing station directly in its database.
+    /// `running` is configuration which deployment was started with
+    pub fn update_data(
+        &self,
+        running: &Config,
+        update: &DataUpdate,
+    ) -> std::result::Result<Config, UpdateError> {
+        update.apply(self.db_path(), running)
+    }
+
     pub fn proposals(&self, group: &str) -> Result<Vec<FullProposalInfo>> {
         Ok(self.rest_client.proposals(group)?)
     }
@@ -56,12 +73,17 @@ impl VitStationController {
         Ok(self.rest_client.challenges()?)
     }
 
+    pub fn pid(&self) -> u32 {
+        self.process.id()
+    }
+
     pub fn as_named_process(&self) -> NamedProcess {
         NamedProcess::new(self.alias().to_string(), self.process.id() as usize)
     }
 
+    /// Kills and reaps process, so later waits only return its exit status
     pub fn shutdown(&mut self) {
-        let _ = self.process.kill();
+        let _ = kill_child(&mut self.process);
     }
 
     pub fn wait(&mut self) -> Result<Status> {
@@ -1,10 +1,11 @@
 use super::{
-    ArbitraryValidVotingTemplateGenerator, DbBuilder, ExternalValidVotingTemplateGenerator,
-    ValidVotePlanGenerator, ValidVotePlanParameters, ValidVotingTemplateGenerator,
+    DbBuilder, ExternalValidVotingTemplateGenerator, ValidVotePlanGenerator,
+    ValidVotePlanParameters, ValidVotingTemplateGenerator,
 };
 use crate::builders::utils::DeploymentTree;
+use crate::builders::{TemplatesError, TemplatesGenerator, VotePlanLayout};
 use crate::config::MigrationError;
-use crate::config::MigrationFilesBuilder;
+use crate::config::{Config, DbMigrator, MigrationFilesBuilder, ServiceVersion};
 use std::path::Path;
 use std::path::PathBuf;
 use thiserror::Error;
@@ -17,11 +18,15 @@ pub enum Error {
     DbBuilder(#[from] vit_servicing_station_tests::common::startup::db::DbBuilderError),
     #[error(transparent)]
     Migration(#[from] MigrationError),
+    #[error(transparent)]
+    Templates(#[from] TemplatesError),
 }
 
 pub struct DbGenerator {
     parameters: ValidVotePlanParameters,
     root: PathBuf,
+    service_version: Option<String>,
+    vote_plan_layout: VotePlanLayout,
 }
 
 impl DbGenerator {
@@ -29,35 +34,63 @@ impl DbGenerator {
         Self {
             parameters,
             root: root.as_ref().to_path_buf(),
+            service_version: None,
+            vote_plan_layout: VotePlanLayout::default(),
         }
     }
 
+    /// Per-definition vote options and challenges, see [`VotePlanLayout`]
+    pub fn vote_plan_layout(mut self, vote_plan_layout: VotePlanLayout) -> Self {
+        self.vote_plan_layout = vote_plan_layout;
+        self
+    }
+
+    /// Produce database with schema of given vit-servicing-station release instead of latest one
+    pub fn service_version<S: Into<String>>(mut self, service_version: S) -> Self {
+        self.service_version = Some(service_version.into());
+        self
+    }
+
     pub fn build(
         self,
         db_file: &Path,
         template_generator: &mut dyn ValidVotingTemplateGenerator,
     ) -> Result<(), Error> {
+        let service_version = match &self.service_version {
+            Some(version) => version.parse()?,
+            None => ServiceVersion::latest(),
+        };
         std::fs::File::create(&db_file)?;
 
         let migration_scripts_path = MigrationFilesBuilder::default().build(&self.root)?;
         println!("{:?}", migration_scripts_path);
         let mut generator = ValidVotePlanGenerator::new(self.parameters);
-        let snapshot = generator.build(template_generator);
+        let mut snapshot = generator.build(template_generator);
+        self.vote_plan_layout.apply(&mut snapshot);
         DbBuilder::new()
             .with_snapshot(&snapshot)
             .with_migrations_from(migration_scripts_path)
-            .build_into_path(db_file)
-            .map(|_| ())
-            .map_err(Into::into)
+            .build_into_path(db_file)?;
+
+        // data is generated with latest models, so older schemas are reached by reverting
+        for step in DbMigrator::open(db_file)?.migrate_to(&service_version)? {
+            println!("{}", step);
+        }
+        Ok(())
     }
 }
 
+/// Database with servicing station data drawn from config seed, see [`TemplatesGenerator`]
 pub fn generate_random_database(
     tree: &DeploymentTree,
     vit_parameters: ValidVotePlanParameters,
+    vote_plan_layout: &VotePlanLayout,
+    config: &Config,
 ) -> Result<(), Error> {
-    let mut template_generator = ArbitraryValidVotingTemplateGenerator::new();
+    let mut template_generator = TemplatesGenerator::from_config(config).build()?;
     DbGenerator::new(vit_parameters, tree.root_path())
+        .service_version(&config.service.version)
+        .vote_plan_layout(vote_plan_layout.clone())
         .build(&tree.database_path(), &mut template_generator)
 }
 
@@ -65,7 +98,11 @@ pub fn generate_database(
     tree: &DeploymentTree,
     vit_parameters: ValidVotePlanParameters,
     mut template_generator: ExternalValidVotingTemplateGenerator,
+    vote_plan_layout: &VotePlanLayout,
+    service_version: &str,
 ) -> Result<(), Error> {
     DbGenerator::new(vit_parameters, tree.root_path())
+        .service_version(service_version)
+        .vote_plan_layout(vote_plan_layout.clone())
         .build(&tree.database_path(), &mut template_generator)
 }
@@ -1,5 +1,6 @@
 mod controller;
 mod data;
+mod update;
 
 pub use vit_servicing_station_tests::common::{
     clients::RestClient,
@@ -17,6 +18,7 @@ pub use controller::{
 };
 pub use data::{generate_database, generate_random_database, DbGenerator, Error as DataError};
 use std::time::Duration;
+pub use update::{DataUpdate, Error as UpdateError, ProposalUpdate, ReviewUpdate};
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(custom_debug::Debug, thiserror::Error)]
@@ -0,0 +1,480 @@
+use crate::builders::build_next_funds;
+use crate::config::Config;
+use diesel::connection::SimpleConnection;
+use diesel::{Connection, ExpressionMethods, QueryDsl, RunQueryDsl, SqliteConnection};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use vit_servicing_station_lib::db::schema::{
+    community_advisors_reviews, funds, goals, proposal_community_choice_challenge,
+    proposal_simple_challenge, proposals,
+};
+
+/// Changes of servicing station data which can be applied on running deployment,
+/// directly in its database. Vote plans and block0 stay untouched, so `config`
+/// may differ from running one only in fund data (names, urls, goals, dates other
+/// than voting ones) and in next funds
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct DataUpdate {
+    #[serde(default)]
+    pub config: Option<Config>,
+    #[serde(default)]
+    pub proposals: Vec<ProposalUpdate>,
+    #[serde(default)]
+    pub reviews: Vec<ReviewUpdate>,
+}
+
+/// Texts of proposal identified by `proposal_id`. Missing fields are left as they are
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ProposalUpdate {
+    pub proposal_id: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub proposer_name: Option<String>,
+    #[serde(default)]
+    pub proposer_relevant_experience: Option<String>,
+    #[serde(default)]
+    pub solution: Option<String>,
+}
+
+/// Community advisor review identified by its `id`. Missing fields are left as they are
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ReviewUpdate {
+    pub id: i32,
+    #[serde(default)]
+    pub impact_alignment_rating_given: Option<i32>,
+    #[serde(default)]
+    pub impact_alignment_note: Option<String>,
+    #[serde(default)]
+    pub feasibility_rating_given: Option<i32>,
+    #[serde(default)]
+    pub feasibility_note: Option<String>,
+    #[serde(default)]
+    pub auditability_rating_given: Option<i32>,
+    #[serde(default)]
+    pub auditability_note: Option<String>,
+}
+
+impl DataUpdate {
+    /// Applies update in single transaction. Returns configuration which describes
+    /// deployment after update
+    pub fn apply<P: AsRef<Path>>(&self, db: P, running: &Config) -> Result<Config, Error> {
+        let config = self.config.clone().unwrap_or_else(|| running.clone());
+        let changes = requires_new_deployment(running, &config);
+        if !changes.is_empty() {
+            return Err(Error::RequiresNewDeployment(changes));
+        }
+
+        let db = db.as_ref();
+        let connection = SqliteConnection::establish(&db.to_string_lossy())
+            .map_err(|e| Error::Connection(db.to_path_buf(), e.to_string()))?;
+
+        connection.transaction::<_, Error, _>(|| {
+            if self.config.is_some() {
+                update_funds(&connection, &config)?;
+            }
+            for proposal in &self.proposals {
+                update_proposal(&connection, proposal)?;
+            }
+            for review in &self.reviews {
+                update_review(&connection, review)?;
+            }
+            Ok(())
+        })?;
+        Ok(config)
+    }
+}
+
+/// Settings which are part of block0 or were used to generate vote plans and proposals
+pub fn requires_new_deployment(running: &Config, requested: &Config) -> Vec<String> {
+    let running = serde_json::to_value(running).unwrap();
+    let requested = serde_json::to_value(requested).unwrap();
+    let mut changes = Vec::new();
+
+    if let (Value::Object(running), Value::Object(requested)) = (&running, &requested) {
+        for (section, value) in running {
+            // data is compared field by field below, metrics export does not affect deployment
+            if section == "data" || section == "metrics" {
+                continue;
+            }
+            if requested.get(section) != Some(value) {
+                changes.push(section.clone());
+            }
+        }
+    }
+
+    let current_fund = |config: &Value| config["data"]["current_fund"].clone();
+    let (running, requested) = (current_fund(&running), current_fund(&requested));
+    for field in [
+        "options",
+        "proposals",
+        "challenges",
+        "reviews",
+        "fund_id",
+        "groups",
+    ] {
+        if running[field] != requested[field] {
+            changes.push(format!("data.current_fund.{}", field));
+        }
+    }
+    changes
+}
+
+fn update_funds(connection: &SqliteConnection, config: &Config) -> Result<(), Error> {
+    let current_fund = &config.data.current_fund;
+    let fund_id = current_fund.fund_info.fund_id;
+    let dates = &current_fund.dates;
+
+    // voting_start, voting_end and tallying_end come from block0 and are not touched
+    diesel::update(funds::table.filter(funds::id.eq(fund_id)))
+        .set((
+            funds::fund_name.eq(&current_fund.fund_info.fund_name),
+            funds::results_url.eq(&current_fund.fund_info.results_url),
+            funds::survey_url.eq(&current_fund.fund_info.survey_url),
+            funds::voting_power_threshold.eq((current_fund.voting_power * 1_000_000) as i64),
+            funds::registration_snapshot_time.eq(dates.snapshot_time.unix_timestamp()),
+            funds::snapshot_start.eq(dates.snapshot_time.unix_timestamp()),
+            funds::next_registration_snapshot_time.eq(dates.next_snapshot_time.unix_timestamp()),
+            funds::next_fund_start_time.eq(dates.next_vote_start_time.unix_timestamp()),
+            funds::insight_sharing_start.eq(dates.insight_sharing_start.unix_timestamp()),
+            funds::proposal_submission_start.eq(dates.proposal_submission_start.unix_timestamp()),
+            funds::refine_proposals_start.eq(dates.refine_proposals_start.unix_timestamp()),
+            funds::finalize_proposals_start.eq(dates.finalize_proposals_start.unix_timestamp()),
+            funds::proposal_assessment_start.eq(dates.proposal_assessment_start.unix_timestamp()),
+            funds::assessment_qa_start.eq(dates.assessment_qa_start.unix_timestamp()),
+        ))
+        .execute(connection)?;
+
+    // older vit-servicing-station releases have no goals table
+    let has_goals = connection
+        .batch_execute("SELECT id FROM goals LIMIT 0")
+        .is_ok();
+    if has_goals {
+        diesel::delete(goals::table).execute(connection)?;
+        for (idx, goal_name) in current_fund.fund_info.goals.iter().enumerate() {
+            insert_goal(connection, fund_id, idx, goal_name)?;
+        }
+    }
+
+    diesel::delete(funds::table.filter(funds::id.ne(fund_id))).execute(connection)?;
+
+    for fund in build_next_funds(config) {
+        diesel::insert_into(funds::table)
+            .values((
+                funds::id.eq(fund.fund_id),
+                funds::fund_name.eq(&fund.fund_name),
+                funds::fund_goal.eq(&fund.fund_goal),
+                funds::registration_snapshot_time.eq(fund.dates.registration_snapshot_time),
+                funds::next_registration_snapshot_time
+                    .eq(fund.dates.next_registration_snapshot_time),
+                funds::voting_power_threshold.eq(fund.voting_power_threshold),
+                funds::fund_start_time.eq(fund.dates.voting_start),
+                funds::fund_end_time.eq(fund.dates.voting_tally_start),
+                funds::next_fund_start_time.eq(fund.dates.next_fund_start_time),
+                funds::insight_sharing_start.eq(fund.dates.insight_sharing_start),
+                funds::proposal_submission_start.eq(fund.dates.proposal_submission_start),
+                funds::refine_proposals_start.eq(fund.dates.refine_proposals_start),
+                funds::finalize_proposals_start.eq(fund.dates.finalize_proposals_start),
+                funds::proposal_assessment_start.eq(fund.dates.proposal_assessment_start),
+                funds::assessment_qa_start.eq(fund.dates.assessment_qa_start),
+                funds::snapshot_start.eq(fund.dates.registration_snapshot_time),
+                funds::voting_start.eq(fund.dates.voting_start),
+                funds::voting_end.eq(fund.dates.voting_tally_start),
+                funds::tallying_end.eq(fund.dates.voting_tally_end),
+                funds::results_url.eq(&fund.results_url),
+                funds::survey_url.eq(&fund.survey_url),
+            ))
+            .execute(connection)?;
+
+        if has_goals {
+            for (idx, goal) in fund.goals.iter().enumerate() {
+                insert_goal(connection, fund.fund_id, idx, &goal.goal_name)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn insert_goal(
+    connection: &SqliteConnection,
+    fund_id: i32,
+    idx: usize,
+    goal_name: &str,
+) -> Result<(), Error> {
+    diesel::insert_into(goals::table)
+        .values((
+            goals::id.eq(fund_id * 100 + idx as i32),
+            goals::goal_name.eq(goal_name),
+            goals::fund_id.eq(fund_id),
+        ))
+        .execute(connection)?;
+    Ok(())
+}
+
+fn update_proposal(connection: &SqliteConnection, proposal: &ProposalUpdate) -> Result<(), Error> {
+    let id = &proposal.proposal_id;
+    let found: i64 = proposals::table
+        .filter(proposals::proposal_id.eq(id))
+        .count()
+        .get_result(connection)?;
+    if found == 0 {
+        return Err(Error::ProposalNotFound(id.clone()));
+    }
+
+    if proposal.title.is_some()
+        || proposal.summary.is_some()
+        || proposal.url.is_some()
+        || proposal.proposer_name.is_some()
+        || proposal.proposer_relevant_experience.is_some()
+    {
+        diesel::update(proposals::table.filter(proposals::proposal_id.eq(id)))
+            .set((
+                proposal
+                    .title
+                    .as_ref()
+                    .map(|title| proposals::proposal_title.eq(title)),
+                proposal
+                    .summary
+                    .as_ref()
+                    .map(|summary| proposals::proposal_summary.eq(summary)),
+                proposal
+                    .url
+                    .as_ref()
+                    .map(|url| proposals::proposal_url.eq(url)),
+                proposal
+                    .proposer_name
+                    .as_ref()
+                    .map(|name| proposals::proposer_name.eq(name)),
+                proposal
+                    .proposer_relevant_experience
+                    .as_ref()
+                    .map(|experience| proposals::proposer_relevant_experience.eq(experience)),
+            ))
+            .execute(connection)?;
+    }
+
+    // proposal has row only in table of its challenge type. Community choice
+    // proposals describe their solution in brief
+    if let Some(solution) = &proposal.solution {
+        diesel::update(
+            proposal_simple_challenge::table.filter(proposal_simple_challenge::proposal_id.eq(id)),
+        )
+        .set(proposal_simple_challenge::proposal_solution.eq(solution))
+        .execute(connection)?;
+        diesel::update(
+            proposal_community_choice_challenge::table
+                .filter(proposal_community_choice_challenge::proposal_id.eq(id)),
+        )
+        .set(proposal_community_choice_challenge::proposal_brief.eq(solution))
+        .execute(connection)?;
+    }
+    Ok(())
+}
+
+fn update_review(connection: &SqliteConnection, review: &ReviewUpdate) -> Result<(), Error> {
+    let found: i64 = community_advisors_reviews::table
+        .filter(community_advisors_reviews::id.eq(review.id))
+        .count()
+        .get_result(connection)?;
+    if found == 0 {
+        return Err(Error::ReviewNotFound(review.id));
+    }
+
+    if review.impact_alignment_rating_given.is_some()
+        || review.impact_alignment_note.is_some()
+        || review.feasibility_rating_given.is_some()
+        || review.feasibility_note.is_some()
+        || review.auditability_rating_given.is_some()
+        || review.auditability_note.is_some()
+    {
+        diesel::update(
+            community_advisors_reviews::table.filter(community_advisors_reviews::id.eq(review.id)),
+        )
+        .set((
+            review
+                .impact_alignment_rating_given
+                .map(|rating| community_advisors_reviews::impact_alignment_rating_given.eq(rating)),
+            review
+                .impact_alignment_note
+                .as_ref()
+                .map(|note| community_advisors_reviews::impact_alignment_note.eq(note)),
+            review
+                .feasibility_rating_given
+                .map(|rating| community_advisors_reviews::feasibility_rating_given.eq(rating)),
+            review
+                .feasibility_note
+                .as_ref()
+                .map(|note| community_advisors_reviews::feasibility_note.eq(note)),
+            review
+                .auditability_rating_given
+                .map(|rating| community_advisors_reviews::auditability_rating_given.eq(rating)),
+            review
+                .auditability_note
+                .as_ref()
+                .map(|note| community_advisors_reviews::auditability_note.eq(note)),
+        ))
+        .execute(connection)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(
+        "changes of {0:?} require new block0, please restart deployment with new configuration"
+    )]
+    RequiresNewDeployment(Vec<String>),
+    #[error("no deployment is running")]
+    NoRunningDeployment,
+    #[error("proposal '{0}' not found")]
+    ProposalNotFound(String),
+    #[error("review with id {0} not found")]
+    ReviewNotFound(i32),
+    #[error("cannot open database {0}: {1}")]
+    Connection(PathBuf, String),
+    #[error(transparent)]
+    Db(#[from] diesel::result::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ConfigBuilder, DbMigrator, ServiceVersion};
+    use time::ext::NumericalDuration;
+
+    #[test]
+    fn static_data_is_updated_without_new_block0() {
+        let running = ConfigBuilder::default().build();
+
+        let mut requested = running.clone();
+        requested.data.current_fund.fund_info.fund_name = "Demo".to_string();
+        requested.data.current_fund.dates.proposal_assessment_start += 10.minutes();
+        assert!(requires_new_deployment(&running, &requested).is_empty());
+
+        requested.data.current_fund.proposals += 1;
+        requested.blockchain.slot_duration += 1;
+        assert_eq!(
+            requires_new_deployment(&running, &requested),
+            vec![
+                "blockchain".to_string(),
+                "data.current_fund.proposals".to_string()
+            ]
+        );
+
+        let db = assert_fs::NamedTempFile::new("storage.db").unwrap();
+        DbMigrator::open(db.path())
+            .unwrap()
+            .migrate_to(&ServiceVersion::latest())
+            .unwrap();
+
+        let update = DataUpdate {
+            config: Some(requested),
+            ..Default::default()
+        };
+        assert!(matches!(
+            update.apply(db.path(), &running),
+            Err(Error::RequiresNewDeployment(_))
+        ));
+
+        let update = DataUpdate {
+            proposals: vec![ProposalUpdate {
+                proposal_id: "missing".to_string(),
+                title: Some("title".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert!(matches!(
+            update.apply(db.path(), &running),
+            Err(Error::ProposalNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn proposals_and_reviews_are_updated_in_database() {
+        let db = assert_fs::NamedTempFile::new("storage.db").unwrap();
+        DbMigrator::open(db.path())
+            .unwrap()
+            .migrate_to(&ServiceVersion::latest())
+            .unwrap();
+        let connection = SqliteConnection::establish(&db.path().to_string_lossy()).unwrap();
+        connection
+            .batch_execute(
+                "INSERT INTO proposals (proposal_id, proposal_category, proposal_title, \
+                 proposal_summary, proposal_public_key, proposal_funds, proposal_url, \
+                 proposal_files_url, proposal_impact_score, proposer_name, proposer_contact, \
+                 proposer_url, proposer_relevant_experience, chain_proposal_id, \
+                 chain_vote_options, challenge_id) VALUES \
+                 ('simple', '', 'title', 'summary', '', 1, '', '', 1, '', '', '', '', x'01', 'yes,no', 1), \
+                 ('community', '', 'title', 'summary', '', 1, '', '', 1, '', '', '', '', x'02', 'yes,no', 2);
+                 INSERT INTO proposal_simple_challenge VALUES ('simple', 'solution');
+                 INSERT INTO proposal_community_choice_challenge (proposal_id, proposal_brief) \
+                 VALUES ('community', 'brief');
+                 INSERT INTO community_advisors_reviews VALUES \
+                 (1, 1, 'assessor', 1, 'note', 2, 'note', 3, 'note', 0);",
+            )
+            .unwrap();
+
+        let update = DataUpdate {
+            proposals: vec![
+                ProposalUpdate {
+                    proposal_id: "simple".to_string(),
+                    title: Some("new title".to_string()),
+                    solution: Some("new solution".to_string()),
+                    ..Default::default()
+                },
+                ProposalUpdate {
+                    proposal_id: "community".to_string(),
+                    solution: Some("new brief".to_string()),
+                    ..Default::default()
+                },
+            ],
+            reviews: vec![ReviewUpdate {
+                id: 1,
+                feasibility_rating_given: Some(4),
+                auditability_note: Some("new note".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        update
+            .apply(db.path(), &ConfigBuilder::default().build())
+            .unwrap();
+
+        let (title, summary): (String, String) = proposals::table
+            .filter(proposals::proposal_id.eq("simple"))
+            .select((proposals::proposal_title, proposals::proposal_summary))
+            .first(&connection)
+            .unwrap();
+        assert_eq!(title, "new title");
+        assert_eq!(summary, "summary");
+
+        let solution: Option<String> = proposal_simple_challenge::table
+            .select(proposal_simple_challenge::proposal_solution)
+            .first(&connection)
+            .unwrap();
+        assert_eq!(solution.as_deref(), Some("new solution"));
+        let brief: Option<String> = proposal_community_choice_challenge::table
+            .select(proposal_community_choice_challenge::proposal_brief)
+            .first(&connection)
+            .unwrap();
+        assert_eq!(brief.as_deref(), Some("new brief"));
+
+        let review: (i32, i32, String) = community_advisors_reviews::table
+            .filter(community_advisors_reviews::id.eq(1))
+            .select((
+                community_advisors_reviews::impact_alignment_rating_given,
+                community_advisors_reviews::feasibility_rating_given,
+                community_advisors_reviews::auditability_note,
+            ))
+            .first(&connection)
+            .unwrap();
+        assert_eq!(review, (1, 4, "new note".to_string()));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::mode::standard::kill_child;
 use jormungandr_automation::jormungandr::{NodeAlias, Status};
 use jormungandr_automation::testing::NamedProcess;
 use std::process::Child;
@@ -72,6 +73,10 @@ impl WalletProxyController {
         self.settings.address()
     }
 
+    pub fn pid(&self) -> u32 {
+        self.process.id()
+    }
+
     pub fn as_named_process(&self) -> NamedProcess {
         NamedProcess::new(self.alias().to_string(), self.process.id() as usize)
     }
@@ -80,8 +85,9 @@ impl WalletProxyController {
         &self.settings
     }
 
+    /// Kills and reaps process, so later waits only return its exit status
     pub fn shutdown(&mut self) {
-        let _ = self.process.kill();
+        let _ = kill_child(&mut self.process);
     }
 }
 
@@ -0,0 +1,356 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::process::Child;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Fault injected into node, vit station or wallet proxy of running deployment
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum DisruptionAction {
+    /// SIGKILL, process stays down until restarted
+    Kill,
+    /// kills process (if still running) and starts it again with the same settings
+    Restart,
+    /// SIGSTOP, process is frozen but keeps its sockets open
+    Pause,
+    /// SIGCONT, reverts pause
+    Resume,
+    /// restarts node without trusted peers and p2p listen address, so it can
+    /// neither reach nor be reached by the rest of network. Nodes only
+    Isolate,
+    /// restarts isolated node with its original trusted peers
+    Reconnect,
+}
+
+impl DisruptionAction {
+    /// Action which brings target back, if there is any
+    pub fn revert(&self) -> Option<Self> {
+        match self {
+            Self::Kill => Some(Self::Restart),
+            Self::Pause => Some(Self::Resume),
+            Self::Isolate => Some(Self::Reconnect),
+            Self::Restart | Self::Resume | Self::Reconnect => None,
+        }
+    }
+}
+
+impl FromStr for DisruptionAction {
+    type Err = Error;
+
+    fn from_str(action: &str) -> Result<Self, Self::Err> {
+        match action.to_lowercase().as_str() {
+            "kill" => Ok(Self::Kill),
+            "restart" => Ok(Self::Restart),
+            "pause" => Ok(Self::Pause),
+            "resume" => Ok(Self::Resume),
+            "isolate" => Ok(Self::Isolate),
+            "reconnect" => Ok(Self::Reconnect),
+            _ => Err(Error::UnknownAction(action.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for DisruptionAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Single disruption request, e.g. `{ "target": "Leader1", "action": "pause", "delay": 60, "duration": 30 }`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Disruption {
+    /// node alias, vit station alias (`vit_station`) or wallet proxy alias
+    pub target: String,
+    pub action: DisruptionAction,
+    /// seconds to wait before action is applied
+    #[serde(default)]
+    pub delay: u64,
+    /// seconds after which action is reverted (kill -> restart, pause -> resume,
+    /// isolate -> reconnect). By default it is never reverted
+    #[serde(default)]
+    pub duration: Option<u64>,
+}
+
+impl Disruption {
+    pub fn new<S: Into<String>>(target: S, action: DisruptionAction) -> Self {
+        Self {
+            target: target.into(),
+            action,
+            delay: 0,
+            duration: None,
+        }
+    }
+
+    fn reverted(&self) -> Option<Self> {
+        self.action
+            .revert()
+            .map(|action| Self::new(self.target.clone(), action))
+    }
+}
+
+impl fmt::Display for Disruption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.action, self.target)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PendingDisruption {
+    /// seconds left until disruption is applied
+    pub due_in: u64,
+    pub disruption: Disruption,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AppliedDisruption {
+    pub disruption: Disruption,
+    /// error message if disruption could not be applied
+    pub error: Option<String>,
+}
+
+/// Snapshot of schedule returned by service mode REST API
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DisruptionStatus {
+    pub pending: Vec<PendingDisruption>,
+    pub applied: Vec<AppliedDisruption>,
+}
+
+/// Disruptions waiting for their time together with log of already applied ones
+#[derive(Default)]
+pub struct DisruptionSchedule {
+    pending: Vec<(Instant, Disruption)>,
+    applied: Vec<AppliedDisruption>,
+}
+
+impl DisruptionSchedule {
+    pub fn schedule(&mut self, disruption: Disruption) {
+        let due = Instant::now() + Duration::from_secs(disruption.delay);
+        self.pending.push((due, disruption));
+    }
+
+    /// Removes disruptions whose time has come, in order of their due time.
+    /// Reverts of disruptions with duration are scheduled right away
+    pub fn take_due(&mut self) -> Vec<Disruption> {
+        let now = Instant::now();
+        let (mut due, pending): (Vec<_>, Vec<_>) = self
+            .pending
+            .drain(..)
+            .partition(|(instant, _)| *instant <= now);
+        self.pending = pending;
+        due.sort_by_key(|(instant, _)| *instant);
+
+        let due: Vec<Disruption> = due.into_iter().map(|(_, disruption)| disruption).collect();
+        for disruption in &due {
+            if let (Some(duration), Some(mut revert)) = (disruption.duration, disruption.reverted())
+            {
+                revert.delay = duration;
+                self.schedule(revert);
+            }
+        }
+        due
+    }
+
+    pub fn record(&mut self, disruption: Disruption, result: &Result<(), Error>) {
+        self.applied.push(AppliedDisruption {
+            disruption,
+            error: result.as_ref().err().map(ToString::to_string),
+        });
+    }
+
+    pub fn pending(&self) -> Vec<PendingDisruption> {
+        let now = Instant::now();
+        let mut pending: Vec<PendingDisruption> = self
+            .pending
+            .iter()
+            .map(|(instant, disruption)| PendingDisruption {
+                due_in: instant.saturating_duration_since(now).as_secs(),
+                disruption: disruption.clone(),
+            })
+            .collect();
+        pending.sort_by_key(|pending| pending.due_in);
+        pending
+    }
+
+    pub fn applied(&self) -> &[AppliedDisruption] {
+        &self.applied
+    }
+
+    pub fn status(&self) -> DisruptionStatus {
+        DisruptionStatus {
+            pending: self.pending(),
+            applied: self.applied.clone(),
+        }
+    }
+
+    pub fn clear_pending(&mut self) {
+        self.pending.clear();
+    }
+
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.applied.clear();
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Signal {
+    Kill,
+    Stop,
+    Cont,
+}
+
+impl Signal {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Kill => "KILL",
+            Self::Stop => "STOP",
+            Self::Cont => "CONT",
+        }
+    }
+}
+
+#[cfg(unix)]
+impl From<Signal> for nix::sys::signal::Signal {
+    fn from(signal: Signal) -> Self {
+        match signal {
+            Signal::Kill => Self::SIGKILL,
+            Signal::Stop => Self::SIGSTOP,
+            Signal::Cont => Self::SIGCONT,
+        }
+    }
+}
+
+/// Sends signal to process. Process killed this way is not reaped, it is up to owner
+/// of its handle to wait for it (see [`kill_child`])
+#[cfg(unix)]
+pub fn send_signal(pid: u32, signal: Signal) -> Result<(), Error> {
+    use nix::sys::signal::kill;
+    use nix::unistd::Pid;
+
+    kill(
+        Pid::from_raw(pid as i32),
+        nix::sys::signal::Signal::from(signal),
+    )
+    .map_err(|errno| Error::Signal {
+        pid,
+        signal: signal.name(),
+        message: errno.desc().to_string(),
+    })
+}
+
+/// Only unix processes can be paused and resumed. Processes with known handle
+/// can still be killed with [`kill_child`]
+#[cfg(not(unix))]
+pub fn send_signal(_pid: u32, signal: Signal) -> Result<(), Error> {
+    Err(Error::UnsupportedSignal(signal.name()))
+}
+
+/// Kills child process and waits for it, so it does not linger as zombie.
+/// Process which already exited is only reaped
+pub fn kill_child(child: &mut Child) -> Result<(), Error> {
+    if child.try_wait()?.is_none() {
+        child.kill()?;
+        child.wait()?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("unknown disruption action '{0}', expected one of: kill, restart, pause, resume, isolate, reconnect")]
+    UnknownAction(String),
+    #[error("no node, vit station or wallet proxy with alias '{0}'")]
+    UnknownTarget(String),
+    #[error("{action} is not supported for '{target}'")]
+    UnsupportedAction {
+        target: String,
+        action: DisruptionAction,
+    },
+    #[error("no deployment is running")]
+    NoRunningDeployment,
+    #[error("cannot send SIG{signal} to process {pid}: {message}")]
+    Signal {
+        pid: u32,
+        signal: &'static str,
+        message: String,
+    },
+    #[error("SIG{0} is not supported on this platform, pause and resume need unix")]
+    UnsupportedSignal(&'static str),
+    #[error("cannot restart '{0}': {1}")]
+    Restart(String, String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn due_disruption_schedules_its_revert() {
+        let mut schedule = DisruptionSchedule::default();
+        schedule.schedule(Disruption {
+            target: "Leader1".to_string(),
+            action: DisruptionAction::Pause,
+            delay: 0,
+            duration: Some(60),
+        });
+        schedule.schedule(Disruption {
+            delay: 120,
+            ..Disruption::new("wallet_proxy", DisruptionAction::Kill)
+        });
+
+        let due = schedule.take_due();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].action, DisruptionAction::Pause);
+
+        let pending = schedule.pending();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].disruption.target, "Leader1");
+        assert_eq!(pending[0].disruption.action, DisruptionAction::Resume);
+        assert!(pending[0].due_in <= 60);
+        assert_eq!(pending[1].disruption.target, "wallet_proxy");
+        assert!(schedule.take_due().is_empty());
+
+        assert_eq!(
+            "Isolate".parse::<DisruptionAction>().unwrap(),
+            DisruptionAction::Isolate
+        );
+        assert!("freeze".parse::<DisruptionAction>().is_err());
+    }
+
+    #[test]
+    fn disruptions_are_read_from_configuration() {
+        let config: crate::config::Config = serde_json::from_str(
+            r#"{ "disruptions": [{ "target": "wallet_proxy", "action": "pause", "duration": 30 }] }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.disruptions,
+            vec![Disruption {
+                duration: Some(30),
+                ..Disruption::new("wallet_proxy", DisruptionAction::Pause)
+            }]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn killed_child_is_reaped() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+
+        send_signal(child.id(), Signal::Stop).unwrap();
+        send_signal(child.id(), Signal::Cont).unwrap();
+        kill_child(&mut child).unwrap();
+
+        // status of reaped process is cached by handle
+        assert!(child.try_wait().unwrap().is_some());
+        // killing again only returns cached status
+        kill_child(&mut child).unwrap();
+    }
+}
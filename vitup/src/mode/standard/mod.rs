@@ -1,5 +1,10 @@
 mod controllers;
+mod disruption;
 mod settings;
 
 pub use controllers::*;
+pub use disruption::{
+    kill_child, send_signal, AppliedDisruption, Disruption, DisruptionAction, DisruptionSchedule,
+    DisruptionStatus, Error as DisruptionError, PendingDisruption, Signal,
+};
 pub use settings::VitSettings;
@@ -52,17 +52,21 @@ pub fn vitup_setup(
         std::fs::remove_dir_all(&testing_directory).unwrap();
     }
 
+    let protocol = config.protocol(&testing_directory)?;
+
     let (controller, vit_parameters) = VitBackendSettingsBuilder::default()
         .config(config)
         .session_settings(session_settings.clone())
+        .protocol(protocol.clone())
         .build()?;
 
     let network_spawn_params = NetworkSpawnParams::new(
         endpoint.to_string(),
-        config.protocol(&testing_directory)?,
+        protocol,
         session_settings,
         None,
         config.service.version.clone(),
+        config.topology.clone(),
         testing_directory,
     );
     Ok((controller, vit_parameters, network_spawn_params))